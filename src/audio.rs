@@ -0,0 +1,113 @@
+// 导入Bevy的主要组件
+use bevy::prelude::*;
+
+// 导入玩家模块中的蓄力音效标记
+use crate::player::AccumulationSound;
+// 导入全局音量资源
+use crate::settings::Volume;
+// 导入游戏音效资源
+use crate::ui::GameSounds;
+
+/// 完美落地时每次连击使成功音效提高的播放速度
+const LAND_SOUND_PITCH_STEP: f32 = 0.1;
+/// 成功音效音调最多随连击提高的次数
+const LAND_SOUND_MAX_STEPS: u32 = 5;
+/// 每次连击使得分音效提高的播放速度
+const SCORE_SOUND_PITCH_STEP: f32 = 0.08;
+/// 得分音效音调最多随连击提高的次数
+const SCORE_SOUND_MAX_STEPS: u32 = 8;
+/// 分数里程碑音效的播放速度，使用高音调的成功音效
+const MILESTONE_SOUND_SPEED: f32 = 1.5;
+
+/// 游戏事件，由游戏逻辑系统发送，音效系统统一读取并播放对应音效
+///
+/// 游戏逻辑只负责描述发生了什么，不直接生成音效实体，新增或替换音效时只需修改`play_sounds`
+#[derive(Debug, Clone, Copy, PartialEq, Event)]
+pub enum GameEvent {
+    RunStart,    // 一局开始，玩家出生在第一个平台上
+    ChargeStart, // 开始蓄力
+    Jump,        // 松开蓄力起跳
+    // 成功落在平台上
+    Land {
+        perfect: bool, // 是否完美落地
+        combo: u32,    // 落地后的连续完美落地次数
+    },
+    Fall,        // 开始摔落
+    // 落地得分，飘分出现时发送
+    ScoreUp {
+        combo: u32, // 得分后的连续完美落地次数
+    },
+    Milestone,   // 分数越过里程碑
+}
+
+/// 根据游戏事件播放音效
+///
+/// 统一应用全局音量和按连击升高的音调；静音由全局音量控制，之后生成的音效都不会发声。
+/// 在`PostUpdate`中运行，本帧所有游戏逻辑发送的事件都会在同一帧播放
+///
+/// # 参数
+/// - `commands`: 命令系统，用于生成音效实体
+/// - `game_events`: 游戏事件读取器
+/// - `game_sounds`: 游戏音效资源
+/// - `volume`: 全局音量资源
+pub fn play_sounds(
+    mut commands: Commands,
+    mut game_events: EventReader<GameEvent>,
+    game_sounds: Res<GameSounds>,
+    volume: Res<Volume>,
+) {
+    for event in game_events.read() {
+        match *event {
+            GameEvent::RunStart => {
+                commands.spawn((
+                    AudioPlayer(game_sounds.start.clone()), // 开始音效
+                    volume.apply(PlaybackSettings::DESPAWN), // 播放结束后自动销毁
+                ));
+            }
+            GameEvent::ChargeStart => {
+                // 蓄力音效循环播放，蓄力结束时由stop_accumulation_sound销毁
+                commands.spawn((
+                    AccumulationSound, // 标记为蓄力音效
+                    AudioPlayer(game_sounds.accumulation.clone()), // 蓄力音效资源
+                    volume.apply(PlaybackSettings::LOOP), // 循环播放设置
+                ));
+            }
+            // 起跳暂无音效，蓄力音效随蓄力结束停止
+            GameEvent::Jump => {}
+            GameEvent::Land { perfect, combo } => {
+                // 完美落地时音调随连击数升高
+                let speed = if perfect {
+                    1.0 + LAND_SOUND_PITCH_STEP * combo.min(LAND_SOUND_MAX_STEPS) as f32
+                } else {
+                    1.0
+                };
+                commands.spawn((
+                    AudioPlayer(game_sounds.success.clone()),
+                    volume.apply(PlaybackSettings::DESPAWN.with_speed(speed)),
+                ));
+            }
+            GameEvent::Fall => {
+                commands.spawn((
+                    AudioPlayer(game_sounds.fall.clone()),
+                    volume.apply(PlaybackSettings::DESPAWN),
+                ));
+            }
+            GameEvent::ScoreUp { combo } => {
+                // 得分音效，播放速度随连击提高，音调随之升高
+                let speed =
+                    1.0 + SCORE_SOUND_PITCH_STEP * combo.min(SCORE_SOUND_MAX_STEPS) as f32;
+                commands.spawn((
+                    AudioPlayer(game_sounds.score.clone()),
+                    volume.apply(PlaybackSettings::DESPAWN.with_speed(speed)),
+                ));
+            }
+            GameEvent::Milestone => {
+                // 高音调的成功音效
+                commands.spawn((
+                    AudioPlayer(game_sounds.success.clone()),
+                    volume.apply(PlaybackSettings::DESPAWN.with_speed(MILESTONE_SOUND_SPEED)),
+                ));
+            }
+        }
+    }
+}
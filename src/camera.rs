@@ -4,11 +4,72 @@ use crate::player::{FallState, JumpState, Player, INITIAL_PLAYER_POS};
 use bevy::core_pipeline::bloom::Bloom;
 // 导入Bevy的主要组件
 use bevy::prelude::*;
+// 导入方向光的阴影级联设置
+use bevy::pbr::CascadeShadowConfig;
+// 导入鼠标移动和滚轮的累计量，用于拍照模式的相机环绕
+use bevy::input::mouse::{AccumulatedMouseMotion, AccumulatedMouseScroll};
+// 导入数学常量，用于默认视野角度和限制俯仰角
+use std::f32::consts::{FRAC_PI_2, FRAC_PI_4};
+// 导入地面纹理的采样设置
+use bevy::image::{ImageAddressMode, ImageFilterMode, ImageSampler, ImageSamplerDescriptor};
+// 导入二维仿射变换，用于平铺地面纹理
+use bevy::math::Affine2;
+// 导入正交投影的缩放模式
+use bevy::render::camera::ScalingMode;
+// 导入纹理相关类型，用于生成地面棋盘格纹理
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+
+// 导入分数和连击加速资源，用于随分数缩放相机和调整跟随速度
+use crate::ui::{ChainSpeed, Score};
+// 导入正交相机和抗锯齿设置
+use crate::settings::{BloomLevel, MsaaLevel, OrthographicCamera, ShadowQuality};
 
 /// 相机初始位置常量
 /// 设置为俯视角度：X=-5，Y=8，Z=5，提供良好的游戏视角
 pub const INITIAL_CAMERA_POS: Vec3 = Vec3::new(-5.0, 8.0, 5.0);
 
+/// 相机配置资源
+/// 
+/// 可在运行时修改，用于调整视角和跟随速度
+#[derive(Debug, Resource)]
+pub struct CameraConfig {
+    /// 相机相对玩家的偏移，决定视角方向和距离
+    pub offset: Vec3,
+    /// 跟随速度，每一步移动剩余距离的比例（0.0到1.0，越大追赶越快）
+    pub follow_speed: f32,
+    /// 是否随分数增长逐渐拉远相机
+    pub zoom_with_score: bool,
+    /// 每得1分相机偏移放大的比例
+    pub zoom_per_point: f32,
+    /// 相机偏移最大放大倍数
+    pub max_zoom: f32,
+    /// 透视投影的垂直视野角度（弧度），正交投影不受影响
+    pub fov: f32,
+}
+
+impl Default for CameraConfig {
+    fn default() -> Self {
+        Self {
+            offset: INITIAL_CAMERA_POS,
+            follow_speed: 0.05,
+            zoom_with_score: false,
+            zoom_per_point: 0.01,
+            max_zoom: 1.5,
+            fov: FRAC_PI_4,
+        }
+    }
+}
+
+impl CameraConfig {
+    /// 根据偏移计算相机朝向，相机始终看向玩家
+    pub fn rotation(&self) -> Quat {
+        Transform::from_translation(self.offset)
+            .looking_at(Vec3::ZERO, Vec3::Y)
+            .rotation
+    }
+}
+
 /// 相机移动状态资源
 /// 用于控制相机平滑跟随玩家的逻辑
 #[derive(Debug, Resource)]
@@ -17,6 +78,10 @@ pub struct CameraMoveState {
     step: Vec3,
     /// 记录玩家位置，用于检测移动
     player_pos: Vec3,
+    /// 相机跟随位置（不含震动偏移和缩放）
+    translation: Vec3,
+    /// 当前相机偏移的放大倍数，平滑趋近目标值
+    zoom: f32,
 }
 
 /// 为CameraMoveState实现默认初始化
@@ -25,62 +90,398 @@ impl Default for CameraMoveState {
         Self {
             step: Vec3::ZERO,  // 初始步长为零向量
             player_pos: INITIAL_PLAYER_POS,  // 初始位置设为玩家初始位置
+            translation: INITIAL_CAMERA_POS,  // 初始跟随位置设为相机初始位置
+            zoom: 1.0,  // 初始不缩放
+        }
+    }
+}
+
+/// 光照主题资源，决定方向光、环境光和背景颜色
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Resource)]
+pub enum LightingTheme {
+    #[default]
+    Day,  // 白天
+    Dusk, // 黄昏
+    Night, // 夜晚
+}
+
+/// 可选的光照主题，按设置界面中的切换顺序排列
+const LIGHTING_THEMES: [LightingTheme; 3] =
+    [LightingTheme::Day, LightingTheme::Dusk, LightingTheme::Night];
+
+impl LightingTheme {
+    /// 根据序号获取光照主题，序号超出范围时使用白天主题
+    pub fn from_index(index: usize) -> Self {
+        LIGHTING_THEMES.get(index).copied().unwrap_or_default()
+    }
+
+    /// 光照主题的序号，用于写入存档
+    pub fn index(&self) -> usize {
+        LIGHTING_THEMES
+            .iter()
+            .position(|theme| theme == self)
+            .unwrap_or(0)
+    }
+
+    /// 切换顺序中的下一个光照主题
+    pub fn next(&self) -> Self {
+        Self::from_index((self.index() + 1) % LIGHTING_THEMES.len())
+    }
+
+    /// 光照主题名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Day => "Day",
+            Self::Dusk => "Dusk",
+            Self::Night => "Night",
+        }
+    }
+
+    /// 生成方向光组件
+    /// 
+    /// # 参数
+    /// - `shadow_quality`: 阴影质量设置，决定方向光是否投射阴影
+    fn directional_light(&self, shadow_quality: &ShadowQuality) -> DirectionalLight {
+        let (color, illuminance) = match self {
+            Self::Day => (Color::WHITE, 15000.0),
+            Self::Dusk => (Color::srgb(1.0, 0.7, 0.45), 6000.0), // 暖橙色斜阳
+            Self::Night => (Color::srgb(0.6, 0.7, 1.0), 1500.0), // 冷色月光
+        };
+        DirectionalLight {
+            color,
+            illuminance,  // 设置光照强度
+            shadows_enabled: shadow_quality.shadows_enabled(),  // 按阴影质量设置启用阴影
+            ..default()
+        }
+    }
+
+    /// 方向光的位置和朝向，方向光只有朝向影响光照
+    fn light_transform(&self) -> Transform {
+        let position = match self {
+            Self::Day => Vec3::new(2.0, 10.0, 8.0),
+            Self::Dusk => Vec3::new(-8.0, 3.0, 6.0), // 低角度，影子更长
+            Self::Night => Vec3::new(4.0, 10.0, -6.0),
+        };
+        Transform::from_translation(position).looking_at(Vec3::ZERO, Vec3::Y)
+    }
+
+    /// 环境光
+    fn ambient_light(&self) -> AmbientLight {
+        match self {
+            Self::Day => AmbientLight::default(),
+            Self::Dusk => AmbientLight {
+                color: Color::srgb(1.0, 0.75, 0.6),
+                brightness: 150.0,
+            },
+            Self::Night => AmbientLight {
+                color: Color::srgb(0.4, 0.5, 0.9),
+                brightness: 60.0,
+            },
+        }
+    }
+
+    /// 背景颜色
+    fn clear_color(&self) -> ClearColor {
+        match self {
+            Self::Day => ClearColor::default(),
+            Self::Dusk => ClearColor(Color::srgb(0.85, 0.55, 0.45)),
+            Self::Night => ClearColor(Color::srgb(0.05, 0.07, 0.15)),
         }
     }
 }
 
+/// 标记方向光（太阳光）的组件
+#[derive(Debug, Component)]
+pub struct SunLight;
+
+/// 相机震动资源
+/// 
+/// `trauma`取值0.0到1.0，值越大震动越强，会随时间衰减到0
+#[derive(Debug, Default, Resource)]
+pub struct CameraShake {
+    pub trauma: f32,
+}
+
+/// 正交相机视野的垂直高度（世界单位），与透视相机下平台的大小接近
+const ORTHOGRAPHIC_VIEWPORT_HEIGHT: f32 = 9.0;
+
+/// 相机震动最大位移
+const CAMERA_SHAKE_MAX_OFFSET: f32 = 0.3;
+/// 相机震动最大旋转角度（弧度）
+const CAMERA_SHAKE_MAX_ROLL: f32 = 0.05;
+/// 相机震动每秒衰减量
+const CAMERA_SHAKE_DECAY: f32 = 1.5;
+
 /// 设置游戏相机和光照
 /// 
 /// 此函数在游戏启动时执行，创建方向光和主相机
-pub fn setup_camera(mut commands: Commands) {
-    // 创建方向光（模拟太阳光）
-    // TODO: 未来可以添加更复杂的阴影设置
+pub fn setup_camera(
+    mut commands: Commands,
+    camera_config: Res<CameraConfig>,
+    orthographic: Res<OrthographicCamera>,
+    lighting_theme: Res<LightingTheme>,
+    msaa_level: Res<MsaaLevel>,
+    shadow_quality: Res<ShadowQuality>,
+    bloom_level: Res<BloomLevel>,
+) {
+    // 创建方向光（模拟太阳光），颜色、强度和方向由光照主题决定，阴影由阴影质量决定
     commands.spawn((
-        DirectionalLight {
-            illuminance: 15000.0,  // 设置光照强度
-            shadows_enabled: true,  // 启用阴影
-            ..default()
-        },
-        // 设置光源位置和朝向
-        Transform::from_xyz(2.0, 10.0, 8.0).looking_at(Vec3::ZERO, Vec3::Y),
+        lighting_theme.directional_light(&shadow_quality),
+        lighting_theme.light_transform(),  // 设置光源位置和朝向
+        shadow_quality.cascade_shadow_config(), // 阴影级联数量和距离
+        SunLight,
     ));
+    // 阴影贴图分辨率对所有方向光生效
+    commands.insert_resource(shadow_quality.shadow_map());
+    // 与光照主题匹配的环境光和背景颜色
+    commands.insert_resource(lighting_theme.ambient_light());
+    commands.insert_resource(lighting_theme.clear_color());
 
     // 创建主相机
-    commands.spawn((
+    spawn_game_camera(&mut commands, &camera_config, &orthographic, &msaa_level, &bloom_level);
+}
+
+/// 生成主相机
+/// 
+/// 根据设置使用透视投影或正交投影，并应用抗锯齿等级和泛光强度
+/// 
+/// # 参数
+/// - `commands`: 命令系统，用于创建实体
+/// - `camera_config`: 相机配置资源
+/// - `orthographic`: 正交相机设置
+/// - `msaa_level`: 抗锯齿等级设置
+/// - `bloom_level`: 泛光强度设置
+fn spawn_game_camera(
+    commands: &mut Commands,
+    camera_config: &CameraConfig,
+    orthographic: &OrthographicCamera,
+    msaa_level: &MsaaLevel,
+    bloom_level: &BloomLevel,
+) {
+    let projection = if orthographic.0 {
+        // 正交投影没有近大远小，按固定的垂直视野高度缩放
+        Projection::Orthographic(OrthographicProjection {
+            scaling_mode: ScalingMode::FixedVertical {
+                viewport_height: ORTHOGRAPHIC_VIEWPORT_HEIGHT,
+            },
+            ..OrthographicProjection::default_3d()
+        })
+    } else {
+        Projection::Perspective(PerspectiveProjection {
+            fov: camera_config.fov,
+            ..default()
+        })
+    };
+    let mut camera = commands.spawn((
         Camera3d::default(),  // 3D相机组件
+        projection,  // 透视或正交投影
         // 设置相机初始位置和朝向（俯视视角）
-        Transform::from_translation(INITIAL_CAMERA_POS).looking_at(Vec3::ZERO, Vec3::Y),
+        Transform::from_translation(camera_config.offset).looking_at(Vec3::ZERO, Vec3::Y),
         Camera {
-            hdr: true,  // 启用HDR渲染，获得更好的光照效果
+            hdr: bloom_level.hdr(),  // 开启泛光时启用HDR渲染，获得更好的光照效果
             ..default()
         },
-        Bloom::default(),  // 添加泛光效果，增强视觉体验
+        msaa_level.msaa(),  // 多重采样抗锯齿，平滑模型边缘
     ));
+    // 添加泛光效果，增强视觉体验，强度由设置决定
+    if let Some(bloom) = bloom_level.bloom() {
+        camera.insert(bloom);
+    }
+}
+
+/// 切换投影模式时重新创建相机
+/// 
+/// 新相机的位置由`shake_camera`在同一帧之后的更新中恢复到跟随位置
+pub fn recreate_camera_on_projection_change(
+    mut commands: Commands,
+    camera_config: Res<CameraConfig>,
+    orthographic: Res<OrthographicCamera>,
+    msaa_level: Res<MsaaLevel>,
+    bloom_level: Res<BloomLevel>,
+    q_camera: Query<Entity, With<Camera3d>>,
+) {
+    if !orthographic.is_changed() || orthographic.is_added() {
+        return;
+    }
+    for camera in &q_camera {
+        commands.entity(camera).despawn_recursive();
+    }
+    spawn_game_camera(&mut commands, &camera_config, &orthographic, &msaa_level, &bloom_level);
+}
+
+/// 切换抗锯齿等级时更新相机的多重采样设置
+/// 
+/// 渲染管线会按新的采样数重新配置，不需要重新创建相机
+pub fn apply_msaa_level(msaa_level: Res<MsaaLevel>, mut q_camera: Query<&mut Msaa, With<Camera3d>>) {
+    if !msaa_level.is_changed() || msaa_level.is_added() {
+        return;
+    }
+    for mut msaa in &mut q_camera {
+        *msaa = msaa_level.msaa();
+    }
+}
+
+/// 切换泛光强度时更新相机的HDR开关和泛光效果
+/// 
+/// 渲染管线会按新的HDR设置重新创建渲染目标，不需要重新创建相机，
+/// 相机的投影和视口不变，屏幕坐标换算（如飘分效果）不受影响
+pub fn apply_bloom_level(
+    mut commands: Commands,
+    bloom_level: Res<BloomLevel>,
+    mut q_camera: Query<(Entity, &mut Camera), With<Camera3d>>,
+) {
+    if !bloom_level.is_changed() || bloom_level.is_added() {
+        return;
+    }
+    for (entity, mut camera) in &mut q_camera {
+        camera.hdr = bloom_level.hdr();
+        match bloom_level.bloom() {
+            Some(bloom) => {
+                commands.entity(entity).insert(bloom);
+            }
+            None => {
+                commands.entity(entity).remove::<Bloom>();
+            }
+        }
+    }
+}
+
+/// 切换阴影质量时更新方向光的阴影开关、级联设置和阴影贴图分辨率
+pub fn apply_shadow_quality(
+    mut commands: Commands,
+    shadow_quality: Res<ShadowQuality>,
+    mut q_sun: Query<(&mut DirectionalLight, &mut CascadeShadowConfig), With<SunLight>>,
+) {
+    if !shadow_quality.is_changed() || shadow_quality.is_added() {
+        return;
+    }
+    for (mut light, mut cascades) in &mut q_sun {
+        light.shadows_enabled = shadow_quality.shadows_enabled();
+        *cascades = shadow_quality.cascade_shadow_config();
+    }
+    commands.insert_resource(shadow_quality.shadow_map());
+}
+
+/// 修改相机视野角度时更新透视投影
+pub fn apply_camera_fov(
+    camera_config: Res<CameraConfig>,
+    mut q_projection: Query<&mut Projection, With<Camera3d>>,
+) {
+    if !camera_config.is_changed() || camera_config.is_added() {
+        return;
+    }
+    for mut projection in &mut q_projection {
+        // 先读取再修改，避免视野没有变化时也标记投影已修改
+        if let Projection::Perspective(perspective) = projection.as_ref() {
+            if perspective.fov == camera_config.fov {
+                continue;
+            }
+        }
+        if let Projection::Perspective(perspective) = projection.as_mut() {
+            perspective.fov = camera_config.fov;
+        }
+    }
+}
+
+/// 切换光照主题时更新方向光、环境光和背景颜色
+pub fn apply_lighting_theme(
+    mut commands: Commands,
+    lighting_theme: Res<LightingTheme>,
+    shadow_quality: Res<ShadowQuality>,
+    mut q_sun: Query<(&mut DirectionalLight, &mut Transform), With<SunLight>>,
+) {
+    if !lighting_theme.is_changed() || lighting_theme.is_added() {
+        return;
+    }
+    for (mut light, mut transform) in &mut q_sun {
+        *light = lighting_theme.directional_light(&shadow_quality);
+        *transform = lighting_theme.light_transform();
+    }
+    commands.insert_resource(lighting_theme.ambient_light());
+    commands.insert_resource(lighting_theme.clear_color());
+}
+
+/// 地面平面边长，足够覆盖最远的相机视野
+const GROUND_SIZE: f32 = 120.0;
+/// 地面棋盘格每格的边长，与平台大小接近，便于判断跳跃距离
+const GROUND_CELL_SIZE: f32 = 1.0;
+
+/// 地面组件标记，地面跟随玩家移动
+#[derive(Debug, Component)]
+pub struct Ground;
+
+/// 生成2x2像素的棋盘格纹理
+/// 
+/// 使用重复寻址平铺整个地面，最近邻采样保持格子边缘清晰
+fn checkerboard_image() -> Image {
+    let light = [242, 222, 224, 255]; // 浅粉色
+    let dark = [232, 209, 213, 255];  // 稍深的粉色，对比柔和
+    let data = [light, dark, dark, light].concat();
+    let mut image = Image::new(
+        Extent3d {
+            width: 2,
+            height: 2,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    image.sampler = ImageSampler::Descriptor(ImageSamplerDescriptor {
+        address_mode_u: ImageAddressMode::Repeat,
+        address_mode_v: ImageAddressMode::Repeat,
+        mag_filter: ImageFilterMode::Nearest,
+        min_filter: ImageFilterMode::Linear,
+        ..default()
+    });
+    image
 }
 
 /// 设置游戏地面
 /// 
-/// 创建一个巨大的平面作为游戏的地面
+/// 创建一个铺有浅色棋盘格的平面作为游戏的地面，地面跟随玩家移动，不会走到边界
 pub fn setup_ground(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
+    mut images: ResMut<Assets<Image>>,
 ) {
-    // 创建地面平面
+    // 纹理每重复一次覆盖2x2个格子
+    let repeats = GROUND_SIZE / (GROUND_CELL_SIZE * 2.0);
     commands.spawn((
-        Mesh3d(
-            meshes.add(
-                // 创建一个非常大的平面（1000000x1000000）以确保玩家不会走到边界
-                Plane3d::new(Vec3::Y, Vec2::new(1000000.0, 1000000.0))
-                    .mesh()
-                    .size(1000000.0, 1000000.0),
-            ),
-        ),
-        // 设置地面材质为浅粉色
-        MeshMaterial3d(materials.add(Color::srgb(0.95, 0.87, 0.88))),
+        Mesh3d(meshes.add(Plane3d::default().mesh().size(GROUND_SIZE, GROUND_SIZE))),
+        MeshMaterial3d(materials.add(StandardMaterial {
+            base_color_texture: Some(images.add(checkerboard_image())),
+            uv_transform: Affine2::from_scale(Vec2::splat(repeats)),
+            perceptual_roughness: 1.0,
+            ..default()
+        })),
+        Transform::default(),
+        Ground,
     ));
 }
 
+/// 地面跟随玩家系统
+/// 
+/// 地面中心按整块纹理的间距对齐到玩家附近，移动后棋盘格在世界中的位置保持不变
+pub fn follow_ground(
+    q_player: Query<&Transform, With<Player>>,
+    mut q_ground: Query<&mut Transform, (With<Ground>, Without<Player>)>,
+) {
+    let Ok(player) = q_player.get_single() else {
+        return;
+    };
+    let step = GROUND_CELL_SIZE * 2.0;
+    let snapped = (player.translation.xz() / step).round() * step;
+    for mut ground in &mut q_ground {
+        if ground.translation.xz() != snapped {
+            ground.translation.x = snapped.x;
+            ground.translation.z = snapped.y;
+        }
+    }
+}
+
 /// 相机跟随玩家移动的系统
 /// 
 /// 实现相机平滑跟随玩家的功能，只在玩家不跳跃或不摔落时移动
@@ -88,33 +489,270 @@ pub fn move_camera(
     q_player: Query<&Transform, With<Player>>,  // 查询玩家变换组件
     mut q_camera: Query<&mut Transform, (With<Camera>, Without<Player>)>,  // 查询相机变换组件
     mut camera_move_state: ResMut<CameraMoveState>,  // 相机移动状态资源
+    camera_config: Res<CameraConfig>,  // 相机配置资源
     jump_state: Res<JumpState>,  // 跳跃状态资源
     fall_state: Res<FallState>,  // 摔落状态资源
+    chain_speed: Res<ChainSpeed>,  // 连击加速资源
 ) {
     // 只有当跳跃和摔落动画都完成时，才移动相机
     // 这样可以避免在跳跃过程中相机跟随，影响玩家体验
     if jump_state.completed && fall_state.completed {
-        let player = q_player.single();
-        let mut camera = q_camera.single_mut();
+        // 玩家或相机不存在时（例如重新开始游戏的过渡帧）跳过本帧
+        let (Ok(player), Ok(mut camera)) = (q_player.get_single(), q_camera.get_single_mut()) else {
+            return;
+        };
         
         // 计算相机应该到达的目标位置
         // 保持与玩家的相对位置不变
-        let camera_destination = INITIAL_CAMERA_POS + player.translation;
-
-        // 检测玩家是否移动了足够的距离（大于0.1单位）
-        // 如果移动了，则重新计算相机移动步长
-        if camera_move_state.player_pos.distance(player.translation) > 0.1 {
-            let delta = camera_destination - camera.translation;
-            // 步长设置为总距离的5%，实现平滑过渡效果
-            camera_move_state.step = 0.05 * delta;
+        let camera_destination = camera_config.offset + player.translation;
+
+        // 检测玩家是否移动了足够的距离（大于0.1单位）或相机配置是否发生变化
+        // 如果是，则重新计算相机移动步长
+        if camera_move_state.player_pos.distance(player.translation) > 0.1
+            || camera_config.is_changed()
+        {
+            let delta = camera_destination - camera_move_state.translation;
+            // 步长设置为总距离的一定比例（默认5%），实现平滑过渡效果
+            // 连击加速时按倍率加快追赶，比例限制在1.0以内，保证不会越过目标位置
+            camera_move_state.step =
+                (camera_config.follow_speed * chain_speed.multiplier()).clamp(0.0, 1.0) * delta;
             // 更新记录的玩家位置
             camera_move_state.player_pos = player.translation;
         }
 
         // 如果相机还没到达目标位置，则继续移动
         // 使用步长向量的长度作为阈值，避免无限接近但永远无法到达的情况
-        if camera.translation.distance(camera_destination) > Vec3::ZERO.distance(camera_move_state.step) {
-            camera.translation = camera.translation + camera_move_state.step;
+        if camera_move_state.translation.distance(camera_destination) > Vec3::ZERO.distance(camera_move_state.step) {
+            let step = camera_move_state.step;
+            camera_move_state.translation += step;
         }
+        camera.translation = camera_move_state.translation;
+    }
+}
+
+/// 摔落时触发相机震动
+/// 
+/// 检测摔落动画开始的时刻（跳跃已完成且摔落未完成），为相机注入震动强度
+pub fn trigger_fall_camera_shake(
+    mut camera_shake: ResMut<CameraShake>,
+    jump_state: Res<JumpState>,
+    fall_state: Res<FallState>,
+    mut was_falling: Local<bool>,
+) {
+    let falling = jump_state.completed && !fall_state.completed;
+    if falling && !*was_falling {
+        camera_shake.trauma = (camera_shake.trauma + 0.8).min(1.0);
+    }
+    *was_falling = falling;
+}
+
+/// 相机缩放系统
+/// 
+/// 开启随分数缩放时，相机偏移随分数逐渐放大直到上限；每帧平滑趋近目标值，不会在跳跃间突变
+pub fn update_camera_zoom(
+    mut camera_move_state: ResMut<CameraMoveState>,
+    camera_config: Res<CameraConfig>,
+    score: Res<Score>,
+    time: Res<Time>,
+) {
+    let target_zoom = if camera_config.zoom_with_score {
+        (1.0 + camera_config.zoom_per_point * score.0 as f32).min(camera_config.max_zoom)
+    } else {
+        1.0
+    };
+    // 指数平滑，与帧率无关
+    let t = 1.0 - (-2.0 * time.delta_secs()).exp();
+    camera_move_state.zoom += (target_zoom - camera_move_state.zoom) * t;
+}
+
+/// 相机震动系统
+/// 
+/// 在相机跟随位置的基础上叠加缩放偏移和随时间衰减的震动偏移，不影响平滑跟随逻辑
+pub fn shake_camera(
+    mut camera_shake: ResMut<CameraShake>,
+    camera_move_state: Res<CameraMoveState>,
+    camera_config: Res<CameraConfig>,
+    mut q_camera: Query<&mut Transform, (With<Camera>, Without<Player>)>,
+    time: Res<Time>,
+) {
+    let Ok(mut camera) = q_camera.get_single_mut() else {
+        return;
+    };
+    // 相机的基础朝向，由相机偏移决定
+    let base_rotation = camera_config.rotation();
+
+    // 震动强度取trauma的平方，使震动衰减更自然
+    let shake = camera_shake.trauma * camera_shake.trauma;
+    let t = time.elapsed_secs();
+    // 使用不同频率的正弦波叠加模拟噪声
+    let offset = CAMERA_SHAKE_MAX_OFFSET
+        * shake
+        * Vec3::new((t * 37.0).sin(), (t * 41.0 + 1.0).sin(), (t * 43.0 + 2.0).sin());
+    let roll = CAMERA_SHAKE_MAX_ROLL * shake * (t * 47.0 + 3.0).sin();
+
+    // 跟随位置已包含一倍相机偏移，缩放只叠加多出的部分
+    let zoom_offset = (camera_move_state.zoom - 1.0) * camera_config.offset;
+    camera.translation = camera_move_state.translation + zoom_offset + offset;
+    camera.rotation = base_rotation * Quat::from_rotation_z(roll);
+
+    // 震动强度随时间衰减到0
+    camera_shake.trauma = (camera_shake.trauma - CAMERA_SHAKE_DECAY * time.delta_secs()).max(0.0);
+}
+
+/// 拍照模式中按键环绕的角速度（弧度/秒）
+const PHOTO_ORBIT_KEY_SPEED: f32 = 1.5;
+/// 拍照模式中拖动鼠标时每像素环绕的角度（弧度）
+const PHOTO_ORBIT_MOUSE_SPEED: f32 = 0.005;
+/// 拍照模式中每格滚轮改变的相机距离比例
+const PHOTO_ZOOM_STEP: f32 = 0.1;
+/// 拍照模式中相机距离的范围
+const PHOTO_DISTANCE_RANGE: (f32, f32) = (3.0, 40.0);
+/// 拍照模式中俯仰角的下限，避免相机穿到地面以下
+const PHOTO_MIN_PITCH: f32 = 0.1;
+/// 拍照模式中俯仰角的上限，避免越过正上方导致画面翻转
+const PHOTO_MAX_PITCH: f32 = FRAC_PI_2 - 0.05;
+
+/// 拍照模式资源
+/// 
+/// 游戏结束界面按P进入，隐藏所有界面，相机围绕玩家最终位置自由环绕，方便截图；
+/// 再次按P或按Esc退出，恢复界面，相机由`shake_camera`回到跟随位置
+#[derive(Debug, Default, Resource)]
+pub struct PhotoMode {
+    /// 是否处于拍照模式
+    pub active: bool,
+    /// 环绕中心，即玩家的最终位置
+    pub focus: Vec3,
+    /// 水平环绕角度（弧度）
+    pub yaw: f32,
+    /// 俯仰角度（弧度）
+    pub pitch: f32,
+    /// 相机到环绕中心的距离
+    pub distance: f32,
+    /// 进入拍照模式前界面根节点的可见性，退出时恢复
+    hidden_ui: Vec<(Entity, Visibility)>,
+}
+
+/// 界面根节点可见性查询，拍照模式隐藏和恢复界面时使用
+type RootUiQuery<'w, 's> =
+    Query<'w, 's, (Entity, &'static mut Visibility), (With<Node>, Without<Parent>)>;
+
+impl PhotoMode {
+    /// 按环绕角度和距离计算相机位置
+    pub fn camera_translation(&self) -> Vec3 {
+        let direction = Vec3::new(
+            self.pitch.cos() * self.yaw.sin(),
+            self.pitch.sin(),
+            self.pitch.cos() * self.yaw.cos(),
+        );
+        self.focus + direction * self.distance
+    }
+
+    /// 退出拍照模式，恢复界面的可见性
+    fn leave(&mut self, q_ui: &mut RootUiQuery) {
+        for (entity, visibility) in self.hidden_ui.drain(..) {
+            if let Ok((_, mut ui_visibility)) = q_ui.get_mut(entity) {
+                *ui_visibility = visibility;
+            }
+        }
+        self.active = false;
+    }
+}
+
+/// 判断是否处于拍照模式，用作系统运行条件
+pub fn photo_mode_active(photo_mode: Res<PhotoMode>) -> bool {
+    photo_mode.active
+}
+
+/// 切换拍照模式
+/// 
+/// 进入时以当前相机相对玩家的偏移作为初始环绕角度，隐藏所有界面根节点
+pub fn toggle_photo_mode(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut photo_mode: ResMut<PhotoMode>,
+    q_player: Query<&Transform, With<Player>>,
+    q_camera: Query<&Transform, (With<Camera3d>, Without<Player>)>,
+    mut q_ui: RootUiQuery,
+) {
+    if photo_mode.active {
+        if keys.just_pressed(KeyCode::KeyP) || keys.just_pressed(KeyCode::Escape) {
+            info!("Leave photo mode");
+            photo_mode.leave(&mut q_ui);
+        }
+        return;
+    }
+    if !keys.just_pressed(KeyCode::KeyP) {
+        return;
+    }
+    let (Ok(player), Ok(camera)) = (q_player.get_single(), q_camera.get_single()) else {
+        return;
+    };
+    info!("Enter photo mode");
+    let offset = camera.translation - player.translation;
+    let distance = offset.length().max(PHOTO_DISTANCE_RANGE.0);
+    photo_mode.active = true;
+    photo_mode.focus = player.translation;
+    photo_mode.yaw = offset.x.atan2(offset.z);
+    photo_mode.pitch = (offset.y / distance)
+        .clamp(-1.0, 1.0)
+        .asin()
+        .clamp(PHOTO_MIN_PITCH, PHOTO_MAX_PITCH);
+    photo_mode.distance = distance;
+    photo_mode.hidden_ui = q_ui
+        .iter_mut()
+        .map(|(entity, mut visibility)| {
+            let previous = *visibility;
+            *visibility = Visibility::Hidden;
+            (entity, previous)
+        })
+        .collect();
+}
+
+/// 拍照模式的相机控制
+/// 
+/// WASD或方向键环绕，按住鼠标左键拖动也可以环绕，滚轮调整距离；
+/// 在`shake_camera`之后运行并覆盖相机位置，不影响跟随逻辑
+pub fn orbit_photo_camera(
+    keys: Res<ButtonInput<KeyCode>>,
+    buttons: Res<ButtonInput<MouseButton>>,
+    mouse_motion: Res<AccumulatedMouseMotion>,
+    mouse_scroll: Res<AccumulatedMouseScroll>,
+    time: Res<Time<Real>>,
+    mut photo_mode: ResMut<PhotoMode>,
+    mut q_camera: Query<&mut Transform, With<Camera3d>>,
+) {
+    let Ok(mut camera) = q_camera.get_single_mut() else {
+        return;
+    };
+    // 按键环绕，暂停时也可以操作，使用真实时间
+    let step = PHOTO_ORBIT_KEY_SPEED * time.delta_secs();
+    let axis = |negative: [KeyCode; 2], positive: [KeyCode; 2]| {
+        keys.any_pressed(positive) as i32 as f32 - keys.any_pressed(negative) as i32 as f32
+    };
+    let mut yaw = step * axis([KeyCode::KeyD, KeyCode::ArrowRight], [KeyCode::KeyA, KeyCode::ArrowLeft]);
+    let mut pitch = step * axis([KeyCode::KeyS, KeyCode::ArrowDown], [KeyCode::KeyW, KeyCode::ArrowUp]);
+    // 拖动鼠标环绕
+    if buttons.pressed(MouseButton::Left) {
+        yaw -= mouse_motion.delta.x * PHOTO_ORBIT_MOUSE_SPEED;
+        pitch += mouse_motion.delta.y * PHOTO_ORBIT_MOUSE_SPEED;
+    }
+    photo_mode.yaw += yaw;
+    photo_mode.pitch = (photo_mode.pitch + pitch).clamp(PHOTO_MIN_PITCH, PHOTO_MAX_PITCH);
+    // 滚轮向上拉近，向下拉远
+    if mouse_scroll.delta.y != 0.0 {
+        photo_mode.distance = (photo_mode.distance * (1.0 - PHOTO_ZOOM_STEP * mouse_scroll.delta.y))
+            .clamp(PHOTO_DISTANCE_RANGE.0, PHOTO_DISTANCE_RANGE.1);
+    }
+
+    *camera = Transform::from_translation(photo_mode.camera_translation())
+        .looking_at(photo_mode.focus, Vec3::Y);
+}
+
+/// 离开游戏结束界面时退出拍照模式
+pub fn exit_photo_mode(
+    mut photo_mode: ResMut<PhotoMode>,
+    mut q_ui: RootUiQuery,
+) {
+    if photo_mode.active {
+        photo_mode.leave(&mut q_ui);
     }
 }
@@ -0,0 +1,285 @@
+// 导入Bevy的主要组件
+use bevy::prelude::*;
+// 导入系统参数派生宏，用于组合各种输入
+use bevy::ecs::system::SystemParam;
+// 导入光标移动事件，鼠标移动也视为玩家输入
+use bevy::window::CursorMoved;
+
+// 导入平台模块中的平台标记
+use crate::platform::{AimedPlatform, CurrentPlatform, MovingPlatform, NextPlatform};
+// 导入玩家模块中的蓄力、跳跃和摔落相关资源
+use crate::player::{
+    Accumulator, CountdownTimer, FallState, IntroAnimation, JumpState, MaxCharge, PhysicsTuning,
+    Player, PrepareJumpTimer, Retries, horizontal_direction,
+};
+// 导入UI模块中的游戏状态、计分和连击加速资源
+use crate::ui::{ChainSpeed, GameState, ScoreKeeper};
+
+/// 主菜单空闲多久后开始演示（秒）
+pub const DEMO_IDLE_SECS: f32 = 10.0;
+
+/// 演示中每次落地后等待多久再开始蓄力（秒）
+const DEMO_JUMP_DELAY_SECS: f32 = 0.6;
+
+/// 主菜单演示状态，只在主菜单状态下存在
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Default, SubStates)]
+#[source(GameState = GameState::MainMenu)]
+pub enum DemoState {
+    #[default]
+    Idle,    // 等待玩家操作，空闲计时中
+    Running, // 自动演示中
+}
+
+/// 主菜单空闲计时器，玩家有任何操作时重新计时
+#[derive(Debug, Resource)]
+pub struct DemoIdleTimer(pub Timer);
+
+impl Default for DemoIdleTimer {
+    fn default() -> Self {
+        Self(Timer::from_seconds(DEMO_IDLE_SECS, TimerMode::Once))
+    }
+}
+
+/// 演示自动跳跃状态
+#[derive(Debug, Resource)]
+pub struct DemoJump {
+    /// 落地后开始下一次蓄力前的等待计时器
+    pub delay: Timer,
+    /// 本次蓄力的目标时长（秒），未在蓄力时为None
+    pub target_charge: Option<f32>,
+    /// 蓄力达到目标时长，本帧松开
+    pub release: bool,
+}
+
+impl Default for DemoJump {
+    fn default() -> Self {
+        Self {
+            delay: Timer::from_seconds(DEMO_JUMP_DELAY_SECS, TimerMode::Once),
+            target_charge: None,
+            release: false,
+        }
+    }
+}
+
+/// 玩家输入检测系统参数
+///
+/// 任意按键、鼠标按键、触摸或鼠标移动都视为玩家操作
+#[derive(SystemParam)]
+pub struct AnyInput<'w, 's> {
+    keys: Res<'w, ButtonInput<KeyCode>>,
+    buttons: Res<'w, ButtonInput<MouseButton>>,
+    touches: Res<'w, Touches>,
+    cursor_moved: EventReader<'w, 's, CursorMoved>,
+}
+
+impl AnyInput<'_, '_> {
+    /// 本帧玩家是否有任何操作
+    pub fn any(&mut self) -> bool {
+        // 先读取光标移动事件，避免短路后事件积压到下一帧
+        let cursor_moved = self.cursor_moved.read().count() > 0;
+        cursor_moved
+            || self.keys.get_just_pressed().next().is_some()
+            || self.buttons.get_just_pressed().next().is_some()
+            || self.touches.any_just_pressed()
+    }
+}
+
+/// 计算落在下一个平台中心所需的蓄力时长
+///
+/// 移动平台按蓄力和跳跃所需的时间预测落地时刻的位置，迭代几次使蓄力时长收敛
+///
+/// # 参数
+/// - `player_pos`: 玩家当前位置
+/// - `current_platform_pos`: 当前平台位置
+/// - `next_platform_pos`: 下一个平台当前位置
+/// - `moving`: 下一个平台的移动组件
+/// - `max_charge`: 最大蓄力时长
+/// - `tuning`: 物理手感调节资源
+/// - `chain_speed`: 连击加速倍率，跳跃时长按倍率缩短
+///
+/// # 返回值
+/// 目标蓄力时长（秒），不超过最大蓄力时长
+pub fn ideal_charge(
+    player_pos: Vec3,
+    current_platform_pos: Vec3,
+    next_platform_pos: Vec3,
+    moving: Option<&MovingPlatform>,
+    max_charge: &MaxCharge,
+    tuning: &PhysicsTuning,
+    chain_speed: f32,
+) -> f32 {
+    let mut charge_secs = 0.0;
+    for _ in 0..3 {
+        let target = next_platform_pos
+            + moving.map_or(Vec3::ZERO, |moving| {
+                moving.offset_after(charge_secs + tuning.jump_duration(charge_secs) / chain_speed)
+            });
+        // 跳跃沿当前平台指向下一个平台的方向，只需要计算该方向上的水平距离
+        let direction = horizontal_direction(current_platform_pos, target);
+        let distance = (target - player_pos).with_y(0.0).dot(direction).max(0.0);
+        charge_secs = tuning.charge_for_distance(distance, max_charge);
+    }
+    charge_secs
+}
+
+/// 瞄准的下一个平台查询，返回平台位置和可选的移动组件
+type AimedPlatformQuery<'w, 's> = Query<
+    'w,
+    's,
+    (&'static Transform, Option<&'static MovingPlatform>),
+    (With<NextPlatform>, With<AimedPlatform>, Without<Player>),
+>;
+
+/// 演示跳跃目标系统参数
+///
+/// 组合玩家、当前平台、瞄准的下一个平台、最大蓄力时长、物理手感和连击加速，计算演示每次跳跃的理想蓄力时长
+#[derive(SystemParam)]
+pub struct DemoJumpTarget<'w, 's> {
+    max_charge: Res<'w, MaxCharge>,
+    tuning: Res<'w, PhysicsTuning>,
+    chain_speed: Res<'w, ChainSpeed>,
+    q_player: Query<'w, 's, &'static Transform, With<Player>>,
+    q_current_platform: Query<'w, 's, &'static Transform, (With<CurrentPlatform>, Without<Player>)>,
+    q_next_platform: AimedPlatformQuery<'w, 's>,
+}
+
+impl DemoJumpTarget<'_, '_> {
+    /// 落在瞄准平台中心所需的蓄力时长，缺少玩家或平台时返回`None`
+    fn ideal_charge(&self) -> Option<f32> {
+        let (Ok(player), Ok(current_platform), Ok((next_platform, moving))) = (
+            self.q_player.get_single(),
+            self.q_current_platform.get_single(),
+            self.q_next_platform.get_single(),
+        ) else {
+            return None;
+        };
+        Some(ideal_charge(
+            player.translation,
+            current_platform.translation,
+            next_platform.translation,
+            moving,
+            &self.max_charge,
+            &self.tuning,
+            self.chain_speed.multiplier(),
+        ))
+    }
+}
+
+/// 重置主菜单空闲计时器
+///
+/// 进入主菜单或演示结束时重新开始计时
+pub fn reset_demo_idle_timer(mut idle_timer: ResMut<DemoIdleTimer>) {
+    idle_timer.0.reset();
+}
+
+/// 主菜单空闲计时
+///
+/// 玩家有任何操作时重新计时，空闲时间达到阈值后开始演示
+pub fn tick_demo_idle_timer(
+    mut input: AnyInput,
+    time: Res<Time>,
+    mut idle_timer: ResMut<DemoIdleTimer>,
+    mut next_demo_state: ResMut<NextState<DemoState>>,
+) {
+    if input.any() {
+        idle_timer.0.reset();
+        return;
+    }
+    if idle_timer.0.tick(time.delta()).just_finished() {
+        info!("Main menu idle, start demo");
+        next_demo_state.set(DemoState::Running);
+    }
+}
+
+/// 开始演示
+///
+/// 重置分数和跳跃状态，跳过开局倒计时和入场动画，并给予无限重试，演示中摔落后直接回到当前平台
+pub fn start_demo(
+    mut score_keeper: ScoreKeeper,
+    mut retries: ResMut<Retries>,
+    mut countdown_timer: ResMut<CountdownTimer>,
+    mut prepare_jump_timer: ResMut<PrepareJumpTimer>,
+    mut intro_animation: ResMut<IntroAnimation>,
+    mut demo_jump: ResMut<DemoJump>,
+) {
+    score_keeper.reset();
+    retries.0 = u32::MAX;
+    // 演示不需要倒计时，直接放行跳跃
+    let countdown_duration = countdown_timer.0.duration();
+    countdown_timer.0.set_elapsed(countdown_duration);
+    let prepare_duration = prepare_jump_timer.0.duration();
+    prepare_jump_timer.0.set_elapsed(prepare_duration);
+    // 推进整段时长使计时器进入结束状态
+    let intro_duration = intro_animation.0.duration();
+    intro_animation.0.tick(intro_duration);
+    *demo_jump = DemoJump::default();
+}
+
+/// 玩家有任何操作时结束演示，回到普通主菜单
+pub fn stop_demo_on_input(
+    mut input: AnyInput,
+    mut next_demo_state: ResMut<NextState<DemoState>>,
+) {
+    if input.any() {
+        info!("Input received, stop demo");
+        next_demo_state.set(DemoState::Idle);
+    }
+}
+
+/// 演示自动跳跃
+///
+/// 落地后稍作停顿，按计算出的理想蓄力时长开始蓄力，达到目标时长后松开，
+/// 蓄力和跳跃完全复用`player_jump`的逻辑
+pub fn demo_auto_jump(
+    time: Res<Time>,
+    real_time: Res<Time<Real>>,
+    mut accumulator: ResMut<Accumulator>,
+    mut demo_jump: ResMut<DemoJump>,
+    jump_state: Res<JumpState>,
+    fall_state: Res<FallState>,
+    target: DemoJumpTarget,
+) {
+    // 跳跃、落地摇晃或摔落过程中重新等待
+    if !jump_state.is_settled() || !fall_state.completed {
+        demo_jump.delay.reset();
+        return;
+    }
+
+    match demo_jump.target_charge {
+        // 正在蓄力，达到目标时长后松开
+        Some(target_charge) => {
+            if accumulator.0.is_none()
+                || accumulator.charge_secs(&target.max_charge) >= target_charge
+            {
+                demo_jump.release = accumulator.0.is_some();
+                demo_jump.target_charge = None;
+                demo_jump.delay.reset();
+            }
+        }
+        // 等待一段时间后开始下一次蓄力
+        None => {
+            if !demo_jump.delay.tick(time.delta()).finished() {
+                return;
+            }
+            let Some(target_charge) = target.ideal_charge() else {
+                return;
+            };
+            demo_jump.target_charge = Some(target_charge);
+            // 与player_jump一致，使用真实时间记录蓄力开始时刻
+            accumulator.0 = real_time.last_update();
+        }
+    }
+}
+
+/// 结束演示
+///
+/// 重置跳跃、摔落和演示状态，避免演示中途的状态影响正式游戏
+pub fn reset_demo(
+    mut jump_state: ResMut<JumpState>,
+    mut fall_state: ResMut<FallState>,
+    mut demo_jump: ResMut<DemoJump>,
+) {
+    *jump_state = JumpState::default();
+    *fall_state = FallState::default();
+    *demo_jump = DemoJump::default();
+}
@@ -0,0 +1,328 @@
+// 导入Bevy的主要组件
+use bevy::prelude::*;
+// 导入系统参数派生宏，用于组合本局模式和种子相关资源
+use bevy::ecs::system::SystemParam;
+// 导入哈希表，按种子和模式保存最佳轨迹
+use bevy::utils::HashMap;
+// 导入序列化库，幽灵随存档一起保存
+use serde::{Deserialize, Serialize};
+
+// 导入平台模块中的游戏模式和固定种子
+use crate::platform::{GameMode, GameSeed};
+// 导入玩家模块中的玩家标记和皮肤
+use crate::player::{Player, PlayerSkin};
+// 导入录像模块中的录像记录和回放状态，幽灵与录像使用相同的种子和计时
+use crate::replay::{ReplayLog, ReplayPlayback};
+// 导入UI模块中的分数和练习模式
+use crate::ui::{PracticeMode, Score};
+
+/// 记录玩家位置的时间间隔（秒），回放时在相邻记录之间插值
+const GHOST_SAMPLE_SECS: f32 = 0.05;
+
+/// 幽灵的不透明度
+const GHOST_ALPHA: f32 = 0.3;
+
+/// 最多保存的幽灵轨迹条数，超出时丢弃得分最低的一条
+pub const MAX_BEST_RUN_GHOSTS: usize = 20;
+
+/// 幽灵轨迹中的一个位置记录
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GhostSample {
+    /// 从本局开始计算的游戏时间（秒），与录像使用相同的计时，暂停期间不计时
+    pub time: f32,
+    /// 玩家位置
+    pub pos: [f32; 3],
+}
+
+/// 记录幽灵轨迹的游戏模式
+///
+/// 不同模式即使种子相同，平台序列也不同，幽灵只和同一模式的成绩比较
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum GhostMode {
+    /// 指定了种子的无尽模式
+    #[default]
+    Endless,
+    /// 每日挑战
+    Daily,
+}
+
+impl GhostMode {
+    /// 游戏模式对应的幽灵模式，不记录幽灵的模式返回`None`
+    pub fn of(game_mode: &GameMode) -> Option<Self> {
+        match game_mode {
+            GameMode::Endless => Some(Self::Endless),
+            GameMode::Daily(_) => Some(Self::Daily),
+            GameMode::TimeAttack | GameMode::Forked => None,
+        }
+    }
+}
+
+/// 一局游戏的幽灵轨迹
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GhostRun {
+    /// 该局平台序列的种子，只有种子和模式都相同时平台位置才一致
+    pub seed: u64,
+    /// 该局的游戏模式，旧存档缺少该字段时视为无尽模式
+    #[serde(default)]
+    pub mode: GhostMode,
+    /// 该局得分
+    pub score: u32,
+    /// 按时间顺序排列的位置记录
+    pub samples: Vec<GhostSample>,
+}
+
+impl GhostRun {
+    /// 计算指定时刻幽灵的位置，在相邻记录之间线性插值
+    ///
+    /// # 参数
+    /// - `time`: 从本局开始计算的游戏时间（秒）
+    ///
+    /// # 返回值
+    /// 没有位置记录时返回`None`，超过最后一条记录后停在最后的位置
+    pub fn position_at(&self, time: f32) -> Option<Vec3> {
+        let next = self.samples.partition_point(|sample| sample.time <= time);
+        let last = self.samples.get(next.checked_sub(1)?)?;
+        let Some(following) = self.samples.get(next) else {
+            return Some(Vec3::from(last.pos));
+        };
+        let span = following.time - last.time;
+        let t = if span > 0.0 { (time - last.time) / span } else { 1.0 };
+        Some(Vec3::from(last.pos).lerp(Vec3::from(following.pos), t))
+    }
+}
+
+/// 最佳成绩幽灵资源，按种子和模式分别保存得分最高一局的轨迹，跨局保留并写入存档
+///
+/// 同一种子和模式只保存一条轨迹，得分更高时才替换；最多保存`MAX_BEST_RUN_GHOSTS`条
+#[derive(Debug, Default, Resource)]
+pub struct BestRunGhost(pub HashMap<(u64, GhostMode), GhostRun>);
+
+impl BestRunGhost {
+    /// 从存档的轨迹列表创建
+    ///
+    /// 存档可能被手动修改，同一种子和模式保留得分最高的一条，并截断到最多保存的条数
+    ///
+    /// # 参数
+    /// - `runs`: 存档中的轨迹列表
+    pub fn from_runs(runs: Vec<GhostRun>) -> Self {
+        let mut best_run = Self::default();
+        for run in runs {
+            best_run.insert(run);
+        }
+        best_run
+    }
+
+    /// 写入存档的轨迹列表，按种子和模式排序，保证存档内容稳定
+    pub fn runs(&self) -> Vec<GhostRun> {
+        let mut runs: Vec<GhostRun> = self.0.values().cloned().collect();
+        runs.sort_unstable_by_key(|run| (run.seed, run.mode));
+        runs
+    }
+
+    /// 指定种子和模式的最佳轨迹，没有对应记录时返回`None`
+    pub fn for_seed(&self, seed: u64, mode: GhostMode) -> Option<&GhostRun> {
+        self.0.get(&(seed, mode))
+    }
+
+    /// 保存一局的轨迹，得分超过同一种子和模式的已有记录时才替换
+    ///
+    /// 保存的条数超出上限时丢弃其他种子和模式中得分最低的一条
+    ///
+    /// # 参数
+    /// - `run`: 本局的轨迹
+    pub fn insert(&mut self, run: GhostRun) {
+        let key = (run.seed, run.mode);
+        if self.for_seed(run.seed, run.mode).is_some_and(|best| best.score >= run.score) {
+            return;
+        }
+        self.0.insert(key, run);
+        if self.0.len() > MAX_BEST_RUN_GHOSTS {
+            let lowest = self
+                .0
+                .iter()
+                .filter(|(other, _)| **other != key)
+                .min_by_key(|(other, run)| (run.score, **other))
+                .map(|(other, _)| *other);
+            if let Some(lowest) = lowest {
+                self.0.remove(&lowest);
+            }
+        }
+    }
+}
+
+/// 本局幽灵轨迹记录资源
+#[derive(Debug, Default, Resource)]
+pub struct GhostRecorder {
+    /// 本局是否记录轨迹，只有使用固定种子时才记录
+    pub recording: bool,
+    /// 本局的幽灵模式
+    pub mode: GhostMode,
+    /// 本局的位置记录
+    pub samples: Vec<GhostSample>,
+}
+
+/// 幽灵标记组件
+#[derive(Debug, Component)]
+pub struct Ghost;
+
+/// 本局种子系统参数
+///
+/// 组合游戏模式、固定种子、录像回放、练习模式和本局录像记录，判断本局是否记录幽灵轨迹并提供本局种子
+#[derive(SystemParam)]
+pub struct RunSeed<'w> {
+    game_mode: Res<'w, GameMode>,
+    game_seed: Res<'w, GameSeed>,
+    playback: Res<'w, ReplayPlayback>,
+    practice_mode: Res<'w, PracticeMode>,
+    replay_log: Res<'w, ReplayLog>,
+}
+
+impl RunSeed<'_> {
+    /// 本局是否使用固定种子
+    ///
+    /// 每日挑战和指定了种子的无尽模式每局的平台序列相同，幽灵才有比较的意义；
+    /// 回放录像和练习模式不参与
+    fn is_seeded(&self) -> bool {
+        !self.playback.is_active()
+            && !self.practice_mode.0
+            && match *self.game_mode {
+                GameMode::Daily(_) => true,
+                GameMode::Endless => self.game_seed.0.is_some(),
+                GameMode::TimeAttack | GameMode::Forked => false,
+            }
+    }
+
+    /// 本局的幽灵模式，不记录幽灵的模式返回`None`
+    fn mode(&self) -> Option<GhostMode> {
+        GhostMode::of(&self.game_mode)
+    }
+
+    /// 本局使用的种子，与录像记录的种子相同
+    fn seed(&self) -> u64 {
+        self.replay_log.seed
+    }
+}
+
+/// 开始记录本局幽灵轨迹，并在有本种子最佳轨迹时生成幽灵
+///
+/// 需要在`seed_game_rng`写入本局种子之后执行
+pub fn setup_ghost(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut recorder: ResMut<GhostRecorder>,
+    best_run: Res<BestRunGhost>,
+    run_seed: RunSeed,
+    player_skin: Res<PlayerSkin>,
+) {
+    recorder.samples.clear();
+    recorder.recording = run_seed.is_seeded();
+    if !recorder.recording {
+        return;
+    }
+    // 记录幽灵的模式一定有对应的幽灵模式
+    let Some(mode) = run_seed.mode() else {
+        return;
+    };
+    recorder.mode = mode;
+    let Some(start_pos) = best_run
+        .for_seed(run_seed.seed(), mode)
+        .and_then(|run| run.position_at(0.0))
+    else {
+        return;
+    };
+    info!("Racing the best run ghost for seed {}", run_seed.seed());
+    // 幽灵使用与玩家相同的模型，半透明显示
+    commands.spawn((
+        Mesh3d(meshes.add(player_skin.mesh.mesh())),
+        MeshMaterial3d(materials.add(StandardMaterial {
+            base_color: player_skin.color.with_alpha(GHOST_ALPHA),
+            alpha_mode: AlphaMode::Blend,
+            unlit: true,
+            ..default()
+        })),
+        Transform::from_translation(start_pos),
+        Ghost,
+    ));
+}
+
+/// 记录玩家位置
+///
+/// 每隔固定时间追加一条位置记录，游戏结束时与最佳轨迹比较
+pub fn record_ghost_run(
+    time: Res<Time>,
+    replay_log: Res<ReplayLog>,
+    mut recorder: ResMut<GhostRecorder>,
+    q_player: Query<&Transform, With<Player>>,
+) {
+    if !recorder.recording {
+        return;
+    }
+    let Ok(player) = q_player.get_single() else {
+        return;
+    };
+    let elapsed = replay_log.elapsed(&time);
+    if recorder
+        .samples
+        .last()
+        .is_some_and(|last| elapsed - last.time < GHOST_SAMPLE_SECS)
+    {
+        return;
+    }
+    recorder.samples.push(GhostSample {
+        time: elapsed,
+        pos: player.translation.to_array(),
+    });
+}
+
+/// 按最佳轨迹移动幽灵
+pub fn animate_ghost(
+    time: Res<Time>,
+    replay_log: Res<ReplayLog>,
+    recorder: Res<GhostRecorder>,
+    best_run: Res<BestRunGhost>,
+    mut q_ghost: Query<&mut Transform, With<Ghost>>,
+) {
+    let Some(run) = best_run.for_seed(replay_log.seed, recorder.mode) else {
+        return;
+    };
+    let Some(pos) = run.position_at(replay_log.elapsed(&time)) else {
+        return;
+    };
+    for mut transform in &mut q_ghost {
+        transform.translation = pos;
+    }
+}
+
+/// 游戏结束时保存最佳轨迹
+///
+/// 本局得分超过同一种子和模式的最佳成绩，或该种子和模式还没有轨迹时保存本局轨迹，
+/// 其他种子和模式的轨迹保持不变
+pub fn save_best_run_ghost(
+    score: Res<Score>,
+    replay_log: Res<ReplayLog>,
+    mut recorder: ResMut<GhostRecorder>,
+    mut best_run: ResMut<BestRunGhost>,
+) {
+    if !recorder.recording || recorder.samples.is_empty() {
+        return;
+    }
+    recorder.recording = false;
+    // 只有确实要替换时才修改资源，避免触发存档写入
+    if best_run
+        .for_seed(replay_log.seed, recorder.mode)
+        .is_some_and(|run| run.score >= score.0)
+    {
+        return;
+    }
+    info!(
+        "New best run ghost for seed {} with score {}",
+        replay_log.seed, score.0
+    );
+    best_run.insert(GhostRun {
+        seed: replay_log.seed,
+        mode: recorder.mode,
+        score: score.0,
+        samples: std::mem::take(&mut recorder.samples),
+    });
+}
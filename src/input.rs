@@ -0,0 +1,364 @@
+// 导入Bevy的主要组件
+use bevy::prelude::*;
+// 导入系统参数派生宏，用于组合按键绑定和输入状态
+use bevy::ecs::system::SystemParam;
+// 导入序列化库，用于把按键绑定写入存档
+use serde::{Deserialize, Serialize};
+
+// 导入UI模块中的游戏状态、菜单按钮、文字按钮和按钮交互查询
+use crate::ui::{
+    spawn_text_button, ButtonInteractions, GameState, MenuButtonAction, StateTransition,
+    TEXT_BUTTON_COLOR,
+};
+
+/// 单个输入绑定，可以是键盘按键或鼠标按键
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InputBinding {
+    Key(KeyCode),       // 键盘按键
+    Mouse(MouseButton), // 鼠标按键
+}
+
+impl InputBinding {
+    /// 按键的显示名称
+    ///
+    /// 去掉`Key`、`Digit`等前缀，使按键设置界面更简洁
+    pub fn label(&self) -> String {
+        match self {
+            InputBinding::Key(key) => {
+                let name = format!("{:?}", key);
+                name.strip_prefix("Key")
+                    .or_else(|| name.strip_prefix("Digit"))
+                    .unwrap_or(&name)
+                    .to_string()
+            }
+            InputBinding::Mouse(button) => format!("Mouse {:?}", button),
+        }
+    }
+}
+
+/// 可以绑定按键的逻辑操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputAction {
+    Charge,  // 蓄力，按住蓄力、松开跳跃
+    Pause,   // 暂停或继续游戏
+    Restart, // 重新开始本局
+    Mute,    // 切换静音
+    SwitchTarget, // 分岔路径时切换瞄准的平台
+}
+
+impl InputAction {
+    /// 所有逻辑操作，按设置界面中的显示顺序排列
+    pub const ALL: [InputAction; 5] = [
+        InputAction::Charge,
+        InputAction::Pause,
+        InputAction::Restart,
+        InputAction::Mute,
+        InputAction::SwitchTarget,
+    ];
+
+    /// 操作的显示名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            InputAction::Charge => "Charge",
+            InputAction::Pause => "Pause",
+            InputAction::Restart => "Restart",
+            InputAction::Mute => "Mute",
+            InputAction::SwitchTarget => "Switch target",
+        }
+    }
+}
+
+/// 按键绑定资源，记录每个逻辑操作对应的按键，写入存档
+///
+/// 缺失的字段使用默认绑定，保证旧存档可以正常读取
+#[derive(Debug, Clone, Resource, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InputBindings {
+    /// 蓄力
+    pub charge: InputBinding,
+    /// 暂停
+    pub pause: InputBinding,
+    /// 重新开始
+    pub restart: InputBinding,
+    /// 静音
+    pub mute: InputBinding,
+    /// 切换瞄准的平台
+    pub switch_target: InputBinding,
+}
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        Self {
+            charge: InputBinding::Mouse(MouseButton::Left),
+            pause: InputBinding::Key(KeyCode::Escape),
+            restart: InputBinding::Key(KeyCode::KeyR),
+            mute: InputBinding::Key(KeyCode::KeyM),
+            switch_target: InputBinding::Key(KeyCode::Tab),
+        }
+    }
+}
+
+impl InputBindings {
+    /// 获取操作当前绑定的按键
+    pub fn get(&self, action: InputAction) -> InputBinding {
+        match action {
+            InputAction::Charge => self.charge,
+            InputAction::Pause => self.pause,
+            InputAction::Restart => self.restart,
+            InputAction::Mute => self.mute,
+            InputAction::SwitchTarget => self.switch_target,
+        }
+    }
+
+    /// 获取操作绑定的可变引用
+    fn get_mut(&mut self, action: InputAction) -> &mut InputBinding {
+        match action {
+            InputAction::Charge => &mut self.charge,
+            InputAction::Pause => &mut self.pause,
+            InputAction::Restart => &mut self.restart,
+            InputAction::Mute => &mut self.mute,
+            InputAction::SwitchTarget => &mut self.switch_target,
+        }
+    }
+
+    /// 重新绑定操作的按键
+    ///
+    /// 新按键已被其他操作使用时，两个操作交换按键，保证每个按键只对应一个操作
+    ///
+    /// # 参数
+    /// - `action`: 要重新绑定的操作
+    /// - `binding`: 新的按键
+    pub fn set(&mut self, action: InputAction, binding: InputBinding) {
+        let old_binding = self.get(action);
+        if let Some(other) = InputAction::ALL
+            .into_iter()
+            .find(|&other| other != action && self.get(other) == binding)
+        {
+            *self.get_mut(other) = old_binding;
+        }
+        *self.get_mut(action) = binding;
+    }
+}
+
+/// 逻辑操作输入系统参数
+///
+/// 根据按键绑定查询键盘和鼠标输入，其他系统只关心逻辑操作，不直接读取具体按键
+#[derive(SystemParam)]
+pub struct ActionInput<'w> {
+    bindings: Res<'w, InputBindings>,
+    keys: Res<'w, ButtonInput<KeyCode>>,
+    buttons: Res<'w, ButtonInput<MouseButton>>,
+}
+
+impl ActionInput<'_> {
+    /// 本帧是否按下了操作绑定的按键
+    pub fn just_pressed(&self, action: InputAction) -> bool {
+        match self.bindings.get(action) {
+            InputBinding::Key(key) => self.keys.just_pressed(key),
+            InputBinding::Mouse(button) => self.buttons.just_pressed(button),
+        }
+    }
+
+    /// 本帧是否释放了操作绑定的按键
+    pub fn just_released(&self, action: InputAction) -> bool {
+        match self.bindings.get(action) {
+            InputBinding::Key(key) => self.keys.just_released(key),
+            InputBinding::Mouse(button) => self.buttons.just_released(button),
+        }
+    }
+
+    /// 操作绑定的按键的显示名称，用于提示文字
+    pub fn label(&self, action: InputAction) -> String {
+        self.bindings.get(action).label()
+    }
+}
+
+/// 正在重新绑定的操作，等待玩家按下新的按键
+#[derive(Debug, Default, Resource)]
+pub struct Rebinding(pub Option<InputAction>);
+
+/// 标记按键设置界面元素的组件
+#[derive(Component)]
+pub struct OnControlsScreen;
+
+/// 按键设置界面中重新绑定按钮的功能组件
+#[derive(Component)]
+pub struct RebindButton(pub InputAction);
+
+/// 标记按键名称文本的组件，记录对应的操作
+#[derive(Component)]
+pub struct BindingText(pub InputAction);
+
+/// 设置按键设置界面
+///
+/// 每个操作一行，点击按键按钮后按下新的按键或鼠标按键完成绑定
+pub fn setup_controls_menu(mut commands: Commands, bindings: Res<InputBindings>) {
+    commands
+        .spawn((
+            Node { // 主容器节点，全屏覆盖
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            OnControlsScreen, // 标记为属于按键设置界面的元素
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((Node { // 垂直排列的内容容器
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },))
+                .with_children(|parent| {
+                    // 标题
+                    parent.spawn((
+                        Text::new("Controls"),
+                        TextColor(Color::BLACK),
+                        TextFont {
+                            font_size: 50.0,
+                            ..default()
+                        },
+                        Node {
+                            margin: UiRect::all(Val::Px(20.0)),
+                            ..default()
+                        },
+                    ));
+
+                    // 每个操作一行
+                    for action in InputAction::ALL {
+                        parent
+                            .spawn((Node { // 水平排列的按键设置容器
+                                flex_direction: FlexDirection::Row,
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },))
+                            .with_children(|parent| {
+                                parent.spawn((
+                                    Text::new(action.name()),
+                                    TextColor(Color::BLACK),
+                                    TextFont {
+                                        font_size: 30.0,
+                                        ..default()
+                                    },
+                                    Node {
+                                        min_width: Val::Px(140.0),
+                                        ..default()
+                                    },
+                                ));
+                                parent
+                                    .spawn((
+                                        Button, // 按钮交互组件
+                                        Node { // 按钮样式
+                                            min_width: Val::Px(160.),
+                                            height: Val::Px(40.),
+                                            padding: UiRect::horizontal(Val::Px(12.0)),
+                                            margin: UiRect::all(Val::Px(6.0)),
+                                            justify_content: JustifyContent::Center,
+                                            align_items: AlignItems::Center,
+                                            ..default()
+                                        },
+                                        BackgroundColor(TEXT_BUTTON_COLOR),
+                                        BorderRadius::all(Val::Px(8.0)),
+                                        RebindButton(action), // 按钮功能标记
+                                    ))
+                                    .with_child((
+                                        Text::new(bindings.get(action).label()),
+                                        TextColor(Color::WHITE),
+                                        TextFont {
+                                            font_size: 24.0,
+                                            ..default()
+                                        },
+                                        BindingText(action), // 标记为按键名称文本
+                                    ));
+                            });
+                    }
+
+                    // 返回设置界面按钮
+                    spawn_text_button(parent, "Back", MenuButtonAction::BackToSettings);
+                });
+        });
+}
+
+/// 处理重新绑定按钮点击事件
+///
+/// 点击后进入等待按键状态，再次点击同一个按钮取消
+pub fn click_rebind_button(
+    interaction_query: ButtonInteractions<RebindButton>,
+    mut rebinding: ResMut<Rebinding>,
+) {
+    for (interaction, rebind_button) in &interaction_query {
+        // 只有在按钮被按下时处理
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        rebinding.0 = if rebinding.0 == Some(rebind_button.0) {
+            None
+        } else {
+            Some(rebind_button.0)
+        };
+    }
+}
+
+/// 捕获新的按键
+///
+/// 等待按键状态下，把下一个按下的键盘按键或鼠标按键绑定到操作上
+/// 需要在处理按钮点击之前运行，避免把点击按钮的那次鼠标按下当作新的按键
+pub fn capture_rebinding(
+    keys: Res<ButtonInput<KeyCode>>,
+    buttons: Res<ButtonInput<MouseButton>>,
+    mut rebinding: ResMut<Rebinding>,
+    mut bindings: ResMut<InputBindings>,
+) {
+    let Some(action) = rebinding.0 else {
+        return;
+    };
+    let binding = keys
+        .get_just_pressed()
+        .next()
+        .map(|&key| InputBinding::Key(key))
+        .or_else(|| buttons.get_just_pressed().next().map(|&button| InputBinding::Mouse(button)));
+    if let Some(binding) = binding {
+        info!("Bind {} to {}", action.name(), binding.label());
+        bindings.set(action, binding);
+        rebinding.0 = None;
+    }
+}
+
+/// 更新按键名称显示
+///
+/// 等待按键的操作显示提示文字，其余显示当前绑定的按键
+pub fn update_binding_text(
+    bindings: Res<InputBindings>,
+    rebinding: Res<Rebinding>,
+    mut q_binding_text: Query<(&mut Text, &BindingText)>,
+) {
+    if !bindings.is_changed() && !rebinding.is_changed() {
+        return;
+    }
+    for (mut text, binding_text) in &mut q_binding_text {
+        text.0 = if rebinding.0 == Some(binding_text.0) {
+            "Press a key...".to_string()
+        } else {
+            bindings.get(binding_text.0).label()
+        };
+    }
+}
+
+/// 清除等待按键状态
+///
+/// 离开按键设置界面时调用，避免下次进入时仍在等待按键
+pub fn reset_rebinding(mut rebinding: ResMut<Rebinding>) {
+    rebinding.0 = None;
+}
+
+/// 按重新开始键重新开始游戏
+///
+/// 游戏结束界面中与重新开始按钮作用相同
+pub fn restart_on_key(input: ActionInput, mut state_transition: StateTransition) {
+    if input.just_pressed(InputAction::Restart) {
+        info!("Restart key pressed");
+        state_transition.go(GameState::Playing);
+    }
+}
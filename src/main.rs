@@ -2,21 +2,41 @@
 use std::time::Duration;
 
 // 导入游戏各模块中的所有公共功能
+use crate::audio::*;     // 音效相关功能
 use crate::camera::*;    // 相机相关功能
+use crate::demo::*;      // 主菜单演示相关功能
+use crate::ghost::*;     // 最佳成绩幽灵相关功能
+use crate::input::*;     // 按键绑定相关功能
 use crate::platform::*;  // 平台相关功能
 use crate::player::*;    // 玩家相关功能
+use crate::powerup::*;   // 道具相关功能
+use crate::replay::*;    // 录像相关功能
+use crate::settings::*;  // 设置和存档相关功能
 use crate::ui::*;        // UI和游戏状态相关功能
+use crate::window::*;    // 窗口配置相关功能
 
 // 导入Bevy游戏引擎的主要功能
 use bevy::prelude::*;
+// 导入帧时间诊断插件（用于调试信息中的帧率显示）
+use bevy::diagnostic::FrameTimeDiagnosticsPlugin;
+// 导入UI系统集，键盘菜单导航在UI交互检测之后运行
+use bevy::ui::UiSystem;
 // 导入粒子效果插件（用于蓄力特效）
 use bevy_hanabi::prelude::*;
 
 // 声明游戏的各个模块
+mod audio;     // 处理根据游戏事件播放音效
 mod camera;    // 处理相机设置和跟随
+mod demo;      // 处理主菜单空闲时的自动演示
+mod ghost;     // 处理最佳成绩幽灵的记录和回放
+mod input;     // 处理按键绑定和按键设置界面
 mod platform;  // 处理平台生成和逻辑
 mod player;    // 处理玩家角色的行为和动画
+mod powerup;   // 处理平台上道具的生成、动画和拾取
+mod replay;    // 处理录像记录、保存和回放
+mod settings;  // 处理设置界面和存档读写
 mod ui;        // 处理用户界面和游戏状态
+mod window;    // 处理窗口标题、大小、图标和全屏切换
 
 /// 游戏的主入口函数
 /// 
@@ -26,8 +46,17 @@ fn main() {
     // 创建新的Bevy应用实例
     let mut app = App::new();
     
-    // 添加Bevy的默认插件（渲染、窗口管理、输入处理等核心功能）
-    app.add_plugins(DefaultPlugins);
+    // 窗口配置，决定主窗口的标题、大小和图标
+    let window_config = WindowConfig::default();
+
+    // 添加Bevy的默认插件（渲染、窗口管理、输入处理等核心功能），使用窗口配置创建主窗口
+    app.add_plugins(DefaultPlugins.set(WindowPlugin {
+        primary_window: Some(window_config.window()),
+        ..default()
+    }));
+
+    // 添加帧时间诊断插件，为调试信息提供帧率数据
+    app.add_plugins(FrameTimeDiagnosticsPlugin);
 
     // 仅在非Web平台添加粒子效果插件
     // Web平台(wasm32)可能不支持某些粒子效果功能
@@ -36,30 +65,240 @@ fn main() {
         app.add_plugins(HanabiPlugin);
     }
 
+    // 读取存档，恢复上次保存的设置和最高分
+    let save_data = SaveData::load();
+    
+    // 蓄力粒子效果质量，决定粒子生成间隔
+    let particle_quality = ParticleQuality::from_index(save_data.particle_quality);
+
     // 初始化游戏状态和各种资源
     // 这些资源将在整个游戏运行过程中保持，并可被不同系统访问和修改
     app
         // 初始化游戏状态机，默认为主菜单状态
         .init_state::<GameState>()
         
+        // 游戏进行中的暂停子状态，离开游戏进行状态时自动移除
+        .add_sub_state::<PauseState>()
+        
+        // 主菜单演示子状态，离开主菜单状态时自动移除
+        .add_sub_state::<DemoState>()
+        
+        // 主菜单空闲计时器，空闲一段时间后开始演示
+        .insert_resource(DemoIdleTimer::default())
+        
+        // 演示自动跳跃状态
+        .insert_resource(DemoJump::default())
+        
+        // 按键绑定资源，从存档恢复
+        .insert_resource(save_data.input_bindings.clone())
+        
+        // 正在重新绑定的操作，按键设置界面使用
+        .insert_resource(Rebinding::default())
+        
         // 相机移动状态资源，用于控制相机跟随逻辑
         .insert_resource(CameraMoveState::default())
         
+        // 窗口配置资源，供设置窗口图标使用
+        .insert_resource(window_config)
+        
+        // 光照主题资源，从存档恢复
+        .insert_resource(LightingTheme::from_index(save_data.lighting_theme))
+        
+        // 相机配置资源，控制视角偏移、跟随速度和视野角度
+        .insert_resource(CameraConfig::default())
+        
+        // 相机震动资源，摔落时产生震动反馈
+        .insert_resource(CameraShake::default())
+        
+        // 拍照模式资源，游戏结束界面可自由环绕相机截图
+        .insert_resource(PhotoMode::default())
+        
         // 游戏分数资源，初始为0
         .insert_resource(Score(0))
         
+        // 连续完美落地次数资源，初始为0
+        .insert_resource(Combo::default())
+        
+        // 连击加速资源，记录未被打断的连续完美落地次数，初始为0
+        .insert_resource(ChainSpeed::default())
+        
+        // 本局统计资源，记录跳跃次数、完美落地次数和最长连击
+        .insert_resource(RunStats::default())
+        
+        // 最高分资源，从存档恢复
+        .insert_resource(HighScore(save_data.high_score))
+        
+        // 无尽模式排行榜资源，从存档恢复
+        .insert_resource(Leaderboard::from_scores(save_data.leaderboard.clone()))
+        
+        // 最佳成绩幽灵，从存档恢复，与排行榜一起保存
+        .insert_resource(BestRunGhost::from_runs(save_data.best_run_ghosts.clone()))
+        
+        // 本局幽灵轨迹记录
+        .insert_resource(GhostRecorder::default())
+        
+        // 每日挑战最高分资源，从存档恢复
+        .insert_resource(DailyHighScore {
+            date_seed: save_data.daily_date_seed,
+            score: save_data.daily_high_score,
+        })
+        
+        // 限时挑战最高分资源，从存档恢复
+        .insert_resource(TimeAttackHighScore(save_data.time_attack_high_score))
+        
+        // 分岔路径挑战最高分资源，从存档恢复
+        .insert_resource(ForkedHighScore(save_data.forked_high_score))
+        
+        // 限时挑战计时资源，每局开始时重置
+        .insert_resource(TimeAttack::default())
+        
+        // 游戏模式资源，默认无尽模式
+        .insert_resource(GameMode::default())
+        
+        // 练习模式资源，从主菜单练习按钮进入时开启
+        .insert_resource(PracticeMode::default())
+        
+        // 全局音量资源，从存档恢复
+        .insert_resource(Volume(save_data.volume))
+        
+        // 静音开关，从存档恢复
+        .insert_resource(AudioMuted(save_data.audio_muted))
+        
+        // 全局音量，静音时为0，之后生成的音效都不会发声
+        .insert_resource(GlobalVolume::new(if save_data.audio_muted { 0.0 } else { 1.0 }))
+        
+        // 平台中心标记显示开关，从存档恢复
+        .insert_resource(ShowTargetMarker(save_data.show_target_marker))
+        
+        // 状态切换过渡效果开关，从存档恢复
+        .insert_resource(FadeTransitions(save_data.fade_transitions))
+        
+        // 安全网开关，从存档恢复
+        .insert_resource(SafetyNet(save_data.safety_net))
+        
+        // 过度蓄力取消开关，从存档恢复
+        .insert_resource(FizzleOnOvercharge(save_data.fizzle_on_overcharge))
+        
+        // 玩家颜色随分数变化开关，从存档恢复
+        .insert_resource(ScoreColorProgression(save_data.score_color_progression))
+        
+        // 连击加速开关，从存档恢复
+        .insert_resource(ComboChains(save_data.combo_chains))
+        
+        // 摔落暗角开关，从存档恢复
+        .insert_resource(FallVignette(save_data.fall_vignette))
+        
+        // 下一个平台发光脉冲开关，从存档恢复
+        .insert_resource(PulseNextPlatform(save_data.pulse_next_platform))
+        
+        // 新平台生成动画开关，从存档恢复
+        .insert_resource(PlatformSpawnAnimation(save_data.platform_spawn_animation))
+        
+        // 跳跃时面朝落点方向开关，从存档恢复
+        .insert_resource(FaceJumpDirection(save_data.face_jump_direction))
+        
+        // 落地容差倍率，从存档恢复，简单模式放大落地判定范围
+        .insert_resource(LandingTolerance(save_data.landing_tolerance))
+        
+        // 快速点按处理方式，从存档恢复，默认取消蓄力过短的跳跃
+        .insert_resource(ShortTapBehavior::from_index(save_data.short_tap))
+        
+        // 失去焦点时自动暂停开关，从存档恢复
+        .insert_resource(AutoPause(save_data.auto_pause))
+        
+        // 存档待写入标记，修改后最多每秒写入一次存档
+        .insert_resource(SettingsDirty::default())
+        
+        // 是否已经看过新手引导，从存档恢复
+        .insert_resource(SeenTutorial(save_data.seen_tutorial))
+        
+        // 是否使用正交相机，从存档恢复
+        .insert_resource(OrthographicCamera(save_data.orthographic_camera))
+        
+        // 剩余重试次数，每局开始时重置
+        .insert_resource(Retries::default())
+        
+        // 道具配置，控制道具出现概率、种类和效果
+        .insert_resource(PowerUpConfig::default())
+        
+        // 道具资源池，复用道具网格和材质
+        .insert_resource(PowerUpAssetPool::default())
+        
+        // 放宽完美判定道具的剩余效果
+        .insert_resource(WidePerfectBoost::default())
+        
+        // 状态切换过渡资源，驱动全屏遮罩的淡入淡出
+        .insert_resource(Transition::default())
+        
         // 蓄力状态资源，存储玩家当前的蓄力值和开始时间
         .insert_resource(Accumulator(None))
         
+        // 最大蓄力时长资源，超过该时长继续按住不再增加跳跃距离
+        .insert_resource(MaxCharge::default())
+        
+        // 物理手感调节资源，集中管理跳跃和摔落参数，蓄力曲线从存档恢复
+        .insert_resource(PhysicsTuning {
+            charge_curve: ChargeCurve::from_index(save_data.charge_curve),
+            ..default()
+        })
+        
+        // 触摸蓄力资源，记录开始蓄力的手指
+        .insert_resource(ChargeTouch::default())
+        
+        // 难度配置资源，控制平台间距随分数增长
+        .insert_resource(Difficulty::default())
+        
+        // 游戏随机数生成器和种子配置，用于生成可复现的平台序列
+        .insert_resource(GameRng::default())
+        .insert_resource(GameSeed::default())
+        
+        // 录像记录和回放状态，默认为空
+        .insert_resource(ReplayLog::default())
+        .insert_resource(ReplayPlayback::default())
+        
+        // 平台配色，可替换以切换配色主题
+        .insert_resource(PlatformPalette::default())
+        
+        // 平台资源池，复用平台网格和材质
+        .insert_resource(PlatformAssetPool::default())
+        
+        // 平台生成计数和同时存在的平台数量上限
+        .insert_resource(PlatformSpawnCounter::default())
+        .insert_resource(MaxPlatforms::default())
+        
+        // 第一个平台配置，教程或特殊模式可固定第一个平台的形状和位置
+        .insert_resource(StartPlatformConfig::default())
+        
         // 跳跃状态资源，控制跳跃动画和逻辑流程
         .insert_resource(JumpState::default())
         
         // 摔落状态资源，控制摔落动画和逻辑流程
         .insert_resource(FallState::default())
         
-        // 蓄力粒子特效计时器，控制特效生成频率（每200毫秒生成一次）
+        // 蓄力粒子效果质量，从存档恢复
+        .insert_resource(particle_quality)
+        
+        // 抗锯齿等级，从存档恢复
+        .insert_resource(MsaaLevel::from_index(save_data.msaa_level))
+        
+        // 泛光强度，从存档恢复，关闭时同时关闭HDR
+        .insert_resource(BloomLevel::from_index(save_data.bloom_level))
+        
+        // 平台材质风格，从存档恢复，只影响新生成的平台
+        .insert_resource(PlatformStyle::from_index(save_data.platform_style))
+        
+        // 阴影质量，从存档恢复，网页版默认关闭阴影
+        .insert_resource(ShadowQuality::from_index(save_data.shadow_quality))
+        
+        // 色盲配色模式，从存档恢复
+        .insert_resource(ColorblindMode::from_index(save_data.colorblind_mode))
+        
+        // 帧率限制，从存档恢复，默认开启垂直同步
+        .insert_resource(FrameRateLimit::from_index(save_data.frame_rate_limit))
+        
+        // 蓄力粒子特效计时器，控制特效生成频率（默认每200毫秒生成一次，由粒子效果质量决定）
         .insert_resource(GenerateAccumulationParticleEffectTimer(Timer::new(
-            Duration::from_millis(200),
+            particle_quality.spawn_interval(),
             TimerMode::Once, // 一次性计时器，每次触发后需手动重置
         )))
         
@@ -70,8 +309,38 @@ fn main() {
             TimerMode::Once,
         )))
         
-        // 分数上升效果队列，用于存储和显示得分动画信息
-        .insert_resource(ScoreUpQueue(Vec::new()))
+        // 蓄力过度状态，现在松开会跳过下一个平台时为true
+        .insert_resource(ChargeOvershoot::default())
+        
+        // 开局倒计时计时器，倒计时结束前忽略玩家输入
+        .insert_resource(CountdownTimer::default())
+        
+        // 开局入场动画计时器，每局开始时重置
+        .insert_resource(IntroAnimation::default())
+        
+        // 得分事件，计分逻辑发送，飘分效果等系统读取
+        .add_event::<ScoreUpEvent>()
+        
+        // 分数里程碑间隔资源，默认每10分庆祝一次
+        .insert_resource(MilestoneInterval::default())
+        
+        // 分数里程碑事件，触发礼花粒子效果
+        .add_event::<MilestoneEvent>()
+        
+        // 游戏事件，游戏逻辑发送，音效系统读取后播放对应音效
+        .add_event::<GameEvent>()
+        
+        // 完美落地闪白计时器
+        .insert_resource(PerfectFlash::default())
+        
+        // 跳跃轨迹颜色，可由皮肤修改
+        .insert_resource(JumpTrailColor::default())
+        
+        // 玩家皮肤，从存档恢复上次选择的皮肤
+        .insert_resource(PlayerSkin::from_index(save_data.skin))
+        
+        // 键盘菜单导航焦点，初始没有焦点
+        .insert_resource(MenuFocus::default())
         
         // ===== 启动时执行的系统 =====
         // 这些系统仅在游戏首次启动时执行一次
@@ -79,6 +348,10 @@ fn main() {
             setup_camera,    // 设置3D相机和光照
             setup_ground,    // 创建地面平面
             setup_game_sounds, // 加载游戏音效资源
+            setup_debug_overlay, // 创建默认隐藏的调试信息
+            setup_fade_overlay,  // 创建状态切换遮罩
+            setup_mute_indicator, // 创建右下角静音状态图标
+            setup_jump_trail_assets, // 创建跳跃轨迹共用的网格和材质
         ))
         
         // ===== 主菜单状态 =====
@@ -90,12 +363,71 @@ fn main() {
                 clear_player,        // 清除可能存在的玩家实体
                 clear_platforms,     // 清除可能存在的平台实体
                 despawn_scoreboard,  // 清除可能存在的计分板UI
+                despawn_screen::<BackgroundMusic>, // 停止并销毁背景音乐
             ),
         )
         .add_systems(
             // 主菜单状态下每帧更新的系统
             Update,
-            (click_button,).run_if(in_state(GameState::MainMenu)), // 处理按钮点击事件
+            (
+                click_button,       // 处理按钮点击事件
+                click_skin_button,  // 处理皮肤选择按钮
+                update_skin_text,   // 更新皮肤名称显示
+                toggle_quit_confirm, // 按Esc打开或关闭退出确认对话框
+            )
+                .run_if(in_state(GameState::MainMenu)),
+        )
+        
+        // ===== 主菜单演示 =====
+        .add_systems(
+            // 进入空闲状态（进入主菜单或演示结束）时重新计时
+            OnEnter(DemoState::Idle),
+            reset_demo_idle_timer,
+        )
+        .add_systems(
+            // 主菜单空闲计时，空闲足够久后开始演示
+            Update,
+            tick_demo_idle_timer.run_if(in_state(DemoState::Idle)),
+        )
+        .add_systems(
+            // 开始演示时生成平台和玩家，复用游戏开局的系统
+            OnEnter(DemoState::Running),
+            (
+                clear_player,     // 清除可能存在的玩家实体
+                clear_platforms,  // 清除可能存在的平台实体
+                setup_first_platform.after(clear_platforms), // 设置第一个平台
+                setup_player.after(clear_player),           // 设置玩家
+                start_demo,       // 重置分数、跳过倒计时并给予无限重试
+                disable_forked_paths, // 演示中不出现分岔路径
+            ),
+        )
+        .add_systems(
+            // 演示中每帧更新的系统，跳跃逻辑与游戏进行状态完全相同
+            Update,
+            (
+                stop_demo_on_input,                // 玩家有任何操作时结束演示
+                demo_auto_jump.before(player_jump), // 按理想蓄力时长自动蓄力和松开
+                generate_next_platform,            // 生成下一个平台
+                despawn_passed_platforms,          // 销毁远离玩家的已跳过平台
+                move_platforms,                    // 更新移动平台位置
+                move_camera,                       // 相机跟随玩家移动
+                player_jump,                       // 玩家跳跃核心逻辑
+                animate_jump,                      // 执行跳跃动画
+                animate_fall,                      // 执行摔落动画，演示中摔落后直接重试
+                animate_player_accumulation.after(animate_platform_accumulation), // 玩家蓄力视觉效果
+                animate_platform_accumulation.after(player_jump), // 平台蓄力效果
+            )
+                .run_if(in_state(DemoState::Running)),
+        )
+        .add_systems(
+            // 结束演示（玩家操作或开始游戏）时清理演示场景
+            OnExit(DemoState::Running),
+            (
+                clear_player,      // 清除演示玩家
+                clear_platforms,   // 清除演示平台
+                clear_accumulator, // 清除蓄力状态
+                reset_demo,        // 重置跳跃、摔落和演示状态
+            ),
         )
         .add_systems(
             // 退出主菜单状态时执行的一次性系统
@@ -111,11 +443,23 @@ fn main() {
                 clear_player,                   // 清除旧的玩家实体
                 clear_platforms,                // 清除旧的平台实体
                 despawn_scoreboard,             // 清除旧的计分板
-                setup_first_platform.after(clear_platforms), // 设置第一个平台（注意依赖关系）
+                seed_game_rng,                  // 使用种子初始化随机数生成器
+                setup_first_platform.after(clear_platforms).after(seed_game_rng), // 设置第一个平台（注意依赖关系）
                 setup_player.after(clear_player),           // 设置玩家（注意依赖关系）
+                settle_wobble,                  // 结束上一局未完成的落地摇晃
                 setup_scoreboard.after(despawn_scoreboard), // 设置计分板（注意依赖关系）
+                setup_charge_bar,               // 设置蓄力条
+                setup_next_platform_arrow,      // 设置下一个平台指示箭头
                 reset_score,                    // 重置分数为0
+                reset_retries,                  // 根据安全网设置重置重试次数
                 reset_prepare_jump_timer,       // 重置准备跳跃计时器
+                reset_countdown_timer,          // 重置开局倒计时
+                reset_intro_animation,          // 重置开局入场动画
+                setup_countdown,                // 显示开局倒计时
+                setup_tutorial_hint,            // 首次游戏时显示新手引导
+                setup_perfect_flash,            // 创建完美落地闪白遮罩
+                setup_daily_label,              // 每日挑战时显示日期和种子
+                play_background_music,          // 播放背景音乐
             ),
         )
         .add_systems(
@@ -124,43 +468,543 @@ fn main() {
             (
                 // 游戏核心逻辑系统，按特定顺序执行
                 prepare_jump,                      // 更新准备跳跃计时器
+                update_countdown,                  // 更新开局倒计时
                 generate_next_platform,            // 生成下一个平台
+                despawn_passed_platforms,          // 销毁远离玩家的已跳过平台
+                update_target_markers,             // 在下一个平台中心显示完美落地标记
+                move_platforms,                    // 更新移动平台位置
                 move_camera,                       // 相机跟随玩家移动
                 player_jump,                       // 玩家跳跃核心逻辑
+                update_landing_preview.after(player_jump), // 蓄力时显示落点预览
                 update_scoreboard,                 // 更新分数显示
+                update_retries_text,               // 更新剩余重试次数显示
+                update_charge_bar.after(update_landing_preview), // 更新蓄力条显示（跳过头时变红）
                 animate_jump,                      // 执行跳跃动画
                 animate_fall,                      // 执行摔落动画（如果需要）
-                animate_player_accumulation,       // 玩家蓄力视觉效果
+                animate_player_accumulation.after(animate_platform_accumulation), // 玩家蓄力视觉效果（跟随平台压缩高度）
                 animate_platform_accumulation.after(player_jump), // 平台蓄力效果（依赖跳跃逻辑）
                 spawn_score_up_effect,             // 生成得分上升效果
                 sync_score_up_effect,              // 同步得分效果位置到屏幕坐标
                 shift_score_up_effect,             // 处理得分效果的上移动画
             )
-                .run_if(in_state(GameState::Playing)), // 条件：仅在游戏进行状态执行
+                .run_if(in_state(GameState::Playing)) // 条件：仅在游戏进行状态执行
+                .run_if(in_state(PauseState::Running)) // 条件：未暂停
+                .run_if(transition_finished),         // 条件：状态过渡淡入完成后才继续游戏
+        )
+        .add_systems(
+            // 开局入场动画，第一个平台升起后玩家落到平台上，动画结束前不响应输入
+            // 淡入过程中也要运行，让画面停在动画起点
+            Update,
+            animate_intro
+                .before(player_jump)
+                .run_if(in_state(GameState::Playing))
+                .run_if(in_state(PauseState::Running)),
+        )
+        .add_systems(
+            // 分岔路径时切换瞄准的下一个平台
+            Update,
+            switch_aimed_platform
+                .before(player_jump)
+                .run_if(in_state(GameState::Playing))
+                .run_if(in_state(PauseState::Running))
+                .run_if(transition_finished),
+        )
+        .add_systems(
+            // 进入游戏时清除上一局未用完的道具效果
+            OnEnter(GameState::Playing),
+            reset_power_ups,
+        )
+        .add_systems(
+            // 按帧率限制设置切换垂直同步
+            Update,
+            apply_frame_rate_limit,
+        )
+        .add_systems(
+            // 色盲配色模式变化时更换平台调色板，启动时按存档设置一次
+            Update,
+            apply_colorblind_palette.before(generate_next_platform),
+        )
+        .add_systems(
+            // 道具相关系统，道具是平台的子实体，平台销毁时一起清理
+            Update,
+            (
+                spawn_power_ups.after(generate_next_platform), // 新平台按概率放置道具
+                animate_power_ups,                             // 道具漂浮自转
+                collect_power_ups.after(animate_jump),         // 成功落地后拾取道具
+            )
+                .run_if(in_state(GameState::Playing))
+                .run_if(in_state(PauseState::Running))
+                .run_if(transition_finished),
+        )
+        .add_systems(
+            // 平台数量超过上限时让最早的已跳过平台下沉消失，游戏进行和主菜单演示中都生效
+            Update,
+            (
+                enforce_max_platforms,      // 超过上限时标记最早的已跳过平台
+                animate_sinking_platforms,  // 下沉消失动画
+            )
+                .run_if(in_state(GameState::Playing).or(in_state(DemoState::Running))),
+        )
+        .add_systems(
+            // 新平台从零缩放长到完整大小，游戏进行和主菜单演示中都生效
+            Update,
+            animate_spawning_platforms
+                .run_if(in_state(GameState::Playing).or(in_state(DemoState::Running))),
+        )
+        .add_systems(
+            // 下一个平台周期性发光，吸引玩家注意跳跃目标
+            Update,
+            pulse_next_platform
+                .run_if(in_state(GameState::Playing))
+                .run_if(in_state(PauseState::Running)),
+        )
+        .add_systems(
+            // 平台机关相关系统
+            Update,
+            shrink_current_platform // 缩小玩家脚下的缩小平台
+                .before(player_jump)
+                .run_if(in_state(GameState::Playing))
+                .run_if(in_state(PauseState::Running))
+                .run_if(transition_finished),
+        )
+        .add_systems(
+            // 险些踩空时落地后摇晃，游戏进行和主菜单演示中都生效
+            Update,
+            animate_wobble
+                .after(animate_jump)
+                .run_if(
+                    in_state(PauseState::Running)
+                        .and(transition_finished)
+                        .or(in_state(DemoState::Running)),
+                ),
+        )
+        .add_systems(
+            // 成功落地后玩家压扁再恢复，游戏进行和主菜单演示中都生效
+            Update,
+            animate_landing_squash
+                .after(animate_jump)
+                .before(animate_player_accumulation)
+                .run_if(
+                    in_state(PauseState::Running)
+                        .and(transition_finished)
+                        .or(in_state(DemoState::Running)),
+                ),
+        )
+        .add_systems(
+            // 开局不能跳跃时按下蓄力，玩家摇头提示输入被忽略
+            Update,
+            (
+                reject_early_input.before(player_jump), // 检测被忽略的蓄力输入
+                animate_rejected_input_shake,           // 摇头动画
+            )
+                .run_if(in_state(GameState::Playing))
+                .run_if(in_state(PauseState::Running))
+                .run_if(transition_finished),
+        )
+        .add_systems(
+            // 玩家影子跟随玩家，游戏进行和主菜单演示中都显示
+            Update,
+            update_player_shadow
+                .after(animate_jump)
+                .after(animate_wobble)
+                .after(animate_landing_squash)
+                .after(animate_fall)
+                .after(animate_player_accumulation),
+        )
+        .add_systems(
+            // 跳跃轨迹相关系统
+            Update,
+            (
+                spawn_jump_trail.after(animate_jump), // 跳跃中生成轨迹点
+                fade_jump_trail,                      // 轨迹点淡出并销毁
+            )
+                .run_if(in_state(GameState::Playing))
+                .run_if(in_state(PauseState::Running)),
+        )
+        .add_systems(
+            // 新手引导和完美落地闪白等提示效果
+            Update,
+            (
+                update_tutorial_hint, // 新手引导提示
+                update_perfect_flash, // 完美落地闪白衰减
+                detect_score_milestone, // 分数越过里程碑时庆祝
+                start_milestone_pulse.after(detect_score_milestone), // 达到里程碑时开始计分板脉冲
+                animate_milestone_pulse, // 计分板里程碑脉冲动画
+                update_next_platform_arrow, // 下一个平台靠近屏幕边缘时显示指示箭头
+                update_player_score_color, // 玩家颜色随分数变化
+            )
+                .run_if(in_state(GameState::Playing))
+                .run_if(in_state(PauseState::Running)),
+        )
+        .add_systems(
+            // 相机缩放和震动相关系统
+            Update,
+            (
+                trigger_fall_camera_shake.run_if(in_state(GameState::Playing)), // 摔落时触发震动
+                update_camera_zoom,              // 随分数平滑拉远相机
+                follow_ground,                   // 地面跟随玩家，保证视野内总有地面
+                recreate_camera_on_projection_change.before(shake_camera), // 切换投影模式时重新创建相机
+                shake_camera.after(move_camera).after(update_camera_zoom), // 叠加缩放和震动偏移，震动在状态切换后也能自然衰减
+            ),
+        )
+        .add_systems(
+            // 退出游戏进行状态时执行的一次性系统
+            OnExit(GameState::Playing),
+            (
+                despawn_screen::<OnChargeBar>, // 移除蓄力条
+                despawn_screen::<OnNextPlatformArrow>, // 移除下一个平台指示箭头
+                despawn_screen::<OnCountdown>, // 移除未结束的倒计时
+                despawn_screen::<OnTutorialHint>, // 移除未完成的新手引导
+                despawn_screen::<OnPerfectFlash>, // 移除完美落地闪白遮罩
+                despawn_screen::<OnDailyLabel>, // 移除每日挑战日期显示
+                despawn_screen::<OnPracticeLabel>, // 移除练习模式提示
+                despawn_screen::<OnFallVignette>, // 移除摔落暗角
+                reset_perfect_flash,           // 结束未完成的闪白
+                pause_background_music,        // 暂停背景音乐
+                clear_accumulator,             // 清除蓄力状态
+                despawn_screen::<AccumulationSound>, // 停止蓄力音效
+                despawn_screen::<JumpTrail>,   // 清除剩余的跳跃轨迹
+            ),
         )
         
+        // ===== 摔落暗角 =====
+        .add_systems(
+            // 进入游戏时创建透明的暗角遮罩
+            OnEnter(GameState::Playing),
+            setup_fall_vignette,
+        )
+        .add_systems(
+            // 摔落时暗角随下落深度加深
+            Update,
+            update_fall_vignette
+                .after(animate_fall)
+                .run_if(in_state(GameState::Playing))
+                .run_if(in_state(PauseState::Running)),
+        )
+
+        // ===== 最佳成绩幽灵 =====
+        .add_systems(
+            // 进入游戏时开始记录轨迹，有本种子的最佳轨迹时生成幽灵
+            OnEnter(GameState::Playing),
+            setup_ghost.after(seed_game_rng).after(start_replay_log),
+        )
+        .add_systems(
+            // 记录玩家位置，幽灵按最佳轨迹同步移动
+            Update,
+            (
+                record_ghost_run.after(animate_jump).after(animate_fall), // 记录玩家位置
+                animate_ghost,                                             // 按最佳轨迹移动幽灵
+            )
+                .run_if(in_state(GameState::Playing))
+                .run_if(in_state(PauseState::Running)),
+        )
+        .add_systems(
+            // 游戏结束时本局成绩更好则替换最佳轨迹
+            OnEnter(GameState::GameOver),
+            save_best_run_ghost,
+        )
+        .add_systems(
+            // 离开游戏进行状态时移除幽灵
+            OnExit(GameState::Playing),
+            despawn_screen::<Ghost>,
+        )
+
+        // ===== 练习模式 =====
+        .add_systems(
+            // 练习模式中用练习提示代替计分板
+            OnEnter(GameState::Playing),
+            setup_practice_label,
+        )
+        .add_systems(
+            // 练习模式提示中的结束练习按钮
+            Update,
+            click_button
+                .run_if(in_state(GameState::Playing))
+                .run_if(resource_equals(PracticeMode(true))),
+        )
+
+        // ===== 限时挑战 =====
+        .add_systems(
+            OnEnter(GameState::Playing),
+            (
+                reset_time_attack,       // 重置限时挑战计时
+                setup_time_attack_label, // 限时挑战时显示剩余时间
+            ).chain(),
+        )
+        .add_systems(
+            // 限时挑战计时，时间用完时结束本局
+            Update,
+            (
+                tick_time_attack,         // 限时挑战计时
+                update_time_attack_label, // 更新剩余时间显示
+            ).chain()
+                .run_if(in_state(GameState::Playing))
+                .run_if(in_state(PauseState::Running)) // 条件：未暂停
+                .run_if(resource_equals(GameMode::TimeAttack)),
+        )
+        .add_systems(
+            OnExit(GameState::Playing),
+            despawn_screen::<OnTimeAttackLabel>, // 移除限时挑战剩余时间显示
+        )
+
+        // ===== 拍照模式 =====
+        .add_systems(
+            // 游戏结束界面按P切换拍照模式
+            Update,
+            toggle_photo_mode.run_if(in_state(GameState::GameOver)),
+        )
+        .add_systems(
+            // 拍照模式中自由环绕相机，覆盖跟随和震动设置的相机位置
+            Update,
+            orbit_photo_camera
+                .after(toggle_photo_mode)
+                .after(shake_camera)
+                .run_if(photo_mode_active),
+        )
+        .add_systems(
+            // 离开游戏结束界面时退出拍照模式，恢复界面
+            OnExit(GameState::GameOver),
+            exit_photo_mode,
+        )
+
         // ===== 游戏结束状态 =====
         .add_systems(
             // 进入游戏结束状态时执行的一次性系统
             OnEnter(GameState::GameOver), 
-            (setup_game_over_menu,) // 设置游戏结束菜单UI
+            (
+                setup_game_over_menu, // 设置游戏结束菜单UI
+                update_high_score,    // 更新最高分
+            ),
         )
         .add_systems(
             // 游戏结束状态下每帧更新的系统
             Update,
-            (click_button,).run_if(in_state(GameState::GameOver)), // 处理按钮点击事件
+            (
+                click_button,   // 处理按钮点击事件
+                restart_on_key, // 按重新开始键重新开始
+            )
+                .run_if(in_state(GameState::GameOver)),
         )
         .add_systems(
             // 退出游戏结束状态时执行的一次性系统
             OnExit(GameState::GameOver),
             (despawn_screen::<OnGameOverMenuScreen>,), // 移除游戏结束菜单UI
+        )
+        
+        // ===== 卡顿补偿 =====
+        .add_systems(
+            // 卡顿的长帧不计入蓄力时长，需要在设置蓄力开始时刻的系统之前运行
+            Update,
+            compensate_charge_stall
+                .before(drive_replay)
+                .before(demo_auto_jump)
+                .before(player_jump),
+        )
+
+        // ===== 录像 =====
+        .add_systems(
+            // 进入游戏时开始记录本局录像
+            OnEnter(GameState::Playing),
+            start_replay_log,
+        )
+        .add_systems(
+            // 进入游戏时根据游戏模式开启或关闭分岔路径，在生成平台之前设置
+            OnEnter(GameState::Playing),
+            apply_forked_paths.before(setup_first_platform),
+        )
+        .add_systems(
+            // 回放录像时按录像中的时刻模拟蓄力输入
+            Update,
+            drive_replay
+                .before(player_jump)
+                .run_if(in_state(GameState::Playing))
+                .run_if(in_state(PauseState::Running))
+                .run_if(transition_finished),
+        )
+        .add_systems(
+            // 主菜单的回放按钮和游戏结束界面的保存按钮
+            Update,
+            click_replay_button
+                .run_if(in_state(GameState::MainMenu).or(in_state(GameState::GameOver))),
+        )
+        .add_systems(
+            // 离开游戏结束界面时结束回放，重新开始后恢复正常游戏
+            OnExit(GameState::GameOver),
+            stop_replay,
+        )
+        .add_systems(
+            // 回到主菜单时结束回放
+            OnEnter(GameState::MainMenu),
+            stop_replay,
+        )
+        
+        // ===== 设置界面状态 =====
+        .add_systems(
+            // 进入设置界面状态时执行的一次性系统
+            OnEnter(GameState::Settings),
+            (setup_settings_menu,), // 设置设置界面UI
+        )
+        .add_systems(
+            // 设置界面状态下每帧更新的系统
+            Update,
+            (
+                click_button,          // 处理返回按钮点击事件
+                click_settings_button, // 处理音量调节按钮点击事件
+                update_volume_text,    // 更新音量数值显示
+                update_setting_texts,  // 更新各选项按钮的显示文本
+            )
+                .run_if(in_state(GameState::Settings)),
+        )
+        .add_systems(
+            // 退出设置界面状态时执行的一次性系统
+            OnExit(GameState::Settings),
+            (despawn_screen::<OnSettingsScreen>,), // 移除设置界面UI
+        )
+        
+        // ===== 排行榜界面状态 =====
+        .add_systems(
+            // 进入排行榜界面状态时执行的一次性系统
+            OnEnter(GameState::Leaderboard),
+            (setup_leaderboard_menu,), // 设置排行榜界面UI
+        )
+        .add_systems(
+            // 排行榜界面状态下每帧更新的系统
+            Update,
+            (click_button,).run_if(in_state(GameState::Leaderboard)), // 处理返回按钮点击事件
+        )
+        .add_systems(
+            // 退出排行榜界面状态时执行的一次性系统
+            OnExit(GameState::Leaderboard),
+            (despawn_screen::<OnLeaderboardScreen>,), // 移除排行榜界面UI
+        )
+        
+        // ===== 暂停 =====
+        .add_systems(
+            // 游戏进行中按暂停键或窗口失去焦点时暂停
+            Update,
+            (
+                toggle_pause,               // 按暂停键暂停或继续
+                auto_pause_on_focus_change, // 窗口失去焦点时自动暂停
+            )
+                .run_if(in_state(GameState::Playing))
+                .run_if(transition_finished),
+        )
+        .add_systems(OnEnter(PauseState::Paused), pause_game) // 停止游戏时间并显示暂停提示
+        .add_systems(
+            // 继续游戏或离开游戏进行状态时恢复游戏时间
+            OnExit(PauseState::Paused),
+            (resume_game, despawn_screen::<OnPauseScreen>),
+        )
+        
+        // ===== 按键设置界面状态 =====
+        .add_systems(
+            // 进入按键设置界面状态时执行的一次性系统
+            OnEnter(GameState::Controls),
+            (setup_controls_menu,), // 设置按键设置界面UI
+        )
+        .add_systems(
+            // 按键设置界面状态下每帧更新的系统
+            Update,
+            (
+                capture_rebinding.before(click_rebind_button), // 捕获新的按键（在处理点击之前）
+                click_rebind_button, // 处理重新绑定按钮点击事件
+                click_button,        // 处理返回按钮点击事件
+                update_binding_text, // 更新按键名称显示
+            )
+                .run_if(in_state(GameState::Controls)),
+        )
+        .add_systems(
+            // 退出按键设置界面状态时执行的一次性系统
+            OnExit(GameState::Controls),
+            (despawn_screen::<OnControlsScreen>, reset_rebinding), // 移除按键设置界面UI并取消等待按键
+        )
+        
+        // ===== 设置与存档 =====
+        .add_systems(
+            // 任何状态下都运行的设置相关系统
+            Update,
+            (
+                apply_volume_to_sinks,              // 实时调整循环音效和背景音乐音量
+                toggle_mute,                        // 按M切换静音
+                update_mute_indicator,              // 更新静音状态图标
+                stop_accumulation_sound,            // 蓄力结束时停止蓄力音效
+                sync_jump_trail_color,              // 皮肤变化时同步跳跃轨迹颜色
+                apply_lighting_theme,               // 切换光照主题时更新光照
+                apply_msaa_level,                   // 切换抗锯齿等级时更新相机
+                apply_shadow_quality,               // 切换阴影质量时更新方向光阴影
+                apply_bloom_level,                  // 切换泛光强度时更新相机HDR和泛光
+                apply_camera_fov,                   // 修改视野角度时更新透视投影
+                update_transition,                  // 驱动状态切换的淡入淡出
+                toggle_debug_overlay,               // 按F3切换调试信息显示
+                toggle_fullscreen,                  // 按F11切换全屏
+                update_ui_scale,                    // 窗口大小变化时按比例缩放UI
+                update_debug_overlay,               // 更新调试信息中的帧率
+                save_settings,                      // 设置或最高分变化时写入存档（最多每秒一次）
+            ),
+        )
+        .add_systems(
+            // 键盘菜单导航，在UI交互检测之后模拟按钮按下，游戏进行中不响应
+            PreUpdate,
+            navigate_menu_with_keyboard
+                .after(UiSystem::Focus)
+                .run_if(not(in_state(GameState::Playing))),
+        )
+        .add_systems(
+            // 焦点描边和切换界面时重置焦点
+            Update,
+            (
+                reset_menu_focus.run_if(state_changed::<GameState>), // 切换游戏状态时清除焦点
+                update_menu_focus_outline,                           // 焦点按钮显示描边
+            )
+                .chain(),
+        )
+        .add_systems(
+            // 所有游戏逻辑之后根据本帧的游戏事件统一播放音效
+            PostUpdate,
+            play_sounds,
+        )
+        .add_systems(
+            // 退出游戏时写入尚未保存的修改
+            Last,
+            flush_settings_on_exit,
         );
 
     // 仅在非Web平台添加粒子效果动画系统
     // 为蓄力效果提供视觉反馈
     #[cfg(not(target_arch = "wasm32"))]
     {
-        app.add_systems(Update, animate_accumulation_particle_effect);
+        app.add_systems(
+            Update,
+            (
+                animate_accumulation_particle_effect, // 蓄力粒子效果
+                spawn_landing_dust_effect.run_if(in_state(GameState::Playing)), // 成功落地时生成扬尘
+                spawn_milestone_burst_effect,         // 达到分数里程碑时喷出礼花
+                despawn_timed_effects,                // 清理播放完毕的扬尘和礼花
+            ),
+        );
+        // 窗口图标只在桌面平台设置，Web平台使用页面图标
+        app.add_systems(Startup, setup_window_icon)
+            .add_systems(Update, apply_window_icon);
+        // 帧率限制器在每帧末尾休眠，Web平台由浏览器控制帧率
+        app.add_systems(Last, limit_frame_rate);
+    }
+
+    // Web平台没有粒子效果插件，使用网格火花模拟蓄力粒子
+    #[cfg(target_arch = "wasm32")]
+    {
+        app.add_systems(Update, animate_charge_sparks); // 蓄力火花效果
+    }
+
+    // 仅在调试构建中添加跳到目标分数的调试命令，方便测试后期难度
+    #[cfg(debug_assertions)]
+    {
+        app.add_systems(
+            Update,
+            debug_jump_to_score
+                .before(generate_next_platform)
+                .run_if(in_state(GameState::Playing))
+                .run_if(in_state(PauseState::Running)),
+        );
     }
 
     // 启动游戏主循环，开始运行所有注册的系统
@@ -1,28 +1,528 @@
 // 导入Bevy游戏引擎的主要组件
 use bevy::prelude::*;
+// 导入系统参数派生宏，用于组合平台生成和难度相关资源
+use bevy::ecs::system::SystemParam;
+// 导入哈希表，用于缓存平台网格和材质
+use bevy::utils::HashMap;
 // 导入随机数生成库，用于随机生成平台属性
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+// 导入数学常量，用于计算正多边形顶点
+use std::f32::consts::TAU;
 
 // 导入玩家模块中的蓄力状态资源
-use crate::player::Accumulator;
+use crate::player::{
+    animation_delta, horizontal_direction, standing_y, Accumulator, FallState, JumpState,
+    MaxCharge, PhysicsTuning, Player,
+};
+// 导入分数资源，用于难度递增
+use crate::ui::Score;
+// 导入中心标记显示、下一个平台发光脉冲开关、色盲配色模式、平台材质风格和生成动画开关
+use crate::settings::{
+    ColorblindMode, PlatformSpawnAnimation, PlatformStyle, PulseNextPlatform, ShowTargetMarker,
+};
+// 导入逻辑操作输入，用于切换瞄准的平台
+use crate::input::{ActionInput, InputAction};
+// 导入录像记录和回放状态，用于记录和复现种子
+use crate::replay::{ReplayLog, ReplayPlayback};
 
 /// 标记组件：表示当前玩家站立的平台
 #[derive(Debug, Component)]
 pub struct CurrentPlatform;
 
 /// 标记组件：表示下一个需要跳跃的目标平台
+/// 
+/// 开启分岔路径时可能同时存在两个下一个平台，落在其中任意一个上都算成功
 #[derive(Debug, Component)]
 pub struct NextPlatform;
 
-/// 平台形状枚举，表示不同类型的平台
+/// 标记组件：玩家当前瞄准的下一个平台
+/// 
+/// 只有一个下一个平台时该平台总是被瞄准；分岔路径时玩家可以在候选平台之间切换，
+/// 跳跃方向、落点预览和指示箭头都以瞄准的平台为准
+#[derive(Debug, Component)]
+pub struct AimedPlatform;
+
+/// 游戏随机数生成器资源
+/// 
+/// 平台的间距、方向、形状和颜色都由它生成，相同种子会生成完全相同的平台序列
+#[derive(Debug, Resource)]
+pub struct GameRng(pub StdRng);
+
+impl Default for GameRng {
+    fn default() -> Self {
+        Self(StdRng::from_entropy())
+    }
+}
+
+/// 游戏种子配置资源
+/// 
+/// 为`Some`时使用指定种子生成平台，为`None`时每局使用随机种子
+#[derive(Debug, Default, Resource)]
+pub struct GameSeed(pub Option<u64>);
+
+/// 平台配色资源
+/// 
+/// 平台颜色从该调色板中随机选取，替换调色板即可切换配色主题；
+/// 调色板为空时使用固定饱和度和亮度、随机色相的颜色
+#[derive(Debug, Resource)]
+pub struct PlatformPalette(pub Vec<Color>);
+
+impl Default for PlatformPalette {
+    /// 默认配色，所有颜色都与粉色地面有明显区分
+    fn default() -> Self {
+        Self(vec![
+            Color::srgb(0.20, 0.60, 0.86), // 天蓝
+            Color::srgb(0.10, 0.74, 0.61), // 青绿
+            Color::srgb(0.95, 0.77, 0.06), // 金黄
+            Color::srgb(0.90, 0.49, 0.13), // 橙色
+            Color::srgb(0.56, 0.27, 0.68), // 紫色
+            Color::srgb(0.17, 0.24, 0.31), // 深蓝灰
+            Color::srgb(0.18, 0.80, 0.44), // 翠绿
+            Color::srgb(0.50, 0.55, 0.55), // 石板灰
+        ])
+    }
+}
+
+/// 色盲配色模式变化时更换平台调色板
+/// 
+/// 只影响之后生成的平台，已经生成的平台保持原来的颜色
+pub fn apply_colorblind_palette(
+    colorblind_mode: Res<ColorblindMode>,
+    mut palette: ResMut<PlatformPalette>,
+) {
+    if colorblind_mode.is_changed() {
+        *palette = colorblind_mode
+            .platform_palette()
+            .map_or_else(PlatformPalette::default, PlatformPalette);
+    }
+}
+
+/// 平台资源池
+/// 
+/// 每种平台形状共用一个网格，相同材质风格下相近颜色共用一个材质，避免每生成一个平台就新建网格和材质
+#[derive(Debug, Default, Resource)]
+pub struct PlatformAssetPool {
+    /// 按平台形状缓存的网格
+    meshes: HashMap<PlatformShape, Handle<Mesh>>,
+    /// 按材质风格和颜色分组缓存的材质，颜色键为量化后的sRGB分量
+    materials: HashMap<(PlatformStyle, [u8; 3]), Handle<StandardMaterial>>,
+    /// 双倍得分平台共用的金色材质
+    bonus_material: Option<Handle<StandardMaterial>>,
+}
+
+impl PlatformAssetPool {
+    /// 获取平台形状对应的网格，首次使用时创建
+    pub fn mesh(&mut self, shape: PlatformShape, meshes: &mut Assets<Mesh>) -> Handle<Mesh> {
+        self.meshes
+            .entry(shape)
+            .or_insert_with(|| meshes.add(shape.mesh()))
+            .clone()
+    }
+
+    /// 获取颜色和材质风格对应的材质，首次使用该分组时创建
+    /// 
+    /// 每个颜色分量量化为16级，分组内的颜色差异肉眼难以分辨；
+    /// 切换材质风格后新平台使用新风格的材质，已有平台保持原材质不变
+    pub fn material(
+        &mut self,
+        color: Color,
+        style: PlatformStyle,
+        materials: &mut Assets<StandardMaterial>,
+    ) -> Handle<StandardMaterial> {
+        let srgba = color.to_srgba();
+        let bucket = [srgba.red, srgba.green, srgba.blue]
+            .map(|channel| (channel.clamp(0.0, 1.0) * 15.0).round() as u8);
+        self.materials
+            .entry((style, bucket))
+            .or_insert_with(|| materials.add(style.material(color)))
+            .clone()
+    }
+
+    /// 获取双倍得分平台的金色材质，首次使用时创建
+    /// 
+    /// 带金属光泽和微弱自发光，与调色板中的普通金黄色平台区分开
+    pub fn bonus_material(&mut self, materials: &mut Assets<StandardMaterial>) -> Handle<StandardMaterial> {
+        self.bonus_material
+            .get_or_insert_with(|| {
+                materials.add(StandardMaterial {
+                    base_color: BONUS_PLATFORM_COLOR,
+                    emissive: LinearRgba::from(BONUS_PLATFORM_COLOR) * 0.15,
+                    metallic: 0.8,
+                    perceptual_roughness: 0.3,
+                    ..default()
+                })
+            })
+            .clone()
+    }
+}
+
+/// 双倍得分平台的颜色
+pub const BONUS_PLATFORM_COLOR: Color = Color::srgb(1.0, 0.78, 0.2);
+
+/// 双倍得分平台组件，落在该平台上时本次得分翻倍，平台成为当前平台后移除
+#[derive(Debug, Component)]
+pub struct BonusPlatform;
+
+/// 游戏模式资源
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Resource)]
+pub enum GameMode {
+    /// 无尽模式，每局使用新的随机种子
+    #[default]
+    Endless,
+    /// 每日挑战，种子由开始时的UTC日期决定，同一天的平台序列相同
+    Daily(UtcDate),
+    /// 限时挑战，每局使用新的随机种子，在限定时间内尽可能多得分
+    TimeAttack,
+    /// 分岔路径挑战，每局使用新的随机种子，达到分数门槛后有概率同时出现两个下一个平台
+    Forked,
+}
+
+/// UTC日期
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UtcDate {
+    pub year: i64,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl UtcDate {
+    /// 获取当前UTC日期
+    /// 
+    /// Web平台使用JS的`Date`获取当前时间
+    pub fn today() -> Self {
+        #[cfg(not(target_arch = "wasm32"))]
+        let millis = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0.0, |duration| duration.as_millis() as f64);
+        #[cfg(target_arch = "wasm32")]
+        let millis = js_sys::Date::now();
+
+        let days = (millis / 86_400_000.0).floor() as i64;
+        Self::from_days_since_epoch(days)
+    }
+
+    /// 将1970-01-01起的天数转换为公历日期
+    fn from_days_since_epoch(days: i64) -> Self {
+        // 以3月1日作为一年的开始，闰日落在年末，便于计算
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let day_of_era = z.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_from_march = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * month_from_march + 2) / 5 + 1) as u32;
+        let month = if month_from_march < 10 {
+            month_from_march + 3
+        } else {
+            month_from_march - 9
+        } as u32;
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+        Self { year, month, day }
+    }
+
+    /// 由日期生成的种子，形如20240131
+    pub fn seed(&self) -> u64 {
+        (self.year as u64) * 10_000 + (self.month as u64) * 100 + self.day as u64
+    }
+}
+
+impl std::fmt::Display for UtcDate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// 平台中心的基准高度，Y=0.5使平台顶面在Y=1.0
+pub const PLATFORM_BASE_Y: f32 = 0.5;
+
+/// 第一个平台配置资源
+/// 
+/// 教程或特殊模式可以固定第一个平台的形状和位置，玩家出生在该平台上；
+/// 默认随机形状、位于原点正上方的基准高度，与不配置时完全相同
+#[derive(Debug, Clone, Copy, Resource)]
+pub struct StartPlatformConfig {
+    /// 第一个平台的形状，`None`时随机选择
+    pub shape: Option<PlatformShape>,
+    /// 第一个平台的中心位置
+    pub pos: Vec3,
+}
+
+impl Default for StartPlatformConfig {
+    fn default() -> Self {
+        Self {
+            shape: None,
+            pos: Vec3::new(0.0, PLATFORM_BASE_Y, 0.0),
+        }
+    }
+}
+
+impl StartPlatformConfig {
+    /// 玩家在第一个平台上的初始位置，默认与`INITIAL_PLAYER_POS`相同
+    pub fn player_pos(&self) -> Vec3 {
+        self.pos.with_y(standing_y(self.pos.y, 1.0, 1.0))
+    }
+}
+
+/// 标记组件：表示玩家已经跳过的平台
 #[derive(Debug, Component)]
+pub struct PassedPlatform;
+
+/// 已跳过的平台与玩家的距离超过该值时被销毁（约3个平台间距）
+const PASSED_PLATFORM_DESPAWN_DISTANCE: f32 = 12.0;
+
+/// 平台生成序号组件，序号越小生成越早
+#[derive(Debug, Clone, Copy, Component)]
+pub struct SpawnIndex(pub u32);
+
+/// 平台生成计数资源，为每个新平台分配递增的生成序号
+#[derive(Debug, Default, Resource)]
+pub struct PlatformSpawnCounter(pub u32);
+
+impl PlatformSpawnCounter {
+    /// 分配下一个生成序号
+    pub fn next(&mut self) -> SpawnIndex {
+        let index = SpawnIndex(self.0);
+        self.0 = self.0.wrapping_add(1);
+        index
+    }
+}
+
+/// 同时存在的平台数量上限资源
+/// 
+/// 与按距离销毁互不影响，平台数量超过上限时最早生成的已跳过平台下沉消失，
+/// 当前平台和下一个平台永远不会被移除
+#[derive(Debug, Resource)]
+pub struct MaxPlatforms(pub usize);
+
+impl Default for MaxPlatforms {
+    fn default() -> Self {
+        Self(8)
+    }
+}
+
+/// 下沉消失动画组件，动画结束后销毁平台
+#[derive(Debug, Component)]
+pub struct SinkingPlatform {
+    pub elapsed: f32,           // 动画已进行的时长，秒
+    pub from_translation: Vec3, // 开始下沉时的位置
+    pub from_scale: Vec3,       // 开始下沉时的缩放
+}
+
+/// 下沉消失动画时长（秒）
+const PLATFORM_SINK_SECS: f32 = 0.4;
+/// 下沉消失动画结束时下沉的深度
+const PLATFORM_SINK_DEPTH: f32 = 1.0;
+
+/// 完美落地容差：落点与平台中心的水平距离小于该值时视为完美落地
+pub const PERFECT_LANDING_TOLERANCE: f32 = 0.2;
+
+/// 标记组件：表示下一个平台中心的完美落地标记，作为平台的子实体
+#[derive(Debug, Component)]
+pub struct TargetMarker;
+
+/// 平台形状枚举，表示不同类型的平台
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Component)]
 pub enum PlatformShape {
     /// 方形平台
     Box,
     /// 圆柱形平台
     Cylinder,
+    /// 三棱柱平台
+    TriangularPrism,
+    /// 六棱柱平台
+    Hexagon,
+}
+
+/// 难度配置资源，控制平台间距随分数增长
+#[derive(Debug, Resource)]
+pub struct Difficulty {
+    /// 初始最小间距
+    pub base_min: f32,
+    /// 初始最大间距
+    pub base_max: f32,
+    /// 每得1分间距增长量
+    pub growth_per_point: f32,
+    /// 间距上限
+    pub max_gap: f32,
+    /// 是否允许在全部四个水平方向生成平台，关闭时只在X轴正方向和Z轴负方向生成
+    pub four_directions: bool,
+    /// 开始出现移动平台的分数
+    pub moving_platform_score: u32,
+    /// 超过该分数后每得1分移动平台出现概率的增长量
+    pub moving_platform_chance_per_point: f32,
+    /// 移动平台出现概率上限
+    pub moving_platform_max_chance: f32,
+    /// 开始出现高低不同平台的分数
+    pub height_variation_score: u32,
+    /// 平台相对基准高度的最大偏移
+    pub max_height_offset: f32,
+    /// 开始出现缩小平台的分数
+    pub shrinking_platform_score: u32,
+    /// 达到分数门槛后生成缩小平台的概率
+    pub shrinking_platform_chance: f32,
+    /// 缩小平台每秒缩小的比例
+    pub shrinking_platform_rate: f32,
+    /// 生成双倍得分平台的概率
+    pub bonus_platform_chance: f32,
+    /// 是否开启分岔路径挑战，开启后有概率同时生成两个方向不同的下一个平台；
+    /// 每局开始时由`apply_forked_paths`根据游戏模式设置
+    pub forked_paths: bool,
+    /// 开始出现分岔路径的分数
+    pub forked_path_score: u32,
+    /// 达到分数门槛后生成分岔路径的概率
+    pub forked_path_chance: f32,
+}
+
+impl Default for Difficulty {
+    fn default() -> Self {
+        Self {
+            base_min: 2.5,
+            base_max: 4.0,
+            growth_per_point: 0.02,
+            max_gap: 4.5,
+            four_directions: true,
+            moving_platform_score: 10,
+            moving_platform_chance_per_point: 0.02,
+            moving_platform_max_chance: 0.4,
+            height_variation_score: 15,
+            max_height_offset: 0.5,
+            shrinking_platform_score: 20,
+            shrinking_platform_chance: 0.25,
+            shrinking_platform_rate: 0.2,
+            bonus_platform_chance: 0.08,
+            forked_paths: false,
+            forked_path_score: 5,
+            forked_path_chance: 0.3,
+        }
+    }
+}
+
+impl Difficulty {
+    /// 根据当前分数计算平台间距范围
+    /// 
+    /// 间距上限不会超过最大蓄力跳跃所能到达的距离
+    /// 
+    /// # 参数
+    /// - `score`: 当前分数
+    /// - `max_jump_distance`: 最大蓄力时的跳跃距离
+    pub fn gap_range(&self, score: u32, max_jump_distance: f32) -> std::ops::Range<f32> {
+        let growth = self.growth_per_point * score as f32;
+        let ceiling = self.max_gap.min(max_jump_distance);
+        let max = (self.base_max + growth).min(ceiling);
+        let min = (self.base_min + growth).min(max - 0.5);
+        min..max
+    }
+
+    /// 根据当前分数计算生成移动平台的概率，未达到分数门槛时为0
+    pub fn moving_platform_chance(&self, score: u32) -> f64 {
+        if score < self.moving_platform_score {
+            return 0.0;
+        }
+        let chance = self.moving_platform_chance_per_point
+            * (score - self.moving_platform_score + 1) as f32;
+        chance.min(self.moving_platform_max_chance) as f64
+    }
+
+    /// 根据当前分数计算生成分岔路径的概率，未开启分岔路径或未达到分数门槛时为0
+    pub fn forked_path_chance(&self, score: u32) -> f64 {
+        if !self.forked_paths || score < self.forked_path_score {
+            return 0.0;
+        }
+        self.forked_path_chance.clamp(0.0, 1.0) as f64
+    }
+
+    /// 根据当前分数计算生成缩小平台的概率，未达到分数门槛时为0
+    pub fn shrinking_platform_chance(&self, score: u32) -> f64 {
+        if score < self.shrinking_platform_score {
+            0.0
+        } else {
+            self.shrinking_platform_chance as f64
+        }
+    }
+
+    /// 根据当前分数计算平台高度的最大偏移，未达到分数门槛时为0，所有平台保持同一高度
+    pub fn height_offset(&self, score: u32) -> f32 {
+        if score < self.height_variation_score {
+            0.0
+        } else {
+            self.max_height_offset
+        }
+    }
+}
+
+/// 移动平台组件，平台沿指定轴来回往复移动
+#[derive(Debug, Component)]
+pub struct MovingPlatform {
+    /// 移动方向（单位向量）
+    pub axis: Vec3,
+    /// 偏离初始位置的最大距离
+    pub amplitude: f32,
+    /// 相位变化速度（弧度/秒）
+    pub speed: f32,
+    /// 当前相位
+    pub phase: f32,
+}
+
+impl MovingPlatform {
+    /// 计算经过指定时间后平台相对当前位置的偏移
+    /// 
+    /// # 参数
+    /// - `secs`: 经过的时间（秒）
+    pub fn offset_after(&self, secs: f32) -> Vec3 {
+        self.axis * self.amplitude * ((self.phase + self.speed * secs).sin() - self.phase.sin())
+    }
+}
+
+/// 缩小平台组件，玩家站在平台上时平台占地面积逐渐缩小
+#[derive(Debug, Component)]
+pub struct ShrinkingPlatform {
+    /// 每秒缩小的比例
+    pub rate: f32,
+}
+
+/// 缩小平台的最小水平缩放，缩小到该值时玩家摔落
+pub const MIN_PLATFORM_FOOTPRINT: f32 = 0.2;
+
+/// 平台生成动画组件，新平台从零缩放逐渐长到完整大小，动画结束后移除
+/// 
+/// 动画只影响外观：落地判定和落点预览通过`full_scale`始终按完整大小计算；
+/// 中心标记是平台的子实体，随平台一起长大；相机只跟随平台位置，不受缩放影响
+#[derive(Debug, Component)]
+pub struct SpawningPlatform {
+    pub timer: Timer,       // 生成动画计时器
+    pub target_scale: Vec3, // 动画结束时的完整缩放
+}
+
+impl SpawningPlatform {
+    /// 开始生成动画，只把平台缩放置零，保留平台原有的位置、旋转和基础缩放
+    fn start(mut entity: EntityWorldMut) {
+        let Some(mut transform) = entity.get_mut::<Transform>() else {
+            return;
+        };
+        let target_scale = transform.scale;
+        transform.scale = Vec3::ZERO;
+        entity.insert(SpawningPlatform {
+            timer: Timer::from_seconds(PLATFORM_SPAWN_SECS, TimerMode::Once),
+            target_scale,
+        });
+    }
+
+    /// 平台的完整缩放，生成动画中的平台返回动画结束时的缩放
+    pub fn full_scale(spawning: Option<&Self>, transform: &Transform) -> Vec3 {
+        spawning.map_or(transform.scale, |spawning| spawning.target_scale)
+    }
 }
 
+/// 平台生成动画时长（秒），短于最短跳跃动画时长，玩家落地前平台已经长到完整大小
+const PLATFORM_SPAWN_SECS: f32 = 0.25;
+
+/// 三棱柱平台底面外接圆半径
+const TRIANGULAR_PRISM_RADIUS: f32 = 1.0;
+/// 六棱柱平台底面外接圆半径
+const HEXAGON_RADIUS: f32 = 0.8;
+
 impl PlatformShape {
     /// 根据平台形状生成对应的网格模型
     pub fn mesh(&self) -> Mesh {
@@ -31,6 +531,10 @@ impl PlatformShape {
             Self::Box => Mesh::from(Cuboid::new(1.5, 1.0, 1.5)),
             // 生成一个半径0.75，高度1.0的圆柱体
             Self::Cylinder => Mesh::from(Cylinder::new(0.75, 1.0)),
+            // 生成底面为正三角形、高度1.0的三棱柱
+            Self::TriangularPrism => prism_mesh(TRIANGULAR_PRISM_RADIUS, 3),
+            // 生成底面为正六边形、高度1.0的六棱柱
+            Self::Hexagon => prism_mesh(HEXAGON_RADIUS, 6),
         }
     }
     
@@ -38,33 +542,43 @@ impl PlatformShape {
     /// 
     /// # 参数
     /// - `platform_pos`: 平台的位置坐标
+    /// - `footprint`: 平台水平方向的缩放，缩小平台会随时间变小
     /// - `landing_pos`: 玩家的落地点坐标
     /// 
     /// # 返回值
     /// 如果落地点在平台范围内返回true，否则返回false
-    pub fn is_landed_on_platform(&self, platform_pos: Vec3, landing_pos: Vec3) -> bool {
-        // 调试输出，实际游戏中可以移除
-        dbg!(platform_pos);
-        dbg!(landing_pos);
-        
+    pub fn is_landed_on_platform(&self, platform_pos: Vec3, footprint: f32, landing_pos: Vec3) -> bool {
+        // 换算到未缩放平台的坐标系中判断
+        let offset = (landing_pos - platform_pos).xz() / footprint;
         match self {
             // 对于方形平台，判断落地点是否在平台的X和Z轴范围内
-            Self::Box => {
-                (landing_pos.x - platform_pos.x).abs() < 1.5 / 2.0
-                    && (landing_pos.z - platform_pos.z).abs() < 1.5 / 2.0
-            }
-            // 对于圆柱形平台，使用简化的碰撞检测（方形边界）
-            Self::Cylinder => {
-                (landing_pos.x - platform_pos.x).abs() < 0.75
-                    && (landing_pos.z - platform_pos.z).abs() < 0.75
+            Self::Box => offset.x.abs() < 1.5 / 2.0 && offset.y.abs() < 1.5 / 2.0,
+            // 对于圆柱形平台，判断落地点到圆心的水平距离是否小于半径
+            Self::Cylinder => offset.length() < 0.75,
+            // 对于棱柱平台，判断落地点是否在底面多边形内
+            Self::TriangularPrism => {
+                is_in_polygon(offset, &polygon_vertices(TRIANGULAR_PRISM_RADIUS, 3))
             }
+            Self::Hexagon => is_in_polygon(offset, &polygon_vertices(HEXAGON_RADIUS, 6)),
         }
     }
     
+    /// 判断是否为完美落地
+    /// 
+    /// 落点与平台中心的水平距离小于完美落地容差即为完美落地，与平台形状无关
+    /// 
+    /// # 参数
+    /// - `platform_pos`: 平台的位置坐标
+    /// - `landing_pos`: 玩家的落地点坐标
+    pub fn is_perfect_landing(&self, platform_pos: Vec3, landing_pos: Vec3) -> bool {
+        (landing_pos - platform_pos).xz().length() < PERFECT_LANDING_TOLERANCE
+    }
+
     /// 判断玩家是否接触到平台（用于检测边缘碰撞）
     /// 
     /// # 参数
     /// - `platform_pos`: 平台的位置坐标
+    /// - `footprint`: 平台水平方向的缩放，缩小平台会随时间变小
     /// - `landing_pos`: 玩家的位置坐标
     /// - `player_radius`: 玩家的半径（用于碰撞检测）
     /// 
@@ -73,50 +587,207 @@ impl PlatformShape {
     pub fn is_touched_player(
         &self,
         platform_pos: Vec3,
+        footprint: f32,
         landing_pos: Vec3,
         player_radius: f32,
     ) -> bool {
+        // 换算到未缩放平台的坐标系中判断，玩家半径同比换算
+        let offset = (landing_pos - platform_pos).xz() / footprint;
+        let radius = player_radius / footprint;
         match self {
             // 方形平台的接触检测，包含玩家半径
-            Self::Box => {
-                (landing_pos.x - platform_pos.x).abs() < (1.5 / 2.0 + player_radius)
-                    && (landing_pos.z - platform_pos.z).abs() < (1.5 / 2.0 + player_radius)
-            }
-            // 圆柱形平台的接触检测，包含玩家半径
-            Self::Cylinder => {
-                (landing_pos.x - platform_pos.x).abs() < (0.75 + player_radius)
-                    && (landing_pos.z - platform_pos.z).abs() < (0.75 + player_radius)
+            Self::Box => offset.x.abs() < (1.5 / 2.0 + radius) && offset.y.abs() < (1.5 / 2.0 + radius),
+            // 圆柱形平台的接触检测，水平距离小于半径加玩家半径即为接触
+            Self::Cylinder => offset.length() < (0.75 + radius),
+            // 棱柱平台的接触检测，点在多边形内或到多边形边的距离小于玩家半径即为接触
+            Self::TriangularPrism => {
+                is_near_polygon(offset, &polygon_vertices(TRIANGULAR_PRISM_RADIUS, 3), radius)
             }
+            Self::Hexagon => is_near_polygon(offset, &polygon_vertices(HEXAGON_RADIUS, 6), radius),
         }
     }
 }
 
-/// 生成一个随机属性的平台
+/// 生成高度为1.0的正棱柱网格
+/// 
+/// 使用低分辨率圆柱体生成棱柱，并计算平面法线使侧面呈现棱角
+/// 
+/// # 参数
+/// - `radius`: 底面外接圆半径
+/// - `sides`: 底面边数
+fn prism_mesh(radius: f32, sides: u32) -> Mesh {
+    let mut mesh = Cylinder::new(radius, 1.0).mesh().resolution(sides).build();
+    mesh.duplicate_vertices();
+    mesh.compute_flat_normals();
+    mesh
+}
+
+/// 计算正多边形底面的顶点（XZ平面，相对平台中心）
+/// 
+/// 顶点顺序与圆柱体网格的生成顺序一致，第一个顶点位于X轴正方向
 /// 
 /// # 参数
-/// - `commands`: 命令实体，用于生成平台实体
-/// - `meshes`: 网格资源，用于创建平台模型
-/// - `materials`: 材质资源，用于创建平台材质
-/// - `pos`: 平台的位置坐标
-/// - `component`: 平台需要添加的组件（CurrentPlatform或NextPlatform）
-fn spawn_rand_platform<T: Component>(
-    commands: &mut Commands,
-    meshes: &mut ResMut<Assets<Mesh>>,
-    materials: &mut ResMut<Assets<StandardMaterial>>,
-    pos: Vec3,
-    component: T,
+/// - `radius`: 外接圆半径
+/// - `sides`: 边数
+fn polygon_vertices(radius: f32, sides: u32) -> Vec<Vec2> {
+    (0..sides)
+        .map(|i| {
+            let theta = i as f32 * TAU / sides as f32;
+            Vec2::new(radius * theta.cos(), radius * theta.sin())
+        })
+        .collect()
+}
+
+/// 判断点是否在凸多边形内
+/// 
+/// 点位于所有边的同一侧时即在多边形内
+fn is_in_polygon(point: Vec2, vertices: &[Vec2]) -> bool {
+    let crosses: Vec<f32> = vertices
+        .iter()
+        .zip(vertices.iter().cycle().skip(1))
+        .map(|(a, b)| (*b - *a).perp_dot(point - *a))
+        .collect();
+    crosses.iter().all(|cross| *cross > 0.0) || crosses.iter().all(|cross| *cross < 0.0)
+}
+
+/// 判断点是否在凸多边形内或与多边形边的距离小于指定半径
+fn is_near_polygon(point: Vec2, vertices: &[Vec2], radius: f32) -> bool {
+    is_in_polygon(point, vertices)
+        || vertices
+            .iter()
+            .zip(vertices.iter().cycle().skip(1))
+            .any(|(a, b)| distance_to_segment(point, *a, *b) < radius)
+}
+
+/// 计算点到线段的距离
+fn distance_to_segment(point: Vec2, a: Vec2, b: Vec2) -> f32 {
+    let ab = b - a;
+    let t = ((point - a).dot(ab) / ab.length_squared()).clamp(0.0, 1.0);
+    point.distance(a + ab * t)
+}
+
+/// 平台生成系统参数
+/// 
+/// 组合网格和材质资源、平台资源池、游戏随机数生成器、配色、材质风格、生成动画开关和生成序号，
+/// 第一个平台和之后的目标平台使用相同的方式生成
+#[derive(SystemParam)]
+pub struct PlatformSpawner<'w> {
+    meshes: ResMut<'w, Assets<Mesh>>,
+    materials: ResMut<'w, Assets<StandardMaterial>>,
+    pool: ResMut<'w, PlatformAssetPool>,
+    game_rng: ResMut<'w, GameRng>,
+    palette: Res<'w, PlatformPalette>,
+    style: Res<'w, PlatformStyle>,
+    spawn_animation: Res<'w, PlatformSpawnAnimation>,
+    spawn_counter: ResMut<'w, PlatformSpawnCounter>,
+}
+
+impl PlatformSpawner<'_> {
+    /// 游戏随机数生成器，平台的所有随机属性都从这里抽取
+    fn rng(&mut self) -> &mut StdRng {
+        &mut self.game_rng.0
+    }
+
+    /// 生成一个随机属性的平台
+    /// 
+    /// 指定形状时只随机选择颜色，生成的平台带有下一个生成序号
+    /// 
+    /// # 参数
+    /// - `commands`: 命令实体，用于生成平台实体
+    /// - `shape`: 平台形状，`None`时随机选择
+    /// - `pos`: 平台的位置坐标
+    /// - `component`: 平台需要添加的组件（CurrentPlatform或NextPlatform）
+    /// 
+    /// # 返回值
+    /// 生成的平台实体
+    fn spawn<T: Component>(
+        &mut self,
+        commands: &mut Commands,
+        shape: Option<PlatformShape>,
+        pos: Vec3,
+        component: T,
+    ) -> Entity {
+        // 未指定形状时随机生成平台形状
+        let platform_shape = shape.unwrap_or_else(|| rand_platform_shape(&mut self.game_rng.0));
+        let color = rand_platform_color(&mut self.game_rng.0, &self.palette);
+        
+        // 创建平台实体
+        commands.spawn((
+            Mesh3d(self.pool.mesh(platform_shape, &mut self.meshes)),  // 添加共用的网格组件
+            MeshMaterial3d(self.pool.material(color, *self.style, &mut self.materials)),  // 添加共用的材质组件
+            Transform::from_translation(pos),  // 设置位置
+            platform_shape,  // 添加形状组件
+            component,  // 添加平台类型组件
+            self.spawn_counter.next(),  // 添加生成序号
+        )).id()
+    }
+
+    /// 生成动画是否开启
+    fn animate_spawn(&self) -> bool {
+        self.spawn_animation.0
+    }
+
+    /// 获取双倍得分平台的金色材质
+    fn bonus_material(&mut self) -> Handle<StandardMaterial> {
+        self.pool.bonus_material(&mut self.materials)
+    }
+}
+
+/// 平台难度系统参数
+/// 
+/// 组合分数、难度配置、最大蓄力和物理参数，决定下一个平台的间距、高度和各类特殊平台的概率
+#[derive(SystemParam)]
+pub struct PlatformDifficulty<'w> {
+    score: Res<'w, Score>,
+    difficulty: Res<'w, Difficulty>,
+    max_charge: Res<'w, MaxCharge>,
+    tuning: Res<'w, PhysicsTuning>,
+}
+
+/// 初始化游戏随机数生成器
+/// 
+/// 每局开始时重新播种，并输出种子以便复现：回放录像时使用录像中的种子，每日挑战使用日期种子，
+/// 其他模式使用配置的种子或随机种子
+pub fn seed_game_rng(
+    mut game_rng: ResMut<GameRng>,
+    game_seed: Res<GameSeed>,
+    game_mode: Res<GameMode>,
+    replay_playback: Res<ReplayPlayback>,
+    mut replay_log: ResMut<ReplayLog>,
 ) {
-    // 随机生成平台形状
-    let platform_shape = rand_platform_shape();
-    
-    // 创建平台实体
-    commands.spawn((
-        Mesh3d(meshes.add(platform_shape.mesh())),  // 添加网格组件
-        MeshMaterial3d(materials.add(rand_platform_color())),  // 添加材质组件
-        Transform::from_translation(pos),  // 设置位置
-        platform_shape,  // 添加形状组件
-        component,  // 添加平台类型组件
-    ));
+    let seed = match (replay_playback.seed(), *game_mode) {
+        (Some(seed), _) => seed,
+        (None, GameMode::Daily(date)) => date.seed(),
+        (None, GameMode::Endless | GameMode::TimeAttack | GameMode::Forked) => {
+            game_seed.0.unwrap_or_else(rand::random)
+        }
+    };
+    info!("Game seed: {}", seed);
+    game_rng.0 = StdRng::seed_from_u64(seed);
+    // 记录到录像中，回放时使用相同种子生成完全相同的平台序列
+    replay_log.seed = seed;
+}
+
+/// 根据游戏模式开启或关闭分岔路径
+/// 
+/// 分岔路径挑战中开启，其他模式关闭；回放录像时使用录像记录的设置，保证平台序列与录制时相同
+pub fn apply_forked_paths(
+    game_mode: Res<GameMode>,
+    replay_playback: Res<ReplayPlayback>,
+    mut difficulty: ResMut<Difficulty>,
+    mut replay_log: ResMut<ReplayLog>,
+) {
+    let forked_paths = replay_playback
+        .forked_paths()
+        .unwrap_or(*game_mode == GameMode::Forked);
+    difficulty.forked_paths = forked_paths;
+    // 记录到录像中，回放时按相同设置生成平台
+    replay_log.forked_paths = forked_paths;
+}
+
+/// 关闭分岔路径，主菜单演示中始终只有一个下一个平台
+pub fn disable_forked_paths(mut difficulty: ResMut<Difficulty>) {
+    difficulty.forked_paths = false;
 }
 
 /// 设置游戏开始时的第一个平台
@@ -124,112 +795,714 @@ fn spawn_rand_platform<T: Component>(
 /// 在原点位置生成一个作为当前平台的实体
 pub fn setup_first_platform(
     mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut spawner: PlatformSpawner,
+    start_platform: Res<StartPlatformConfig>,
 ) {
-    spawn_rand_platform(
+    spawner.spawn(
         &mut commands,
-        &mut meshes,
-        &mut materials,
-        Vec3::new(0.0, 0.5, 0.0),  // 在(0, 0.5, 0)位置生成（Y=0.5使平台顶面在Y=1.0）
+        start_platform.shape,  // 未配置形状时随机选择
+        start_platform.pos,    // 默认在原点正上方的基准高度生成
         CurrentPlatform,
     );
 }
 
 /// 生成下一个目标平台
 /// 
-/// 当没有下一个平台时，在当前平台的X或Z方向随机生成一个新平台
+/// 当没有下一个平台时，在当前平台的四个水平方向中随机选择一个生成新平台，
+/// 并避开已经存在的平台；开启分岔路径时可能在另一个方向再生成一个候选平台
 pub fn generate_next_platform(
     mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
     q_current_platform: Query<&Transform, With<CurrentPlatform>>,
     q_next_platform: Query<Entity, With<NextPlatform>>,
+    q_platforms: Query<&Transform, (With<PlatformShape>, Without<CurrentPlatform>)>,
+    level: PlatformDifficulty,
+    mut spawner: PlatformSpawner,
 ) {
+    let PlatformDifficulty { score, difficulty, max_charge, tuning } = level;
     // 只有当没有下一个平台时才生成新的
     if q_next_platform.is_empty() {
-        let current_platform = &q_current_platform.single();
-        let mut rng = rand::thread_rng();
+        // 当前平台不存在时（例如清除平台后尚未生成第一个平台）跳过本帧
+        let Ok(current_platform) = q_current_platform.get_single() else {
+            return;
+        };
         
-        // 随机生成平台间的距离（2.5到4.0之间）
-        let rand_distance = rng.gen_range(2.5..4.0);
+        // 随机生成平台间的距离，范围随分数增大
+        // 间距不超过最大蓄力时的跳跃距离
+        let rand_distance = spawner.rng().gen_range(
+            difficulty.gap_range(score.0, tuning.max_jump_distance(&max_charge)),
+        );
         
-        // 50%概率在X轴方向，50%概率在Z轴方向生成新平台
-        let next_pos = if rng.gen_bool(0.5) {
-            Vec3::new(
-                current_platform.translation.x + rand_distance,  // X轴正方向
-                0.5,  // 保持相同高度
-                current_platform.translation.z,
-            )
+        // 达到分数门槛后平台高度在基准高度上下随机偏移，之前保持相同高度
+        let max_height_offset = difficulty.height_offset(score.0);
+        let next_y = if max_height_offset > 0.0 {
+            PLATFORM_BASE_Y + spawner.rng().gen_range(-max_height_offset..=max_height_offset)
         } else {
-            Vec3::new(
-                current_platform.translation.x,
-                0.5,
-                current_platform.translation.z - rand_distance,  // Z轴负方向
-            )
+            PLATFORM_BASE_Y
         };
 
-        // 生成新平台并标记为NextPlatform
-        spawn_rand_platform(
-            &mut commands,
-            &mut meshes,
-            &mut materials,
-            next_pos,
-            NextPlatform,
-        );
+        // 候选方向，开启四方向时额外包含X轴负方向和Z轴正方向
+        let directions: &[Vec3] = if difficulty.four_directions {
+            &[Vec3::X, Vec3::NEG_Z, Vec3::NEG_X, Vec3::Z]
+        } else {
+            &[Vec3::X, Vec3::NEG_Z]
+        };
+        // 排除会与已有平台重叠的方向（例如折返到刚跳过的平台）
+        let candidates: Vec<Vec3> = directions
+            .iter()
+            .map(|direction| {
+                Vec3::new(current_platform.translation.x, next_y, current_platform.translation.z)
+                    + *direction * rand_distance
+            })
+            .filter(|pos| {
+                q_platforms
+                    .iter()
+                    .all(|platform| (platform.translation - *pos).xz().length() > 2.0)
+            })
+            .collect();
+        // 随机选择一个候选位置，全部被占用时退回X轴正方向
+        let next_pos = if candidates.is_empty() {
+            current_platform.translation.with_y(next_y) + Vec3::X * rand_distance
+        } else {
+            candidates[spawner.rng().gen_range(0..candidates.len())]
+        };
+
+        // 开启分岔路径时按概率在另一个方向再生成一个候选平台，玩家可以任选其一；
+        // 未开启时不抽取随机数，保证相同种子的平台序列不变
+        let mut next_positions = vec![next_pos];
+        let fork_chance = difficulty.forked_path_chance(score.0);
+        if fork_chance > 0.0 && spawner.rng().gen_bool(fork_chance) {
+            let fork_candidates: Vec<Vec3> = candidates
+                .iter()
+                .copied()
+                .filter(|pos| (*pos - next_pos).xz().length() > 2.0)
+                .collect();
+            if !fork_candidates.is_empty() {
+                let fork_index = spawner.rng().gen_range(0..fork_candidates.len());
+                next_positions.push(fork_candidates[fork_index]);
+            }
+        }
+
+        for (index, next_pos) in next_positions.into_iter().enumerate() {
+            // 生成新平台并标记为NextPlatform
+            let next_platform = spawner.spawn(&mut commands, None, next_pos, NextPlatform);
+            // 开启生成动画时新平台从零缩放开始长大，不抽取随机数，不影响平台序列
+            if spawner.animate_spawn() {
+                commands.entity(next_platform).queue(SpawningPlatform::start);
+            }
+            // 默认瞄准第一个候选平台
+            if index == 0 {
+                commands.entity(next_platform).insert(AimedPlatform);
+            }
+
+            // 达到分数门槛后，按概率让新平台沿垂直于跳跃方向的轴来回移动
+            if spawner.rng().gen_bool(difficulty.moving_platform_chance(score.0)) {
+                let jump_direction = horizontal_direction(current_platform.translation, next_pos);
+                commands.entity(next_platform).insert(MovingPlatform {
+                    axis: jump_direction.cross(Vec3::Y),
+                    amplitude: 0.8,
+                    speed: spawner.rng().gen_range(1.0..2.0),
+                    phase: spawner.rng().gen_range(0.0..TAU),
+                });
+            }
+
+            // 达到分数门槛后，按概率让新平台在玩家站上去后逐渐缩小
+            if spawner.rng().gen_bool(difficulty.shrinking_platform_chance(score.0)) {
+                commands.entity(next_platform).insert(ShrinkingPlatform {
+                    rate: difficulty.shrinking_platform_rate,
+                });
+            }
+
+            // 按概率生成双倍得分平台，换用金色材质
+            if spawner.rng().gen_bool(difficulty.bonus_platform_chance.clamp(0.0, 1.0) as f64) {
+                commands.entity(next_platform).insert((
+                    BonusPlatform,
+                    MeshMaterial3d(spawner.bonus_material()),
+                ));
+            }
+        }
+    }
+}
+
+/// 切换瞄准的下一个平台
+/// 
+/// 分岔路径时按下切换目标键，按生成顺序在候选平台之间轮流切换瞄准目标；
+/// 蓄力、跳跃和摔落过程中不能切换，回放录像时由录像决定跳向哪个平台
+pub fn switch_aimed_platform(
+    mut commands: Commands,
+    input: ActionInput,
+    accumulator: Res<Accumulator>,
+    jump_state: Res<JumpState>,
+    fall_state: Res<FallState>,
+    playback: Res<ReplayPlayback>,
+    q_next_platforms: Query<(Entity, &SpawnIndex, Has<AimedPlatform>), With<NextPlatform>>,
+) {
+    if !input.just_pressed(InputAction::SwitchTarget)
+        || accumulator.0.is_some()
+        || !jump_state.is_settled()
+        || !fall_state.completed
+        || playback.is_active()
+    {
+        return;
+    }
+    let mut candidates: Vec<_> = q_next_platforms.iter().collect();
+    if candidates.len() < 2 {
+        return;
+    }
+    candidates.sort_by_key(|(_, index, _)| index.0);
+    let aimed = candidates.iter().position(|(_, _, aimed)| *aimed).unwrap_or(0);
+    let next = (aimed + 1) % candidates.len();
+    for (position, (entity, _, _)) in candidates.into_iter().enumerate() {
+        if position == next {
+            commands.entity(entity).insert(AimedPlatform);
+        } else {
+            commands.entity(entity).remove::<AimedPlatform>();
+        }
+    }
+}
+
+/// 玩家脚下的缩小平台查询，返回平台实体、变换和缩小参数
+type ShrinkingCurrentPlatformQuery<'w, 's> = Query<
+    'w,
+    's,
+    (Entity, &'static mut Transform, &'static ShrinkingPlatform),
+    (With<CurrentPlatform>, Without<Player>),
+>;
+
+/// 缩小平台动画系统
+/// 
+/// 玩家站在缩小平台上时平台水平方向逐渐缩小，跳跃和摔落过程中暂停；
+/// 缩小到最小值时玩家还没起跳则取消蓄力并摔落
+pub fn shrink_current_platform(
+    mut commands: Commands,
+    mut accumulator: ResMut<Accumulator>,
+    jump_state: Res<JumpState>,
+    mut fall_state: ResMut<FallState>,
+    time: Res<Time>,
+    q_player: Query<&Transform, With<Player>>,
+    mut q_current_platform: ShrinkingCurrentPlatformQuery,
+) {
+    if !jump_state.is_settled() || !fall_state.completed {
+        return;
+    }
+    let Ok((entity, mut platform, shrinking)) = q_current_platform.get_single_mut() else {
+        return;
+    };
+
+    let footprint =
+        (platform.scale.x - shrinking.rate * time.delta_secs()).max(MIN_PLATFORM_FOOTPRINT);
+    platform.scale.x = footprint;
+    platform.scale.z = footprint;
+
+    if footprint <= MIN_PLATFORM_FOOTPRINT {
+        info!("Shrinking platform vanished under the player");
+        // 平台保持最小尺寸不再缩小，重试时玩家仍可站在上面
+        commands.entity(entity).remove::<ShrinkingPlatform>();
+        // 取消蓄力，玩家从当前位置直接摔落
+        accumulator.0 = None;
+        if let Ok(player) = q_player.get_single() {
+            fall_state.animate_straight_fall(player.translation);
+        }
     }
 }
 
+/// 移动平台动画系统
+/// 
+/// 按相位更新移动平台的位置；玩家落在移动平台上后平台停止移动。
+/// 与跳跃动画使用同样截断后的帧时长，保证落点预测与平台实际移动一致
+pub fn move_platforms(
+    mut commands: Commands,
+    mut q_moving_platforms: Query<(Entity, &mut Transform, &mut MovingPlatform, Has<CurrentPlatform>)>,
+    jump_state: Res<JumpState>,
+    time: Res<Time>,
+) {
+    for (entity, mut transform, mut moving_platform, is_current) in &mut q_moving_platforms {
+        // 跳跃落地后当前平台停止移动，跳跃过程中继续移动以保持与落地预测一致
+        if is_current && jump_state.completed {
+            commands.entity(entity).remove::<MovingPlatform>();
+            continue;
+        }
+        let delta = animation_delta(&time);
+        let offset = moving_platform.offset_after(delta);
+        transform.translation += offset;
+        moving_platform.phase += moving_platform.speed * delta;
+    }
+}
+
+/// 平台生成动画系统
+/// 
+/// 新平台先快后慢地长到完整大小；平台在动画结束前成为当前平台时（例如连击加速后跳跃很快）
+/// 直接恢复完整大小，避免与平台蓄力效果争夺缩放
+pub fn animate_spawning_platforms(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut q_spawning: Query<(Entity, &mut Transform, &mut SpawningPlatform, Has<CurrentPlatform>)>,
+) {
+    for (entity, mut transform, mut spawning, is_current) in &mut q_spawning {
+        spawning.timer.tick(time.delta());
+        if spawning.timer.finished() || is_current {
+            transform.scale = spawning.target_scale;
+            commands.entity(entity).remove::<SpawningPlatform>();
+            continue;
+        }
+        let t = spawning.timer.fraction();
+        let eased = 1.0 - (1.0 - t).powi(2);
+        transform.scale = spawning.target_scale * eased.max(0.01);
+    }
+}
+
+/// 平台回弹动画组件，记录回弹开始后经过的时间和回弹开始时的Y轴缩放
+#[derive(Debug, Component)]
+pub struct PlatformRecovery {
+    pub elapsed: f32,
+    pub from_scale_y: f32,
+}
+
+/// 平台回弹动画时长（秒）
+const PLATFORM_RECOVERY_SECS: f32 = 0.3;
+
+impl PlatformRecovery {
+    /// 当前Y轴缩放，使用阻尼振荡让平台越过原始高度后再回落
+    fn scale_y(&self) -> f32 {
+        // 角频率使动画时长内约振荡1.5次，阻尼使振幅在结束时衰减到约3%
+        let angular_frequency = 3.0 * std::f32::consts::PI / PLATFORM_RECOVERY_SECS;
+        let damping = 12.0;
+        1.0 + (self.from_scale_y - 1.0)
+            * (-damping * self.elapsed).exp()
+            * (angular_frequency * self.elapsed).cos()
+    }
+}
+
+/// 平台压缩与回弹查询，返回平台实体、变换、是否为当前平台以及回弹动画状态
+type PlatformCompressionQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static mut Transform,
+        Has<CurrentPlatform>,
+        Option<&'static mut PlatformRecovery>,
+    ),
+    With<PlatformShape>,
+>;
+
 /// 平台蓄力动画效果
 /// 
-/// 当玩家蓄力时，当前平台会被压缩，模拟蓄力效果
+/// 当玩家蓄力时，当前平台会被压缩，模拟蓄力效果；蓄力结束后平台弹性回弹到原状
 pub fn animate_platform_accumulation(
+    mut commands: Commands,
     accumulator: Res<Accumulator>,  // 蓄力状态资源
-    mut q_current_platform: Query<&mut Transform, With<CurrentPlatform>>,  // 当前平台查询
+    max_charge: Res<MaxCharge>,  // 最大蓄力时长资源
+    mut q_platforms: PlatformCompressionQuery,  // 平台查询
     time: Res<Time>,  // 时间资源，用于帧间平滑过渡
 ) {
-    let mut current_platform = q_current_platform.single_mut();
-    
-    match accumulator.0 {
-        // 正在蓄力时，平台Y轴缩放逐渐减小（压缩效果）
-        Some(_) => {
-            current_platform.scale.y = 
-                (current_platform.scale.y - 0.15 * time.delta_secs()).max(0.6);  // 最小缩放到0.6
+    for (entity, mut platform, is_current, recovery) in &mut q_platforms {
+        match (is_current, accumulator.0, recovery) {
+            // 正在蓄力时，平台Y轴缩放逐渐减小（压缩效果）
+            (true, Some(_), recovery) => {
+                // 回弹过程中再次蓄力时，从当前缩放继续压缩
+                if recovery.is_some() {
+                    commands.entity(entity).remove::<PlatformRecovery>();
+                }
+                // 达到最大蓄力时长后保持当前压缩程度
+                if !accumulator.is_full(&max_charge) {
+                    platform.scale.y =
+                        (platform.scale.y - 0.15 * time.delta_secs()).max(0.6);  // 最小缩放到0.6
+                }
+            }
+            // 回弹中的平台（包括玩家已经跳离的平台）继续回弹动画
+            (_, _, Some(mut recovery)) => {
+                recovery.elapsed += time.delta_secs();
+                if recovery.elapsed >= PLATFORM_RECOVERY_SECS {
+                    platform.scale.y = 1.0; // 只恢复高度，缩小平台的水平缩放保持不变
+                    commands.entity(entity).remove::<PlatformRecovery>();
+                } else {
+                    platform.scale.y = recovery.scale_y();
+                }
+            }
+            // 蓄力结束时，被压缩的当前平台开始回弹
+            (true, None, None) if platform.scale.y != 1.0 => {
+                commands.entity(entity).insert(PlatformRecovery {
+                    elapsed: 0.0,
+                    from_scale_y: platform.scale.y,
+                });
+            }
+            _ => {}
         }
-        // 蓄力结束时，平台恢复原状
-        None => {
-            // TODO: 后续可以添加回弹效果，使平台恢复时更有弹性
-            current_platform.scale = Vec3::ONE;
+    }
+}
+
+/// 尚未开始下沉的已跳过平台查询，返回平台实体和位置
+type PassedPlatformQuery<'w, 's> = Query<
+    'w,
+    's,
+    (Entity, &'static Transform),
+    (With<PassedPlatform>, Without<SinkingPlatform>),
+>;
+
+/// 销毁远离玩家的已跳过平台
+/// 
+/// 避免长时间游戏时平台实体无限累积，当前平台和下一个平台不受影响
+pub fn despawn_passed_platforms(
+    mut commands: Commands,
+    q_player: Query<&Transform, With<Player>>,
+    q_passed_platforms: PassedPlatformQuery,
+) {
+    let Ok(player) = q_player.get_single() else {
+        return;
+    };
+    for (entity, platform) in &q_passed_platforms {
+        // 只比较水平距离
+        if (platform.translation - player.translation).xz().length()
+            > PASSED_PLATFORM_DESPAWN_DISTANCE
+        {
+            commands.entity(entity).despawn_recursive();
         }
     }
 }
 
+/// 尚未开始下沉的平台查询，返回平台实体、位置、生成序号以及是否已跳过
+type LivePlatformQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Transform,
+        Option<&'static SpawnIndex>,
+        Has<PassedPlatform>,
+    ),
+    (With<PlatformShape>, Without<SinkingPlatform>),
+>;
+
+/// 限制同时存在的平台数量
+/// 
+/// 平台数量超过上限时，按生成序号让最早生成的已跳过平台开始下沉消失；
+/// 只考虑已跳过的平台，当前平台和下一个平台即使超过上限也不会被移除
+pub fn enforce_max_platforms(
+    mut commands: Commands,
+    max_platforms: Res<MaxPlatforms>,
+    q_platforms: LivePlatformQuery,
+) {
+    let count = q_platforms.iter().count();
+    if count <= max_platforms.0 {
+        return;
+    }
+    let mut passed: Vec<_> = q_platforms
+        .iter()
+        .filter(|(_, _, _, passed)| *passed)
+        .map(|(entity, transform, index, _)| (entity, transform, index.map_or(0, |index| index.0)))
+        .collect();
+    passed.sort_by_key(|(_, _, index)| *index);
+    for (entity, transform, _) in passed.into_iter().take(count - max_platforms.0) {
+        commands.entity(entity).insert(SinkingPlatform {
+            elapsed: 0.0,
+            from_translation: transform.translation,
+            from_scale: transform.scale,
+        });
+    }
+}
+
+/// 下沉消失动画系统
+/// 
+/// 平台一边下沉一边缩小，动画结束后销毁；平台材质是共用的，因此用缩小代替淡出
+pub fn animate_sinking_platforms(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut q_sinking: Query<(Entity, &mut Transform, &mut SinkingPlatform)>,
+) {
+    for (entity, mut transform, mut sinking) in &mut q_sinking {
+        sinking.elapsed += time.delta_secs();
+        let t = (sinking.elapsed / PLATFORM_SINK_SECS).min(1.0);
+        if t >= 1.0 {
+            commands.entity(entity).despawn_recursive(); // 递归删除，同时清理中心标记
+            continue;
+        }
+        // 先慢后快地下沉和缩小
+        let eased = t * t;
+        transform.translation = sinking.from_translation - Vec3::Y * PLATFORM_SINK_DEPTH * eased;
+        transform.scale = sinking.from_scale * (1.0 - eased);
+    }
+}
+
 /// 清除所有平台实体
 /// 
 /// 用于状态切换时清理场景
 pub fn clear_platforms(mut commands: Commands, q_platforms: Query<Entity, With<PlatformShape>>) {
     for platform in &q_platforms {
-        commands.entity(platform).despawn();
+        commands.entity(platform).despawn_recursive(); // 递归删除，同时清理中心标记
+    }
+}
+
+/// 平台中心标记系统
+/// 
+/// 在下一个平台顶面中心显示完美落地区域标记，平台成为当前平台或关闭标记显示时移除
+/// 
+/// # 参数
+/// - `commands`: 命令系统，用于生成和销毁标记
+/// - `meshes`: 网格资源管理器
+/// - `materials`: 材质资源管理器
+/// - `marker_assets`: 所有标记共用的网格和材质，首次生成标记时创建
+/// - `show_target_marker`: 中心标记显示开关
+/// - `q_next_platform`: 下一个平台查询
+/// - `q_markers`: 中心标记查询
+pub fn update_target_markers(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut marker_assets: Local<Option<(Handle<Mesh>, Handle<StandardMaterial>)>>,
+    show_target_marker: Res<ShowTargetMarker>,
+    q_next_platform: Query<Entity, With<NextPlatform>>,
+    q_markers: Query<(Entity, &Parent), With<TargetMarker>>,
+) {
+    // 移除不属于下一个平台的标记，关闭显示时移除全部标记
+    for (marker, parent) in &q_markers {
+        if !show_target_marker.0 || !q_next_platform.contains(parent.get()) {
+            commands.entity(marker).despawn_recursive();
+        }
+    }
+    if !show_target_marker.0 {
+        return;
+    }
+
+    // 为还没有标记的下一个平台添加标记
+    for platform in &q_next_platform {
+        if q_markers.iter().any(|(_, parent)| parent.get() == platform) {
+            continue;
+        }
+        let (mesh, material) = marker_assets
+            .get_or_insert_with(|| {
+                (
+                    meshes.add(Cylinder::new(PERFECT_LANDING_TOLERANCE, 0.02)), // 扁平圆点，半径即完美落地容差
+                    materials.add(StandardMaterial {
+                        base_color: Color::srgba(1.0, 1.0, 1.0, 0.8), // 半透明白色，与各种平台颜色都有对比
+                        alpha_mode: AlphaMode::Blend,
+                        unlit: true,
+                        ..default()
+                    }),
+                )
+            })
+            .clone();
+        commands.entity(platform).with_child((
+            Mesh3d(mesh), // 所有标记共用的扁平圆点网格
+            MeshMaterial3d(material), // 所有标记共用的半透明材质
+            Transform::from_xyz(0.0, 0.51, 0.0), // 平台顶面中心上方一点，避免闪烁
+            TargetMarker,
+        ));
+    }
+}
+
+/// 下一个平台发光脉冲的周期（秒）
+const PLATFORM_PULSE_PERIOD_SECS: f32 = 1.2;
+/// 发光最强时自发光颜色相对平台颜色的比例，保持柔和
+const PLATFORM_PULSE_MAX_EMISSIVE: f32 = 0.35;
+
+/// 下一个平台发光脉冲组件
+/// 
+/// 平台材质由资源池共用，脉冲期间平台使用复制出的独立材质，避免同色的其他平台一起发光
+#[derive(Debug, Component)]
+pub struct PlatformPulse {
+    /// 平台原来的共用材质，停止脉冲时恢复
+    pub base_material: Handle<StandardMaterial>,
+    /// 脉冲已进行的时长（秒）
+    pub elapsed: f32,
+}
+
+/// 下一个平台脉冲查询，返回平台实体、材质和脉冲状态
+type PulsingNextPlatformQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static mut MeshMaterial3d<StandardMaterial>,
+        Option<&'static mut PlatformPulse>,
+    ),
+    With<NextPlatform>,
+>;
+
+/// 不再是下一个平台但仍在脉冲的平台查询，返回平台实体、材质和脉冲状态
+type StoppedPulseQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static mut MeshMaterial3d<StandardMaterial>,
+        &'static PlatformPulse,
+    ),
+    Without<NextPlatform>,
+>;
+
+/// 下一个平台发光脉冲系统
+/// 
+/// 开启脉冲时为下一个平台复制独立材质，并让其自发光强度随时间平滑起伏；
+/// 平台变为当前平台或关闭脉冲时恢复原来的共用材质，独立材质随句柄释放
+pub fn pulse_next_platform(
+    mut commands: Commands,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    pulse_setting: Res<PulseNextPlatform>,
+    time: Res<Time>,
+    mut q_next_platform: PulsingNextPlatformQuery,
+    mut q_stopped: StoppedPulseQuery,
+) {
+    // 不再是下一个平台的恢复共用材质
+    for (entity, mut material, pulse) in &mut q_stopped {
+        material.0 = pulse.base_material.clone();
+        commands.entity(entity).remove::<PlatformPulse>();
+    }
+
+    for (entity, mut material, pulse) in &mut q_next_platform {
+        match (pulse_setting.0, pulse) {
+            // 关闭脉冲时恢复共用材质
+            (false, Some(pulse)) => {
+                material.0 = pulse.base_material.clone();
+                commands.entity(entity).remove::<PlatformPulse>();
+            }
+            (false, None) => {}
+            // 刚成为下一个平台，复制独立材质后开始脉冲
+            (true, None) => {
+                let Some(base) = materials.get(&material.0).cloned() else {
+                    continue;
+                };
+                let base_material = std::mem::replace(&mut material.0, materials.add(base));
+                commands.entity(entity).insert(PlatformPulse {
+                    base_material,
+                    elapsed: 0.0,
+                });
+            }
+            // 自发光强度按余弦曲线在0和最大值之间起伏，叠加在共用材质原有的自发光上
+            (true, Some(mut pulse)) => {
+                pulse.elapsed += time.delta_secs();
+                let phase = pulse.elapsed / PLATFORM_PULSE_PERIOD_SECS * TAU;
+                let strength = PLATFORM_PULSE_MAX_EMISSIVE * (1.0 - phase.cos()) / 2.0;
+                let base_emissive = materials
+                    .get(&pulse.base_material)
+                    .map_or(LinearRgba::BLACK, |base| base.emissive);
+                if let Some(pulse_material) = materials.get_mut(&material.0) {
+                    pulse_material.emissive =
+                        base_emissive + pulse_material.base_color.to_linear() * strength;
+                }
+            }
+        }
     }
 }
 
 /// 随机生成平台颜色
 /// 
-/// 使用RGB随机值生成平台颜色
-fn rand_platform_color() -> Color {
-    let mut rng = rand::thread_rng();
-    Color::srgb(rng.gen(), rng.gen(), rng.gen())  // 随机生成RGB值
+/// 从调色板中随机选取颜色；调色板为空时使用固定饱和度和亮度的随机色相，
+/// 色相避开与地面接近的粉红色区间
+fn rand_platform_color(rng: &mut StdRng, palette: &PlatformPalette) -> Color {
+    if palette.0.is_empty() {
+        return Color::hsl(rng.gen_range(30.0..300.0), 0.6, 0.55);
+    }
+    palette.0[rng.gen_range(0..palette.0.len())]
 }
 
 /// 随机生成平台形状
 /// 
-/// 50%概率生成方形平台，50%概率生成圆柱形平台
-fn rand_platform_shape() -> PlatformShape {
-    let mut rng = rand::thread_rng();
-    let selection = rng.gen_range(0..2);
+/// 方形、圆柱形、三棱柱和六棱柱平台各占25%概率
+fn rand_platform_shape(rng: &mut StdRng) -> PlatformShape {
+    let selection = rng.gen_range(0..4);
     match selection {
         0 => PlatformShape::Box,
         1 => PlatformShape::Cylinder,
+        2 => PlatformShape::TriangularPrism,
+        3 => PlatformShape::Hexagon,
         _ => PlatformShape::Box,  // 默认情况，避免模式匹配不完整的警告
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 圆柱平台外接正方形角落内、圆外的点（距圆心约0.99，半径0.75）
+    const CORNER: Vec3 = Vec3::new(0.7, 0.0, 0.7);
+
+    #[test]
+    fn cylinder_rejects_landing_in_bounding_square_corner() {
+        let platform_pos = Vec3::new(2.0, 0.5, -3.0);
+        let landing_pos = platform_pos + CORNER;
+        // 点在外接正方形内，方形平台判定为落地
+        assert!(PlatformShape::Box.is_landed_on_platform(platform_pos, 1.0, landing_pos));
+        // 圆柱平台按半径判定，角落处不算落地
+        assert!(!PlatformShape::Cylinder.is_landed_on_platform(platform_pos, 1.0, landing_pos));
+    }
+
+    #[test]
+    fn cylinder_accepts_landing_just_inside_radius() {
+        let platform_pos = Vec3::new(2.0, 0.5, -3.0);
+        let landing_pos = platform_pos + Vec3::new(0.5, 0.0, 0.5); // 距圆心约0.71
+        assert!(PlatformShape::Cylinder.is_landed_on_platform(platform_pos, 1.0, landing_pos));
+    }
+
+    #[test]
+    fn cylinder_touch_uses_radius_plus_player_radius() {
+        let platform_pos = Vec3::ZERO;
+        let landing_pos = platform_pos + CORNER;
+        // 角落点距圆心约0.99，小玩家半径碰不到圆柱边缘
+        assert!(!PlatformShape::Cylinder.is_touched_player(platform_pos, 1.0, landing_pos, 0.1));
+        // 玩家半径足够大时接触到圆柱边缘
+        assert!(PlatformShape::Cylinder.is_touched_player(platform_pos, 1.0, landing_pos, 0.3));
+    }
+
+    #[test]
+    fn cylinder_corner_check_respects_footprint() {
+        // 缩小一半的圆柱平台，角落点按半径0.375判定
+        let landing_pos = CORNER * 0.5;
+        assert!(!PlatformShape::Cylinder.is_landed_on_platform(Vec3::ZERO, 0.5, landing_pos));
+        assert!(PlatformShape::Box.is_landed_on_platform(Vec3::ZERO, 0.5, landing_pos));
+    }
+
+    /// 正多边形第一条边（第0、1个顶点之间）中点沿外法线偏移`outward`后的落点
+    fn first_edge_point(radius: f32, sides: u32, outward: f32) -> Vec3 {
+        let vertices = polygon_vertices(radius, sides);
+        let midpoint = (vertices[0] + vertices[1]) / 2.0;
+        let point = midpoint + midpoint.normalize() * outward;
+        Vec3::new(point.x, 0.0, point.y)
+    }
+
+    #[test]
+    fn triangle_rejects_landing_past_slanted_edge() {
+        let platform_pos = Vec3::new(-1.0, 0.5, 2.0);
+        // 点在三角形外接矩形（X为-0.5到1.0，Z为-0.87到0.87）内，但在斜边外侧
+        let landing_pos = platform_pos + Vec3::new(0.6, 0.0, 0.6);
+        assert!(!PlatformShape::TriangularPrism.is_landed_on_platform(platform_pos, 1.0, landing_pos));
+    }
+
+    #[test]
+    fn triangle_accepts_landing_just_inside_apex() {
+        let platform_pos = Vec3::new(-1.0, 0.5, 2.0);
+        // 顶点位于(1.0, 0.0)，靠近顶点的内侧仍算落地
+        let landing_pos = platform_pos + Vec3::new(0.95, 0.0, 0.0);
+        assert!(PlatformShape::TriangularPrism.is_landed_on_platform(platform_pos, 1.0, landing_pos));
+    }
+
+    #[test]
+    fn hexagon_rejects_landing_in_bounding_box_corner() {
+        let platform_pos = Vec3::new(3.0, 0.5, 1.0);
+        // 点在六边形外接矩形（X为-0.8到0.8，Z为-0.69到0.69）的角落内，但在六边形外
+        let landing_pos = platform_pos + Vec3::new(0.75, 0.0, 0.65);
+        assert!(!PlatformShape::Hexagon.is_landed_on_platform(platform_pos, 1.0, landing_pos));
+        assert!(PlatformShape::Hexagon.is_landed_on_platform(platform_pos, 1.0, platform_pos));
+    }
+
+    #[test]
+    fn triangle_touch_uses_edge_plus_player_radius() {
+        let shape = PlatformShape::TriangularPrism;
+        let inside = first_edge_point(TRIANGULAR_PRISM_RADIUS, 3, 0.15);
+        let outside = first_edge_point(TRIANGULAR_PRISM_RADIUS, 3, 0.25);
+        // 斜边外0.15处没有落地，但在玩家半径0.2以内接触到平台
+        assert!(!shape.is_landed_on_platform(Vec3::ZERO, 1.0, inside));
+        assert!(shape.is_touched_player(Vec3::ZERO, 1.0, inside, 0.2));
+        // 超出玩家半径后不再接触
+        assert!(!shape.is_touched_player(Vec3::ZERO, 1.0, outside, 0.2));
+    }
+
+    #[test]
+    fn hexagon_touch_uses_edge_plus_player_radius() {
+        let shape = PlatformShape::Hexagon;
+        let inside = first_edge_point(HEXAGON_RADIUS, 6, 0.15);
+        let outside = first_edge_point(HEXAGON_RADIUS, 6, 0.25);
+        assert!(!shape.is_landed_on_platform(Vec3::ZERO, 1.0, inside));
+        assert!(shape.is_touched_player(Vec3::ZERO, 1.0, inside, 0.2));
+        assert!(!shape.is_touched_player(Vec3::ZERO, 1.0, outside, 0.2));
+    }
+}
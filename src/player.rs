@@ -1,24 +1,46 @@
-// 导入音频处理相关功能
-use bevy::audio::AudioSink;
 // 导入颜色调色板
 use bevy::color::palettes;
+// 导入系统参数派生宏，用于组合输入相关的系统参数
+use bevy::ecs::system::SystemParam;
 // 导入Bevy核心组件和功能
 use bevy::prelude::*;
+// 明确使用Bevy的透明模式，避免与粒子效果库的同名类型冲突
+use bevy::prelude::AlphaMode;
 // 导入时间戳功能，用于计算蓄力时长
 use bevy::utils::Instant;
 // 导入粒子效果库
 use bevy_hanabi::prelude::*;
 // 导入数学常量，用于旋转计算
 use std::f32::consts::{FRAC_PI_2, PI, TAU};
+// 导入时长类型，用于卡顿补偿
+use std::time::Duration;
 
+// 导入演示模块中的自动跳跃状态
+use crate::demo::DemoJump;
+// 导入输入模块中的逻辑操作输入
+use crate::input::{ActionInput, InputAction};
 // 导入平台相关组件
 use crate::platform::PlatformShape;
+// 导入录像模块中的录像记录和回放状态
+use crate::replay::{ReplayLog, ReplayPlayback};
+// 导入安全网等玩法设置
+use crate::settings::{
+    FaceJumpDirection, FizzleOnOvercharge, LandingTolerance, ParticleQuality, SafetyNet,
+    ScoreColorProgression, ShortTapBehavior,
+};
 // 导入UI和游戏状态相关组件
-use crate::ui::{GameSounds, GameState, ScoreUpEvent, ScoreUpQueue};
-// 导入平台标记组件和分数组件
-use crate::{
-    platform::{CurrentPlatform, NextPlatform},
-    ui::Score,
+use crate::ui::{
+    Combo, GameState, MilestoneEvent, PerfectFlash, PracticeMode, Score, ScoreKeeper,
+    StateTransition, Transition, TransitionPhase,
+};
+// 导入游戏事件，音效由音效系统根据事件统一播放
+use crate::audio::GameEvent;
+// 导入放宽完美判定的道具效果
+use crate::powerup::WidePerfectBoost;
+// 导入平台标记组件
+use crate::platform::{
+    AimedPlatform, BonusPlatform, CurrentPlatform, MovingPlatform, NextPlatform, PassedPlatform,
+    SpawnIndex, SpawningPlatform, StartPlatformConfig,
 };
 
 /// 玩家初始位置常量
@@ -28,6 +50,307 @@ pub const INITIAL_PLAYER_POS: Vec3 = Vec3::new(0.0, 1.5, 0.0);
 #[derive(Debug, Resource)]
 pub struct Accumulator(pub Option<Instant>);
 
+impl Accumulator {
+    /// 获取当前蓄力时长（秒），超过最大蓄力时长的部分会被截断
+    ///
+    /// # 参数
+    /// - `max_charge`: 最大蓄力时长资源
+    ///
+    /// # 返回值
+    /// 未在蓄力时返回0.0
+    pub fn charge_secs(&self, max_charge: &MaxCharge) -> f32 {
+        self.0
+            .as_ref()
+            .map_or(0.0, |start| start.elapsed().as_secs_f32().min(max_charge.0))
+    }
+
+    /// 是否已经达到最大蓄力时长
+    pub fn is_full(&self, max_charge: &MaxCharge) -> bool {
+        self.0.is_some() && self.charge_secs(max_charge) >= max_charge.0
+    }
+
+    /// 达到最大蓄力时长后是否又继续按住了超过`delay`秒
+    pub fn is_overcharged(&self, max_charge: &MaxCharge, delay: f32) -> bool {
+        self.0
+            .as_ref()
+            .is_some_and(|start| start.elapsed().as_secs_f32() >= max_charge.0 + delay)
+    }
+
+    /// 把蓄力开始时刻向后推移，卡顿期间不计入蓄力时长
+    ///
+    /// # 参数
+    /// - `stall`: 要跳过的卡顿时长
+    pub fn skip_stall(&mut self, stall: Duration) {
+        if let Some(start) = self.0.as_mut() {
+            *start += stall;
+        }
+    }
+}
+
+/// 单帧真实时长超过该值（秒）时视为卡顿
+///
+/// 远大于低帧率下的正常帧时长，帧率低或帧时长抖动时蓄力时长不受影响
+const CHARGE_STALL_THRESHOLD_SECS: f32 = 0.25;
+
+/// 单帧动画最多推进的时长（秒），卡顿后的长帧按该时长推进，避免玩家瞬移或跳跃瞬间完成
+///
+/// 与卡顿判定使用相同的阈值，远大于低帧率下的正常帧时长，帧率低时动画速度不变
+const MAX_ANIMATION_DELTA_SECS: f32 = CHARGE_STALL_THRESHOLD_SECS;
+
+/// 获取本帧动画推进的时长（秒），超过`MAX_ANIMATION_DELTA_SECS`的部分会被截断
+pub fn animation_delta(time: &Time) -> f32 {
+    time.delta_secs().min(MAX_ANIMATION_DELTA_SECS)
+}
+
+/// 卡顿补偿系统
+///
+/// 蓄力时长按真实时间计算，卡顿的长帧会让蓄力时长突然增加、跳得过远；
+/// 单帧真实时长超过`CHARGE_STALL_THRESHOLD_SECS`时，超出的部分不计入蓄力，卡顿结束后继续原来的蓄力。
+/// 需要在`player_jump`、演示和录像回放设置蓄力开始时刻之前运行
+pub fn compensate_charge_stall(real_time: Res<Time<Real>>, mut accumulator: ResMut<Accumulator>) {
+    let stall = real_time.delta_secs() - CHARGE_STALL_THRESHOLD_SECS;
+    if stall > 0.0 && accumulator.0.is_some() {
+        debug!("Frame stalled for {:.3}s, skip it in charge", stall);
+        accumulator.skip_stall(Duration::from_secs_f32(stall));
+    }
+}
+
+/// 最大蓄力时长资源（秒），防止长按导致跳跃距离和动画时长失控
+#[derive(Debug, Resource)]
+pub struct MaxCharge(pub f32);
+
+impl Default for MaxCharge {
+    fn default() -> Self {
+        Self(1.5)
+    }
+}
+
+/// 蓄力曲线，决定蓄力时长到跳跃距离的映射
+/// 
+/// 设蓄力进度 t = 蓄力时长 / 最大蓄力时长（0.0到1.0），曲线把 t 映射为距离比例 f(t)（0.0到1.0），
+/// 跳跃距离 = 每秒蓄力距离 × 最大蓄力时长 × f(t)。所有曲线都满足 f(1) = 1，满蓄力时跳跃距离相同；
+/// 线性曲线的跳跃距离即为 每秒蓄力距离 × 蓄力时长，与原有手感完全一致
+/// 
+/// 默认使用线性曲线，可在设置界面中切换为二次曲线或自定义曲线，选择随存档保存
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ChargeCurve {
+    /// f(t) = t
+    #[default]
+    Linear,
+    /// f(t) = t²，短按跳得更近，长按时距离快速增加
+    Quadratic,
+    /// f(t) = t^指数，指数大于1时缓入，小于1时缓出，指数不大于0时按线性处理
+    Custom(f32),
+}
+
+/// 可选的蓄力曲线，按设置界面中的切换顺序排列
+///
+/// 设置界面中的自定义曲线使用1.5次方，短按比线性曲线跳得近，但比二次曲线平缓
+const CHARGE_CURVES: [ChargeCurve; 3] =
+    [ChargeCurve::Linear, ChargeCurve::Quadratic, ChargeCurve::Custom(1.5)];
+
+impl ChargeCurve {
+    /// 根据序号获取蓄力曲线，序号超出范围时使用线性曲线
+    pub fn from_index(index: usize) -> Self {
+        CHARGE_CURVES.get(index).copied().unwrap_or_default()
+    }
+
+    /// 蓄力曲线的序号，用于写入存档
+    pub fn index(&self) -> usize {
+        CHARGE_CURVES
+            .iter()
+            .position(|curve| curve == self)
+            .unwrap_or_default()
+    }
+
+    /// 切换顺序中的下一个蓄力曲线
+    pub fn next(&self) -> Self {
+        Self::from_index((self.index() + 1) % CHARGE_CURVES.len())
+    }
+
+    /// 蓄力曲线名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Linear => "Linear",
+            Self::Quadratic => "Quadratic",
+            Self::Custom(_) => "Custom",
+        }
+    }
+
+    /// 曲线的指数，线性曲线为1，二次曲线为2
+    fn exponent(&self) -> f32 {
+        match *self {
+            ChargeCurve::Linear => 1.0,
+            ChargeCurve::Quadratic => 2.0,
+            ChargeCurve::Custom(exponent) if exponent > 0.0 => exponent,
+            ChargeCurve::Custom(_) => 1.0,
+        }
+    }
+
+    /// 根据蓄力进度计算距离比例 f(t)
+    pub fn apply(&self, progress: f32) -> f32 {
+        progress.clamp(0.0, 1.0).powf(self.exponent())
+    }
+
+    /// 根据距离比例反推蓄力进度 t = f⁻¹(比例)，用于根据目标距离计算蓄力时长
+    pub fn inverse(&self, ratio: f32) -> f32 {
+        ratio.clamp(0.0, 1.0).powf(self.exponent().recip())
+    }
+}
+
+/// 物理手感调节资源
+/// 
+/// 集中管理跳跃距离、跳跃动画和摔落速度等参数，默认值与原有手感一致
+#[derive(Debug, Resource)]
+pub struct PhysicsTuning {
+    /// 每秒蓄力对应的跳跃距离，非线性蓄力曲线下为满蓄力时的平均值
+    pub distance_per_charge: f32,
+    /// 蓄力曲线
+    pub charge_curve: ChargeCurve,
+    /// 每秒蓄力对应的跳跃动画时长
+    pub duration_per_charge: f32,
+    /// 跳跃动画最短时长（秒）
+    pub min_jump_duration: f32,
+    /// 跳跃过程中角色自身旋转的角度（弧度）
+    pub jump_spin_angle: f32,
+    /// 摔落时的下坠速度（单位/秒）
+    pub fall_speed: f32,
+    /// 倾斜摔落时的倾倒速度（弧度/秒）
+    pub tilt_speed: f32,
+    /// 开启过度蓄力取消时，满蓄力后继续按住多久（秒）取消蓄力
+    pub fizzle_delay: f32,
+    /// 最短蓄力时长（秒），低于该时长就松开时按快速点按设置取消或原地小跳
+    pub min_charge_secs: f32,
+}
+
+impl Default for PhysicsTuning {
+    fn default() -> Self {
+        Self {
+            distance_per_charge: 3.0,
+            charge_curve: ChargeCurve::Linear,
+            duration_per_charge: 0.5,
+            min_jump_duration: 0.5,
+            jump_spin_angle: TAU,
+            fall_speed: 0.7,
+            tilt_speed: FRAC_PI_2,
+            fizzle_delay: 1.0,
+            min_charge_secs: 0.1,
+        }
+    }
+}
+
+impl PhysicsTuning {
+    /// 根据蓄力时长计算跳跃动画时长
+    pub fn jump_duration(&self, charge_secs: f32) -> f32 {
+        (charge_secs * self.duration_per_charge).max(self.min_jump_duration)
+    }
+
+    /// 最大蓄力时的跳跃距离
+    pub fn max_jump_distance(&self, max_charge: &MaxCharge) -> f32 {
+        self.distance_per_charge * max_charge.0
+    }
+
+    /// 根据蓄力时长和蓄力曲线计算跳跃距离
+    /// 
+    /// 跳跃逻辑、落点预览和演示都通过此函数计算距离，保证三者一致
+    pub fn jump_distance(&self, charge_secs: f32, max_charge: &MaxCharge) -> f32 {
+        if max_charge.0 <= 0.0 {
+            return 0.0;
+        }
+        self.max_jump_distance(max_charge) * self.charge_curve.apply(charge_secs / max_charge.0)
+    }
+
+    /// 跳跃距离对应的蓄力时长，是`jump_distance`的反函数，超过最大跳跃距离时为最大蓄力时长
+    pub fn charge_for_distance(&self, distance: f32, max_charge: &MaxCharge) -> f32 {
+        let max_distance = self.max_jump_distance(max_charge);
+        if max_distance <= 0.0 {
+            return 0.0;
+        }
+        max_charge.0 * self.charge_curve.inverse(distance / max_distance)
+    }
+}
+
+/// 触摸蓄力资源，记录开始蓄力的手指ID
+///
+/// 多点触控时只响应第一根按下的手指，其他手指的按下和抬起都会被忽略
+#[derive(Debug, Default, Resource)]
+pub struct ChargeTouch(pub Option<u64>);
+
+/// 跳跃输入系统参数
+///
+/// 统一处理蓄力键（默认鼠标左键）和触摸屏输入，触摸按下开始蓄力、抬起触发跳跃，与按键逻辑完全一致；
+/// 主菜单演示通过`DemoJump`模拟松开，录像回放通过`ReplayPlayback`模拟按下和松开，
+/// 每次跳跃的蓄力时长记录到`ReplayLog`
+#[derive(SystemParam)]
+pub struct JumpInput<'w> {
+    actions: ActionInput<'w>,
+    touches: Res<'w, Touches>,
+    charge_touch: ResMut<'w, ChargeTouch>,
+    demo_jump: ResMut<'w, DemoJump>,
+    replay_playback: ResMut<'w, ReplayPlayback>,
+    replay_log: ResMut<'w, ReplayLog>,
+    time: Res<'w, Time>,
+}
+
+impl JumpInput<'_> {
+    /// 本帧是否按下了蓄力输入（蓄力键或第一根手指），回放时只响应录像
+    pub fn just_pressed(&self) -> bool {
+        if self.replay_playback.is_active() {
+            return self.replay_playback.press;
+        }
+        self.actions.just_pressed(InputAction::Charge)
+            || (self.charge_touch.0.is_none() && self.touches.any_just_pressed())
+    }
+
+    /// 本帧是否释放了蓄力输入（蓄力键、开始蓄力的那根手指、演示自动松开或录像回放松开）
+    pub fn just_released(&self) -> bool {
+        if self.replay_playback.is_active() {
+            return self.replay_playback.release;
+        }
+        self.demo_jump.release
+            || self.actions.just_released(InputAction::Charge)
+            || self.charge_touch.0.is_some_and(|id| {
+                self.touches.just_released(id) || self.touches.just_canceled(id)
+            })
+    }
+
+    /// 本帧的松开是否来自演示或录像回放的模拟输入，模拟输入不受快速点按设置影响
+    pub fn is_simulated(&self) -> bool {
+        self.replay_playback.is_active() || self.demo_jump.release
+    }
+
+    /// 记录一次跳跃的蓄力时长和瞄准的平台，用于保存和回放录像
+    pub fn record_jump(&mut self, charge_secs: f32, target: usize) {
+        self.replay_log.record(&self.time, charge_secs, target);
+    }
+
+    /// 回放时本次跳跃瞄准的候选平台序号，未在回放时返回`None`，由玩家瞄准的平台决定
+    pub fn replay_target(&self) -> Option<usize> {
+        self.replay_playback
+            .is_active()
+            .then_some(self.replay_playback.target)
+    }
+
+    /// 开始蓄力时记录负责蓄力的手指
+    pub fn begin_charge(&mut self) {
+        if self.charge_touch.0.is_none() {
+            self.charge_touch.0 = self.touches.iter_just_pressed().next().map(|touch| touch.id());
+        }
+    }
+
+    /// 负责蓄力的手指抬起后清除记录，避免阻塞后续触摸；演示和回放的模拟输入只生效一帧
+    pub fn clear_released_touch(&mut self) {
+        self.demo_jump.release = false;
+        self.replay_playback.press = false;
+        self.replay_playback.release = false;
+        if self.charge_touch.0.is_some_and(|id| {
+            self.touches.just_released(id) || self.touches.just_canceled(id)
+        }) {
+            self.charge_touch.0 = None;
+        }
+    }
+}
+
 /// 蓄力音效组件标记
 #[derive(Debug, Component)]
 pub struct AccumulationSound;
@@ -36,14 +359,99 @@ pub struct AccumulationSound;
 #[derive(Debug, Resource)]
 pub struct PrepareJumpTimer(pub Timer);
 
+/// 开局倒计时时长（秒），倒计时结束显示"Go!"
+pub const COUNTDOWN_SECS: f32 = 3.0;
+
+/// 开局倒计时计时器，总时长比倒计时多出0.5秒用于显示"Go!"
+#[derive(Debug, Resource)]
+pub struct CountdownTimer(pub Timer);
+
+impl Default for CountdownTimer {
+    fn default() -> Self {
+        Self(Timer::from_seconds(COUNTDOWN_SECS + 0.5, TimerMode::Once))
+    }
+}
+
+impl CountdownTimer {
+    /// 倒计时是否已经到达"Go!"
+    pub fn is_go(&self) -> bool {
+        self.0.elapsed_secs() >= COUNTDOWN_SECS
+    }
+}
+
+/// 开局入场动画时长（秒），第一个平台升起后玩家落到平台上
+pub const INTRO_SECS: f32 = 0.8;
+/// 入场动画中平台升起所占的比例，其余时间玩家下落
+const INTRO_RISE_FRACTION: f32 = 0.5;
+/// 第一个平台升起前低于最终位置的深度
+const INTRO_RISE_DEPTH: f32 = 1.5;
+/// 玩家开始下落时高于最终位置的高度
+const INTRO_DROP_HEIGHT: f32 = 4.0;
+
+/// 开局入场动画计时器，动画结束前不响应玩家输入
+#[derive(Debug, Resource)]
+pub struct IntroAnimation(pub Timer);
+
+impl Default for IntroAnimation {
+    fn default() -> Self {
+        Self(Timer::from_seconds(INTRO_SECS, TimerMode::Once))
+    }
+}
+
+/// 跳跃输入门控系统参数
+/// 
+/// 组合准备跳跃计时器、开局倒计时和入场动画，全部结束后才允许蓄力
+#[derive(SystemParam)]
+pub struct JumpGate<'w> {
+    prepare_jump_timer: Res<'w, PrepareJumpTimer>,
+    countdown_timer: Res<'w, CountdownTimer>,
+    intro_animation: Res<'w, IntroAnimation>,
+}
+
+impl JumpGate<'_> {
+    /// 是否允许玩家输入
+    pub fn is_open(&self) -> bool {
+        self.prepare_jump_timer.0.finished()
+            && self.countdown_timer.is_go()
+            && self.intro_animation.0.finished()
+    }
+}
+
+/// 跳跃动画系统参数
+///
+/// 组合跳跃和摔落状态、物理手感和动画时间，跳跃动画和摔落动画共用，避免动画系统的参数过多
+#[derive(SystemParam)]
+pub struct JumpAnimation<'w> {
+    pub jump_state: ResMut<'w, JumpState>,
+    pub fall_state: ResMut<'w, FallState>,
+    pub tuning: Res<'w, PhysicsTuning>,
+    pub time: Res<'w, Time>,
+}
+
+/// 跳跃规则系统参数
+/// 
+/// 组合蓄力上限、物理手感、落地容差和快速点按处理方式，跳跃逻辑和落点预览共用，保证预览与实际判定一致
+#[derive(SystemParam)]
+pub struct JumpRules<'w> {
+    pub max_charge: Res<'w, MaxCharge>,
+    pub tuning: Res<'w, PhysicsTuning>,
+    pub landing_tolerance: Res<'w, LandingTolerance>,
+    pub short_tap: Res<'w, ShortTapBehavior>,
+}
+
 /// 跳跃状态资源，管理跳跃动画和逻辑
 #[derive(Debug, Resource)]
 pub struct JumpState {
     pub start_pos: Vec3,       // 跳跃起始位置
     pub end_pos: Vec3,         // 跳跃目标位置
     pub animation_duration: f32, // 跳跃动画时长，秒
+    pub elapsed: f32,          // 跳跃动画已进行的时长，秒
     pub falled: bool,          // 是否摔落
+    pub perfect: bool,         // 是否完美落地
     pub completed: bool,       // 跳跃是否完成
+    pub wobble_axis: Option<Vec3>, // 险些踩空时落地后摇晃的旋转轴，摇晃结束后为None
+    pub wobble_elapsed: f32,   // 摇晃动画已进行的时长，秒
+    pub facing: Quat,          // 朝向落点的水平朝向（只绕Y轴旋转），与跳跃中的翻滚分开
 }
 /// JumpState的默认实现
 impl Default for JumpState {
@@ -52,8 +460,13 @@ impl Default for JumpState {
             start_pos: Vec3::ZERO,
             end_pos: Vec3::ZERO,
             animation_duration: 0.0,
+            elapsed: 0.0,
             falled: false,
+            perfect: false,
             completed: true, // 默认初始状态为已完成
+            wobble_axis: None,
+            wobble_elapsed: 0.0,
+            facing: Quat::IDENTITY,
         }
     }
 }
@@ -71,7 +484,26 @@ impl JumpState {
         self.start_pos = start_pos;
         self.end_pos = end_pos;
         self.animation_duration = animation_duration;
+        self.elapsed = 0.0;
+        self.perfect = false; // 由落地检测决定是否完美落地
         self.completed = false; // 标记为跳跃中
+        self.wobble_axis = None; // 由落地检测决定是否险些踩空
+        self.wobble_elapsed = 0.0;
+        self.facing = facing_rotation(horizontal_direction(start_pos, end_pos));
+    }
+
+    /// 落地后开始摇晃
+    /// 
+    /// # 参数
+    /// - `axis`: 摇晃的旋转轴，绕该轴正向旋转时玩家倒向平台边缘
+    pub fn start_wobble(&mut self, axis: Vec3) {
+        self.wobble_axis = Some(axis);
+        self.wobble_elapsed = 0.0;
+    }
+
+    /// 跳跃完成且落地后的摇晃也已结束，可以开始下一次蓄力
+    pub fn is_settled(&self) -> bool {
+        self.completed && self.wobble_axis.is_none()
     }
 }
 
@@ -83,6 +515,7 @@ pub struct FallState {
     pub tilt_completed: bool, // 是否完成倾斜动作
     pub completed: bool,      // 是否所有动作完成
     pub played_sound: bool,   // 是否已播放摔落音效
+    pub elapsed: f32,         // 摔落动画已进行的时长，秒
 }
 
 /// 摔落类型枚举
@@ -100,6 +533,7 @@ impl Default for FallState {
             tilt_completed: true,     // 默认已完成倾斜
             completed: true,          // 默认已完成
             played_sound: true,       // 默认已播放音效
+            elapsed: 0.0,
         }
     }
 }
@@ -116,6 +550,7 @@ impl FallState {
         self.fall_type = FallType::Straight;
         self.completed = false;
         self.played_sound = false;
+        self.elapsed = 0.0;
     }
     
     /// 初始化倾斜后下落动画
@@ -130,244 +565,744 @@ impl FallState {
         self.tilt_completed = false; // 标记倾斜未完成
         self.completed = false;
         self.played_sound = false;
+        self.elapsed = 0.0;
+    }
+}
+
+/// 玩家模型形状
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PlayerMesh {
+    Capsule, // 胶囊体
+    Sphere,  // 球体
+    Cube,    // 长方体
+}
+
+impl PlayerMesh {
+    /// 生成对应形状的网格
+    /// 
+    /// 所有形状的底部都与胶囊体底部对齐，保证站在平台上的高度一致
+    pub fn mesh(&self) -> Mesh {
+        match self {
+            PlayerMesh::Capsule => Capsule3d::new(0.2, 0.5).mesh().build(),
+            PlayerMesh::Sphere => Sphere::new(0.3)
+                .mesh()
+                .build()
+                .translated_by(Vec3::new(0.0, -0.15, 0.0)),
+            PlayerMesh::Cube => Cuboid::new(0.4, 0.9, 0.4).mesh().build(),
+        }
+    }
+}
+
+/// 玩家皮肤资源，决定玩家的模型形状和颜色
+#[derive(Debug, Clone, PartialEq, Resource)]
+pub struct PlayerSkin {
+    pub color: Color,     // 玩家颜色
+    pub mesh: PlayerMesh, // 玩家模型形状
+}
+
+/// 可选的玩家皮肤预设，包含名称和皮肤
+pub const PLAYER_SKINS: [(&str, PlayerSkin); 4] = [
+    ("Pink", PlayerSkin { color: Color::Srgba(palettes::css::PINK), mesh: PlayerMesh::Capsule }),
+    ("Sky", PlayerSkin { color: Color::srgb(0.45, 0.75, 1.0), mesh: PlayerMesh::Sphere }),
+    ("Orange", PlayerSkin { color: Color::srgb(1.0, 0.6, 0.2), mesh: PlayerMesh::Cube }),
+    ("Mint", PlayerSkin { color: Color::srgb(0.5, 0.9, 0.7), mesh: PlayerMesh::Capsule }),
+];
+
+impl Default for PlayerSkin {
+    fn default() -> Self {
+        Self::from_index(0)
+    }
+}
+
+impl PlayerSkin {
+    /// 根据预设序号获取皮肤，序号超出范围时循环取值
+    pub fn from_index(index: usize) -> Self {
+        PLAYER_SKINS[index % PLAYER_SKINS.len()].1.clone()
+    }
+
+    /// 当前皮肤在预设中的序号，不在预设中时返回0
+    pub fn index(&self) -> usize {
+        PLAYER_SKINS
+            .iter()
+            .position(|(_, skin)| skin == self)
+            .unwrap_or(0)
+    }
+
+    /// 当前皮肤的名称
+    pub fn name(&self) -> &'static str {
+        PLAYER_SKINS[self.index()].0
     }
 }
 
+/// 每局可用的重试次数（开启安全网时）
+pub const SAFETY_NET_RETRIES: u32 = 3;
+
+/// 剩余重试次数资源
+/// 
+/// 大于0时摔落不会结束游戏，而是消耗一次重试回到当前平台
+#[derive(Debug, Default, Resource)]
+pub struct Retries(pub u32);
+
 /// 玩家组件标记，用于查询玩家实体
 #[derive(Debug, Component)]
 pub struct Player;
 
+/// 输入被忽略时的摇头组件
+/// 
+/// 开局准备、倒计时或入场动画期间按下蓄力时，玩家左右摇头表示还不能跳跃
+#[derive(Debug, Component)]
+pub struct RejectedInputShake {
+    /// 摇头动画已进行的时长（秒）
+    pub elapsed: f32,
+    /// 开始摇头前的朝向，动画结束时恢复
+    pub base_rotation: Quat,
+}
+
+/// 落地冲击压扁组件，成功落地时玩家先压扁再迅速恢复原始缩放
+#[derive(Debug, Component)]
+pub struct LandingSquash {
+    /// 压扁恢复计时器，结束时缩放恢复为原始大小
+    pub timer: Timer,
+}
+
+/// 玩家影子标记组件，影子是跟随玩家的独立实体，不随玩家翻转
+#[derive(Debug, Component)]
+pub struct PlayerShadow;
+
+/// 玩家站在平台上时影子的半径
+const PLAYER_SHADOW_RADIUS: f32 = 0.35;
+/// 玩家站在平台上时影子的不透明度
+const PLAYER_SHADOW_ALPHA: f32 = 0.35;
+/// 玩家脚底离下方表面达到该高度时，影子缩小和变淡到最小
+const PLAYER_SHADOW_FADE_HEIGHT: f32 = 2.0;
+/// 影子缩小到最小时的缩放比例
+const PLAYER_SHADOW_MIN_SCALE: f32 = 0.4;
+
+/// 落点预览标记组件，蓄力时显示在预测落点的地面上
+#[derive(Debug, Component)]
+pub struct LandingMarker;
+
+/// 落点预览标记的正常颜色
+const LANDING_MARKER_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.5);
+
+/// 蓄力过度时落点预览标记和蓄力条的颜色
+pub const OVERSHOOT_COLOR: Color = Color::srgba(0.9, 0.1, 0.1, 0.8);
+
+/// 蓄力过度资源，现在松开是否会跳过下一个平台的远端边缘
+///
+/// 由落点预览系统每帧计算，蓄力条据此变红
+#[derive(Debug, Default, Resource)]
+pub struct ChargeOvershoot(pub bool);
+
 /// 蓄力粒子效果生成计时器
 #[derive(Debug, Resource)]
 pub struct GenerateAccumulationParticleEffectTimer(pub Timer);
 
+/// 一次性粒子效果组件（落地扬尘、里程碑礼花等），计时器结束时（粒子全部消失后）销毁实体
+#[derive(Debug, Component)]
+pub struct TimedEffect(pub Timer);
+
+/// 落地扬尘粒子的生命周期（秒）
+const LANDING_DUST_LIFETIME: f32 = 0.6;
+
+/// 跳跃轨迹颜色资源，不同皮肤可以设置不同的轨迹颜色
+#[derive(Debug, Resource)]
+pub struct JumpTrailColor(pub Color);
+
+impl Default for JumpTrailColor {
+    fn default() -> Self {
+        Self(Color::WHITE)
+    }
+}
+
+/// 跳跃轨迹标记组件，计时器记录剩余寿命，寿命结束后销毁
+#[derive(Debug, Component)]
+pub struct JumpTrail(pub Timer);
+
+/// 跳跃轨迹点的存在时长（秒）
+const JUMP_TRAIL_LIFETIME: f32 = 0.4;
+/// 跳跃轨迹点的初始透明度
+const JUMP_TRAIL_ALPHA: f32 = 0.6;
+/// 跳跃轨迹淡出的透明度级数，每级共用一个材质
+const JUMP_TRAIL_FADE_STEPS: usize = 6;
+
+/// 跳跃轨迹资源
+/// 
+/// 所有轨迹点共用一个小球网格，淡出时在按透明度分级的材质之间切换，避免跳跃时每帧新建网格和材质
+#[derive(Debug, Resource)]
+pub struct JumpTrailAssets {
+    /// 轨迹点小球网格
+    mesh: Handle<Mesh>,
+    /// 按透明度从低到高排列的轨迹材质
+    materials: Vec<Handle<StandardMaterial>>,
+}
+
+impl JumpTrailAssets {
+    /// 剩余寿命比例对应的材质，寿命越短越透明
+    fn material(&self, fraction_remaining: f32) -> &Handle<StandardMaterial> {
+        let step = (fraction_remaining * JUMP_TRAIL_FADE_STEPS as f32).ceil() as usize;
+        &self.materials[step.clamp(1, JUMP_TRAIL_FADE_STEPS) - 1]
+    }
+}
+
+/// 第`step`级（从1开始）轨迹材质的颜色
+fn jump_trail_step_color(color: Color, step: usize) -> Color {
+    color.with_alpha(JUMP_TRAIL_ALPHA * step as f32 / JUMP_TRAIL_FADE_STEPS as f32)
+}
+
 /// 设置玩家实体
 /// 
-/// 创建玩家角色模型并播放开始音效
+/// 根据当前皮肤创建玩家角色模型并播放开始音效
 /// 
 /// # 参数
 /// - `commands`: 命令系统，用于创建实体
 /// - `meshes`: 网格资源管理器
 /// - `materials`: 材质资源管理器
-/// - `game_sounds`: 游戏音效资源
+/// - `player_skin`: 玩家皮肤资源
+/// - `start_platform`: 第一个平台配置，玩家出生在该平台上
+/// - `game_events`: 游戏事件写入器，发送开局事件播放开始音效
 pub fn setup_player(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
-    game_sounds: Res<GameSounds>,
+    player_skin: Res<PlayerSkin>,
+    start_platform: Res<StartPlatformConfig>,
+    mut game_events: EventWriter<GameEvent>,
 ) {
-    // 创建玩家实体，使用胶囊体模型，粉色材质
+    let player_pos = start_platform.player_pos();
+    // 创建玩家实体，模型形状和颜色由皮肤决定
     commands.spawn((
-        Mesh3d(meshes.add(Capsule3d::new(0.2, 0.5).mesh())), // 添加胶囊体网格，半径0.2，高度0.5
-        MeshMaterial3d(materials.add(Color::Srgba(palettes::css::PINK))), // 添加粉色材质
-        Transform::from_translation(INITIAL_PLAYER_POS), // 设置初始位置
+        Mesh3d(meshes.add(player_skin.mesh.mesh())), // 添加皮肤对应的网格
+        MeshMaterial3d(materials.add(player_skin.color)), // 添加皮肤颜色材质
+        Transform::from_translation(player_pos), // 设置初始位置
         Player, // 添加玩家组件标记
     ));
-    // 播放游戏开始音效
+    // 创建玩家脚下的影子，位置和大小由update_player_shadow每帧更新
     commands.spawn((
-        AudioPlayer(game_sounds.start.clone()), // 开始音效
-        PlaybackSettings::DESPAWN, // 播放结束后自动销毁
+        Mesh3d(meshes.add(Cylinder::new(PLAYER_SHADOW_RADIUS, 0.01))), // 扁平圆盘
+        MeshMaterial3d(materials.add(StandardMaterial {
+            base_color: Color::BLACK.with_alpha(PLAYER_SHADOW_ALPHA), // 半透明黑色
+            alpha_mode: AlphaMode::Blend,
+            unlit: true,
+            ..default()
+        })),
+        Transform::from_translation(player_pos.with_y(0.0)),
+        PlayerShadow,
     ));
+    // 播放游戏开始音效
+    game_events.send(GameEvent::RunStart);
+}
+
+/// 根据蓄力时长计算跳跃落点位置
+/// 
+/// 跳跃逻辑和落点预览共用此函数，保证预览位置与实际落点一致
+/// 
+/// # 参数
+/// - `player_pos`: 玩家当前位置
+/// - `current_platform_pos`: 当前平台位置
+/// - `next_platform_pos`: 下一个平台位置
+/// - `charge_secs`: 蓄力时长（秒）
+/// - `max_charge`: 最大蓄力时长
+/// - `tuning`: 物理手感调节资源
+/// 
+/// # 返回值
+/// 跳跃后的落点位置，高度为站在下一个平台顶面上的高度
+pub fn landing_position(
+    player_pos: Vec3,
+    current_platform_pos: Vec3,
+    next_platform_pos: Vec3,
+    charge_secs: f32,
+    max_charge: &MaxCharge,
+    tuning: &PhysicsTuning,
+) -> Vec3 {
+    // 跳跃方向由当前平台指向下一个平台的水平向量决定
+    let direction = horizontal_direction(current_platform_pos, next_platform_pos);
+    // 水平位移由蓄力曲线决定（线性曲线下与蓄力时间成正比），Y轴高度为下一个平台顶面上的站立高度
+    (player_pos + direction * tuning.jump_distance(charge_secs, max_charge))
+        .with_y(standing_y(next_platform_pos.y, 1.0, 1.0))
+}
+
+/// 跳跃落地结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LandingOutcome {
+    /// 落在当前平台上，不得分
+    CurrentPlatform,
+    /// 落在下一个平台上，`perfect`表示是否完美落地，`near_miss`表示是否险些踩空
+    NextPlatform { perfect: bool, near_miss: bool },
+    /// 碰到当前平台边缘摔落
+    TouchedCurrentPlatform,
+    /// 碰到下一个平台边缘摔落，`overshoot`表示是否跳过头
+    TouchedNextPlatform { overshoot: bool },
+    /// 没有碰到任何平台，直接下落
+    Missed,
+}
+
+impl LandingOutcome {
+    /// 分岔路径时选择落地结果的优先级，数值越小越优先
+    /// 
+    /// 落在任意候选平台上都算成功；都没落上时，按当前平台、平台边缘、落空的顺序判定
+    fn priority(&self) -> u8 {
+        match self {
+            Self::NextPlatform { .. } => 0,
+            Self::CurrentPlatform => 1,
+            Self::TouchedCurrentPlatform => 2,
+            Self::TouchedNextPlatform { .. } => 3,
+            Self::Missed => 4,
+        }
+    }
+}
+
+/// 玩家接触平台边缘的检测半径
+const PLAYER_TOUCH_RADIUS: f32 = 0.2;
+
+/// 根据落点判断跳跃结果
+/// 
+/// 只依赖平台形状和变换，不访问ECS，便于单独验证落地判定；
+/// 平台按当前的水平缩放判断（缩小平台会随时间变小），再乘以落地容差倍率，
+/// 落地、险些踩空和边缘接触都按放大后的占地面积判断，保持一致
+/// 
+/// # 参数
+/// - `current_shape`: 当前平台形状
+/// - `current_platform`: 当前平台变换
+/// - `next_shape`: 下一个平台形状
+/// - `next_platform`: 下一个平台在落地时刻的变换
+/// - `landing_pos`: 落点位置
+/// - `tolerance`: 落地容差倍率，1.0为平台实际大小
+pub fn landing_outcome(
+    current_shape: &PlatformShape,
+    current_platform: &Transform,
+    next_shape: &PlatformShape,
+    next_platform: &Transform,
+    landing_pos: Vec3,
+    tolerance: f32,
+) -> LandingOutcome {
+    let current_pos = current_platform.translation;
+    let next_pos = next_platform.translation;
+    let current_footprint = current_platform.scale.x * tolerance;
+    let next_footprint = next_platform.scale.x * tolerance;
+    if next_shape.is_landed_on_platform(next_pos, next_footprint, landing_pos) {
+        // 落点再向外偏移一个接触半径就会落空时，说明刚好踩在平台边缘，视为险些踩空
+        let outward = horizontal_direction(next_pos, landing_pos) * PLAYER_TOUCH_RADIUS;
+        LandingOutcome::NextPlatform {
+            perfect: next_shape.is_perfect_landing(next_pos, landing_pos),
+            near_miss: !next_shape.is_landed_on_platform(
+                next_pos,
+                next_footprint,
+                landing_pos + outward,
+            ),
+        }
+    } else if current_shape.is_landed_on_platform(current_pos, current_footprint, landing_pos) {
+        LandingOutcome::CurrentPlatform
+    } else if current_shape.is_touched_player(
+        current_pos,
+        current_footprint,
+        landing_pos,
+        PLAYER_TOUCH_RADIUS,
+    ) {
+        LandingOutcome::TouchedCurrentPlatform
+    } else if next_shape.is_touched_player(
+        next_pos,
+        next_footprint,
+        landing_pos,
+        PLAYER_TOUCH_RADIUS,
+    ) {
+        // 落点在下一个平台中心之后即为跳过头
+        let jump_direction = horizontal_direction(current_pos, next_pos);
+        LandingOutcome::TouchedNextPlatform {
+            overshoot: (landing_pos - next_pos).dot(jump_direction) > 0.0,
+        }
+    } else {
+        LandingOutcome::Missed
+    }
+}
+
+/// 判断落点是否越过了下一个平台的远端边缘
+/// 
+/// # 参数
+/// - `outcome`: 落地结果
+/// - `current_platform_pos`: 当前平台位置
+/// - `next_platform_pos`: 下一个平台位置
+/// - `landing_pos`: 落点位置
+pub fn is_overshoot(
+    outcome: LandingOutcome,
+    current_platform_pos: Vec3,
+    next_platform_pos: Vec3,
+    landing_pos: Vec3,
+) -> bool {
+    match outcome {
+        LandingOutcome::TouchedNextPlatform { overshoot } => overshoot,
+        // 没碰到任何平台时，落点在下一个平台中心之后说明已越过远端边缘
+        LandingOutcome::Missed => {
+            let jump_direction = horizontal_direction(current_platform_pos, next_platform_pos);
+            (landing_pos - next_platform_pos).dot(jump_direction) > 0.0
+        }
+        _ => false,
+    }
+}
+
+/// 平台高度的一半，平台网格以中心为原点
+const PLATFORM_HALF_HEIGHT: f32 = 0.5;
+/// 未压缩时玩家中心到平台顶面的距离
+const PLAYER_STAND_OFFSET: f32 = 0.5;
+
+/// 计算站在平台上时玩家中心的高度
+/// 
+/// 平台和玩家都以中心为原点缩放，压缩后需要同时考虑两者的Y轴缩放才能保持接触
+/// 
+/// # 参数
+/// - `platform_y`: 平台中心高度
+/// - `platform_scale_y`: 平台Y轴缩放
+/// - `player_scale_y`: 玩家Y轴缩放
+pub fn standing_y(platform_y: f32, platform_scale_y: f32, player_scale_y: f32) -> f32 {
+    platform_y + PLATFORM_HALF_HEIGHT * platform_scale_y + PLAYER_STAND_OFFSET * player_scale_y
+}
+
+/// 计算从`from`指向`to`的水平单位方向向量
+/// 
+/// 两点水平重合时返回X轴正方向
+pub fn horizontal_direction(from: Vec3, to: Vec3) -> Vec3 {
+    (to - from).with_y(0.0).try_normalize().unwrap_or(Vec3::X)
+}
+
+/// 计算让玩家面朝`direction`方向的水平朝向
+/// 
+/// 玩家默认面朝Z轴负方向，只绕Y轴旋转，四个跳跃方向都不会上下颠倒
+/// 
+/// # 参数
+/// - `direction`: 水平单位方向向量
+pub fn facing_rotation(direction: Vec3) -> Quat {
+    Quat::from_rotation_y((-direction.x).atan2(-direction.z))
+}
+
+/// 计算让玩家朝`toward`方向倾倒的旋转轴
+/// 
+/// 绕该轴正向旋转时，位于旋转中心正上方的玩家会倒向`toward`方向
+pub fn tilt_axis(toward: Vec3) -> Vec3 {
+    Vec3::Y.cross(toward)
+}
+
+/// 计算碰到平台边缘摔落时的倾倒旋转轴
+/// 
+/// 倾倒方向为平台中心指向落点的水平方向，旋转轴与该方向垂直，
+/// 无论平台形状和跳跃方向如何，玩家总是倒向远离所碰平台的一侧
+/// 
+/// # 参数
+/// - `platform_pos`: 玩家碰到的平台中心位置
+/// - `landing_pos`: 落点位置
+pub fn edge_tilt_axis(platform_pos: Vec3, landing_pos: Vec3) -> Vec3 {
+    tilt_axis(horizontal_direction(platform_pos, landing_pos))
+}
+
+/// 起跳的当前平台查询，返回平台实体、位置和形状
+type JumpCurrentPlatformQuery<'w, 's> = Query<
+    'w,
+    's,
+    (Entity, &'static Transform, &'static PlatformShape),
+    (With<CurrentPlatform>, Without<Player>),
+>;
+
+/// 下一个平台候选查询，返回平台实体、位置、形状、移动和生成动画组件、是否双倍得分、是否被瞄准以及生成序号
+type JumpCandidatesQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Transform,
+        &'static PlatformShape,
+        Option<&'static MovingPlatform>,
+        Option<&'static SpawningPlatform>,
+        Has<BonusPlatform>,
+        Has<AimedPlatform>,
+        &'static SpawnIndex,
+    ),
+    (With<NextPlatform>, Without<Player>),
+>;
+
+/// 跳跃目标系统参数
+///
+/// 组合玩家、当前平台和下一个平台候选，分岔路径时候选平台不止一个
+#[derive(SystemParam)]
+pub struct JumpTargets<'w, 's> {
+    q_player: Query<'w, 's, &'static mut Transform, With<Player>>,
+    q_current_platform: JumpCurrentPlatformQuery<'w, 's>,
+    q_next_platforms: JumpCandidatesQuery<'w, 's>,
+}
+
+/// 跳跃进程系统参数
+///
+/// 组合跳跃输入门控、蓄力状态、跳跃和摔落状态以及游戏事件，覆盖从蓄力、起跳到落地或摔落的一次完整跳跃；
+/// 蓄力开始时刻使用真实时间记录
+#[derive(SystemParam)]
+pub struct JumpProgress<'w> {
+    jump_gate: JumpGate<'w>,
+    accumulator: ResMut<'w, Accumulator>,
+    jump_state: ResMut<'w, JumpState>,
+    fall_state: ResMut<'w, FallState>,
+    time: Res<'w, Time<Real>>,
+    game_events: EventWriter<'w, GameEvent>,
 }
 
 /// 玩家跳跃逻辑系统
 /// 
-/// 处理鼠标输入、蓄力计算、跳跃轨迹计算和平台检测
+/// 处理鼠标与触摸输入、蓄力计算、跳跃轨迹计算和平台检测；
+/// 分岔路径时朝瞄准的平台跳跃，落地判定检查所有候选平台，落在哪个上就以哪个为当前平台，其余候选被移除
 pub fn player_jump(
     mut commands: Commands,
-    buttons: Res<ButtonInput<MouseButton>>,
-    mut score: ResMut<Score>,
-    mut accumulator: ResMut<Accumulator>,
-    mut jump_state: ResMut<JumpState>,
-    mut fall_state: ResMut<FallState>,
-    mut score_up_queue: ResMut<ScoreUpQueue>,
-    prepare_jump_timer: Res<PrepareJumpTimer>,
-    time: Res<Time<Real>>,
-    game_sounds: Res<GameSounds>,
-    q_accumulation_sound: Query<&AudioSink, With<AccumulationSound>>,
-    q_player: Query<&Transform, With<Player>>,
-    q_current_platform: Query<(Entity, &Transform, &PlatformShape), With<CurrentPlatform>>,
-    q_next_platform: Query<(Entity, &Transform, &PlatformShape), With<NextPlatform>>,
+    mut jump_input: JumpInput,
+    mut score_keeper: ScoreKeeper,
+    rules: JumpRules,
+    progress: JumpProgress,
+    mut wide_perfect: ResMut<WidePerfectBoost>,
+    targets: JumpTargets,
 ) {
-    // 检查准备跳跃计时器是否完成
-    // 如果未完成，说明刚从主菜单进入游戏，忽略输入
-    if !prepare_jump_timer.0.finished() {
+    let JumpProgress {
+        jump_gate,
+        mut accumulator,
+        mut jump_state,
+        mut fall_state,
+        time,
+        mut game_events,
+    } = progress;
+    let JumpTargets { mut q_player, q_current_platform, q_next_platforms } = targets;
+    // 检查准备跳跃计时器和开局倒计时是否完成
+    // 如果未完成，说明刚进入游戏，忽略包括蓄力在内的所有输入
+    if !jump_gate.is_open() {
         return;
     }
     
-    // 鼠标左键按下，开始蓄力
-    // 只有当前跳跃和摔落都已完成时才响应
-    if buttons.just_pressed(MouseButton::Left) && jump_state.completed && fall_state.completed {
+    // 鼠标左键或手指按下，开始蓄力
+    // 只有当前跳跃、落地摇晃和摔落都已完成时才响应；已在蓄力时忽略其他输入的按下，
+    // 避免蓄力中另一种输入按下重置蓄力时间并重复播放蓄力音效
+    if jump_input.just_pressed()
+        && accumulator.0.is_none()
+        && jump_state.is_settled()
+        && fall_state.completed
+    {
         // 记录蓄力开始时间
         accumulator.0 = time.last_update();
+        // 记录负责蓄力的手指（鼠标输入时不记录）
+        jump_input.begin_charge();
         // 播放蓄力音效（循环播放）
-        commands.spawn((
-            AccumulationSound, // 标记为蓄力音效
-            AudioPlayer(game_sounds.accumulation.clone()), // 蓄力音效资源
-            PlaybackSettings::LOOP, // 循环播放设置
-        ));
+        game_events.send(GameEvent::ChargeStart);
     }
     
-    // 鼠标左键释放，结束蓄力并执行跳跃
+    // 鼠标左键或手指释放，结束蓄力并执行跳跃
     // 检查条件：跳跃完成、摔落完成、正在蓄力中
-    if buttons.just_released(MouseButton::Left)
-        && jump_state.completed
+    let released = jump_input.just_released();
+    let simulated = jump_input.is_simulated();
+    jump_input.clear_released_touch();
+    if released
+        && jump_state.is_settled()
         && fall_state.completed
         && accumulator.0.is_some()
     {
         // 检查是否存在下一个平台，不存在则无法跳跃
-        if q_next_platform.is_empty() {
+        if q_next_platforms.is_empty() {
             warn!("There is no next platform");
             return;
         }
-        // 获取当前平台、下一个平台和玩家的信息
-        let (current_platform_entity, current_platform_transform, current_platform_shape) =
-            q_current_platform.single();
-        let (next_platform_entity, next_platform_transform, next_platform_shape) =
-            q_next_platform.single();
-        let player = q_player.single();
-
-        // 计算跳跃后的落点位置
-        // 根据平台排列方向(X轴或Z轴)决定跳跃方向
-        let landing_pos = if (next_platform_transform.translation.x
-            - current_platform_transform.translation.x)
-            < 0.1  // 如果X轴差值小于0.1，说明平台排列在Z轴方向
-        {
-            // Z轴方向跳跃计算
-            Vec3::new(
-                player.translation.x,  // X轴位置不变
-                INITIAL_PLAYER_POS.y,  // Y轴高度保持初始位置
-                player.translation.z
-                    - 3.0 * accumulator.0.as_ref().unwrap().elapsed().as_secs_f32(), // Z轴位移与蓄力时间成正比
-            )
-        } else {  // 否则平台排列在X轴方向
-            // X轴方向跳跃计算
-            Vec3::new(
-                player.translation.x
-                    + 3.0 * accumulator.0.as_ref().unwrap().elapsed().as_secs_f32(), // X轴位移与蓄力时间成正比
-                INITIAL_PLAYER_POS.y,  // Y轴高度保持初始位置
-                player.translation.z,  // Z轴位置不变
-            )
-        };
+        // 平台标记在落地时转移，当前平台标记可能短暂缺失，此时跳过本帧的跳跃而不是崩溃
+        if q_current_platform.is_empty() {
+            warn!("There is no current platform");
+            return;
+        }
+        // 获取当前平台和玩家的信息，任意一个不唯一时跳过本帧
+        let (
+            Ok((current_platform_entity, current_platform_transform, current_platform_shape)),
+            Ok(mut player),
+        ) = (
+            q_current_platform.get_single(),
+            q_player.get_single_mut(),
+        ) else {
+            return;
+        };
+
+        // 下一个平台候选按生成顺序排列，录像中按序号记录瞄准的平台
+        let mut next_platforms: Vec<_> = q_next_platforms.iter().collect();
+        next_platforms.sort_by_key(|(.., spawn_index)| spawn_index.0);
+        // 回放时跳向录像中的平台，否则跳向玩家瞄准的平台，缺少瞄准标记时跳向第一个候选
+        let aimed_index = jump_input
+            .replay_target()
+            .unwrap_or_else(|| {
+                next_platforms
+                    .iter()
+                    .position(|(_, _, _, _, _, _, aimed, _)| *aimed)
+                    .unwrap_or(0)
+            })
+            .min(next_platforms.len() - 1);
+        let aimed_platform_pos = next_platforms[aimed_index].1.translation;
+
+        // 松开时玩家恢复原始缩放和站立高度，跳跃从未压缩的平台顶面开始
+        player.scale = Vec3::ONE;
+        player.translation.y = standing_y(current_platform_transform.translation.y, 1.0, 1.0);
+
+        // 蓄力时长，超过最大蓄力时长的部分不再生效
+        let mut charge_secs = accumulator.charge_secs(&rules.max_charge);
+        // 玩家蓄力过短就松开时按设置处理，避免轻点一下就跳出当前平台；
+        // 原地小跳按零蓄力记录，回放时模拟输入直接使用录像中的蓄力时长，结果与录制时相同
+        if charge_secs < rules.tuning.min_charge_secs && !simulated {
+            match *rules.short_tap {
+                ShortTapBehavior::Jump => {}
+                ShortTapBehavior::Cancel => {
+                    info!("Charge released too early, jump canceled");
+                    accumulator.0 = None;
+                    return;
+                }
+                ShortTapBehavior::Hop => charge_secs = 0.0,
+            }
+        }
+        // 记录到录像和本局统计中
+        jump_input.record_jump(charge_secs, aimed_index);
+        score_keeper.count_jump();
+
+        // 计算跳跃后的落点位置，跳跃方向指向瞄准的平台
+        let mut landing_pos = landing_position(
+            player.translation,
+            current_platform_transform.translation,
+            aimed_platform_pos,
+            charge_secs,
+            &rules.max_charge,
+            &rules.tuning,
+        );
         
-        // 调试信息输出
-        dbg!(player.translation);
-        dbg!(accumulator.0.as_ref().unwrap().elapsed().as_secs_f32());
+        // 调试信息输出，默认日志级别下不显示
+        debug!(
+            "Jump from {} with charge {:.2}s to {}",
+            player.translation, charge_secs, landing_pos
+        );
+
+        // 跳跃持续时间与蓄力时长成正比，但不短于最短时长，连击加速时按倍率缩短
+        let animation_duration = rules.tuning.jump_duration(charge_secs) / score_keeper.chain_speed();
+
+        // 对每个候选平台判断落地结果，移动平台使用按跳跃动画时长预测的落地时刻位置，
+        // 正在播放生成动画的平台落地时已经长到完整大小，按完整大小判断；
+        // 优先级相同时瞄准的平台优先，例如跳不够时按瞄准的平台判断倾倒方向
+        let aimed_first = std::iter::once(aimed_index)
+            .chain((0..next_platforms.len()).filter(|&index| index != aimed_index));
+        let Some((outcome, next_platform_entity, next_platform_pos, next_platform_bonus)) = aimed_first
+            .map(|index| {
+                let (entity, transform, shape, moving, spawning, bonus, _, _) = next_platforms[index];
+                let pos = transform.translation
+                    + moving.map_or(Vec3::ZERO, |moving| moving.offset_after(animation_duration));
+                let scale = SpawningPlatform::full_scale(spawning, transform);
+                let outcome = landing_outcome(
+                    current_platform_shape,
+                    current_platform_transform,
+                    shape,
+                    &transform.with_translation(pos).with_scale(scale),
+                    landing_pos,
+                    rules.landing_tolerance.0,
+                );
+                (outcome, entity, pos, bonus)
+            })
+            .min_by_key(|(outcome, ..)| outcome.priority())
+        else {
+            return;
+        };
+
+        // 落在当前平台上或碰到当前平台边缘时，落点高度为当前平台顶面的站立高度
+        if matches!(
+            outcome,
+            LandingOutcome::CurrentPlatform | LandingOutcome::TouchedCurrentPlatform
+        ) {
+            landing_pos.y = standing_y(current_platform_transform.translation.y, 1.0, 1.0);
+        }
 
         // 初始化跳跃动画
-        // 跳跃持续时间与蓄力时长成正比，但至少为0.5秒
         jump_state.animate_jump(
             player.translation,      // 起始位置
             landing_pos,             // 目标位置
-            (accumulator.0.as_ref().unwrap().elapsed().as_secs_f32() / 2.0).max(0.5), // 动画持续时间
+            animation_duration,      // 动画持续时间
         );
+        game_events.send(GameEvent::Jump);
 
-        // 平台检测：判断角色是否落在平台上
-        // 检查条件：要么落在当前平台，要么落在下一个平台
-        if current_platform_shape
-            .is_landed_on_platform(current_platform_transform.translation, landing_pos)
-            || next_platform_shape
-                .is_landed_on_platform(next_platform_transform.translation, landing_pos)
-        {
-            // 成功跳跃，未摔落
-            jump_state.falled = false;
-            
-            // 如果落在了下一个平台上
-            if next_platform_shape
-                .is_landed_on_platform(next_platform_transform.translation, landing_pos)
-            {
-                // 分数加1
-                score.0 += 1;
-                
-                // 添加分数上升动画事件
-                score_up_queue.0.push(ScoreUpEvent {
-                    landing_pos: Vec3::new(landing_pos.x, landing_pos.y + 0.5, landing_pos.z),
-                });
+        match outcome {
+            // 落在当前平台上，成功跳跃但不得分，不影响连击
+            LandingOutcome::CurrentPlatform => {
+                jump_state.falled = false;
+            }
+            // 落在下一个平台上
+            LandingOutcome::NextPlatform { perfect, near_miss } => {
+                jump_state.falled = false;
+                // 拾取过放宽完美判定道具时，落点在放宽后的范围内同样算完美落地
+                let perfect = wide_perfect.consume(next_platform_pos, landing_pos) || perfect;
+
+                // 计分，完美落地不额外加分、只累计连击，双倍得分平台得分翻倍；险些踩空同样得分，落地后先摇晃再站稳
+                score_keeper.land(perfect, next_platform_bonus, landing_pos);
+                jump_state.perfect = perfect;
+                if near_miss {
+                    info!("Near miss, player wobbles on the edge");
+                    jump_state.start_wobble(edge_tilt_axis(next_platform_pos, landing_pos));
+                }
 
                 // 更新平台状态：
-                // 1. 移除下一个平台的NextPlatform标记
-                commands.entity(next_platform_entity).remove::<NextPlatform>();
+                // 1. 移除落上的平台的NextPlatform、瞄准和双倍得分标记，分岔路径中没选的候选平台直接移除
+                commands
+                    .entity(next_platform_entity)
+                    .remove::<(NextPlatform, AimedPlatform, BonusPlatform)>();
+                for (entity, ..) in &next_platforms {
+                    if *entity != next_platform_entity {
+                        commands.entity(*entity).despawn_recursive(); // 递归删除，同时清理中心标记和道具
+                    }
+                }
                 // 2. 为下一个平台添加CurrentPlatform标记
                 commands.entity(next_platform_entity).insert(CurrentPlatform);
-                // 3. 移除当前平台的CurrentPlatform标记
-                commands.entity(current_platform_entity).remove::<CurrentPlatform>();
+                // 3. 移除当前平台的CurrentPlatform标记，并标记为已跳过的平台
+                commands
+                    .entity(current_platform_entity)
+                    .remove::<CurrentPlatform>()
+                    .insert(PassedPlatform);
             }
-
-        // 蓄力不足或蓄力过度，角色摔落
-        } else {
-            // 标记为摔落状态
-            jump_state.falled = true;
-            
-            // 根据碰撞情况决定摔落类型
-            // 1. 是否碰到当前平台边缘
-            if current_platform_shape.is_touched_player(
-                current_platform_transform.translation,
-                landing_pos,
-                0.2,  // 接触检测半径
-            ) {
+            // 碰到当前平台边缘，沿当前平台中心指向落点的方向倾倒
+            LandingOutcome::TouchedCurrentPlatform => {
                 info!("Player touched current platform");
-                // 根据跳跃方向确定倾斜方向
-                let fall_direction = if landing_pos.x == player.translation.x {
-                    Vec3::NEG_X
-                } else {
-                    Vec3::NEG_Z
-                };
-                // 初始化倾斜摔落动画
-                fall_state.animate_tilt_fall(landing_pos, fall_direction);
+                jump_state.falled = true;
+                score_keeper.break_chain();
+                fall_state.animate_tilt_fall(
+                    landing_pos,
+                    edge_tilt_axis(current_platform_transform.translation, landing_pos),
+                );
             }
-            // 2. 是否碰到下一个平台边缘
-            else if next_platform_shape.is_touched_player(
-                next_platform_transform.translation,
-                landing_pos,
-                0.2,
-            ) {
+            // 沿下一个平台中心指向落点的方向倾倒，跳过头时向前、跳不够时向后、偏到侧面时向侧面
+            LandingOutcome::TouchedNextPlatform { .. } => {
                 info!("Player touched next platform");
-                // 根据跳跃方向和位置确定倾斜方向
-                let fall_direction = if landing_pos.x == player.translation.x {
-                    if landing_pos.z < next_platform_transform.translation.z {
-                        Vec3::NEG_X
-                    } else {
-                        Vec3::X
-                    }
-                } else {
-                    if landing_pos.x < next_platform_transform.translation.x {
-                        Vec3::Z
-                    } else {
-                        Vec3::NEG_Z
-                    }
-                };
-                // 初始化倾斜摔落动画
-                fall_state.animate_tilt_fall(landing_pos, fall_direction);
+                jump_state.falled = true;
+                score_keeper.break_chain();
+                // 移动平台使用预测的落地时刻位置
+                fall_state.animate_tilt_fall(landing_pos, edge_tilt_axis(next_platform_pos, landing_pos));
             }
-            // 3. 完全没碰到平台，直接下落
-            else {
+            // 完全没碰到平台，直接下落
+            LandingOutcome::Missed => {
+                jump_state.falled = true;
+                score_keeper.break_chain();
                 fall_state.animate_straight_fall(landing_pos);
             }
         }
 
-        // 结束蓄力状态
+        // 结束蓄力状态，蓄力音效由stop_accumulation_sound统一停止
         accumulator.0 = None;
-        
-        // 停止蓄力音效
-        for sink in q_accumulation_sound.iter() {
-            sink.pause();
-        }
     }
 }
 
 /// 跳跃动画系统
 /// 
-/// 实现玩家跳跃的弧形轨迹和旋转动画
+/// 实现玩家跳跃的弧形轨迹和旋转动画；每帧按已进行的时长直接计算位置和朝向，
+/// 而不是逐帧累加旋转，不同帧率下的轨迹完全相同
 pub fn animate_jump(
     mut commands: Commands,
-    mut jump_state: ResMut<JumpState>,
-    time: Res<Time>,
-    mut q_player: Query<&mut Transform, With<Player>>,
-    game_sounds: Res<GameSounds>,
+    animation: JumpAnimation,
+    mut perfect_flash: ResMut<PerfectFlash>,
+    combo: Res<Combo>,
+    face_jump_direction: Res<FaceJumpDirection>,
+    mut q_player: Query<(Entity, &mut Transform), With<Player>>,
+    mut game_events: EventWriter<GameEvent>,
 ) {
+    let JumpAnimation { mut jump_state, tuning, time, .. } = animation;
     // 只有当跳跃未完成时执行动画
     if !jump_state.completed {
-        let mut player = q_player.single_mut();
+        let Ok((player_entity, mut player)) = q_player.get_single_mut() else {
+            return;
+        };
 
         // 计算跳跃轨迹的中心点（用于圆周运动）
         let around_point = Vec3::new(
@@ -376,172 +1311,690 @@ pub fn animate_jump(
             (jump_state.start_pos.z + jump_state.end_pos.z) / 2.0, // 中心点Z坐标
         );
 
-        // 确定旋转轴：根据跳跃方向确定
-        let rotate_axis = if (jump_state.end_pos.x - jump_state.start_pos.x) < 0.1 {
-            Vec3::X  // Z轴方向跳跃，绕X轴旋转
-        } else {
-            Vec3::Z  // X轴方向跳跃，绕Z轴旋转
-        };
+        // 确定旋转轴：垂直于水平跳跃方向，保证四个方向的跳跃都向前翻转
+        // 例如X轴正方向跳跃绕Z轴旋转，Z轴负方向跳跃绕X轴旋转
+        let jump_direction = horizontal_direction(jump_state.start_pos, jump_state.end_pos);
+        let rotate_axis = jump_direction.cross(Vec3::Y);
         
-        // 计算旋转四元数
-        // 旋转速度与动画持续时间成反比，确保在指定时间内完成180度旋转
-        let quat = Quat::from_axis_angle(
-            rotate_axis,
-            -(1.0 / jump_state.animation_duration) * PI * time.delta_secs(),
-        );
+        // 本帧动画推进的时长，卡顿后的长帧也只推进一小段，不会瞬间完成跳跃
+        let delta = animation_delta(&time);
 
-        // 预测下一帧位置，用于判断是否到达跳跃底部
-        let mut clone_player = player.clone();
-        clone_player.translate_around(around_point, quat);
-        
-        // 判断是否到达跳跃底部
-        if clone_player.translation.y < INITIAL_PLAYER_POS.y {
+        // 判断本帧是否转完180度到达目标位置，起点和终点高度不同时也按时长判断
+        jump_state.elapsed += delta;
+        if jump_state.elapsed >= jump_state.animation_duration {
             // 到达目标位置，结束跳跃
             player.translation = jump_state.end_pos;
-            player.rotation = Quat::IDENTITY; // 重置旋转
+            player.rotation = Quat::IDENTITY; // 重置旋转，同时恢复默认朝向
 
             // 标记跳跃完成
             jump_state.completed = true;
             
             // 如果成功跳跃（未摔落），播放成功音效
             if !jump_state.falled {
-                commands.spawn((
-                    AudioPlayer(game_sounds.success.clone()),
-                    PlaybackSettings::DESPAWN,
-                ));
+                // 完美落地时音调随连击数升高，并触发屏幕闪白
+                if jump_state.perfect {
+                    perfect_flash.0.reset();
+                }
+                game_events.send(GameEvent::Land {
+                    perfect: jump_state.perfect,
+                    combo: combo.0,
+                });
+
+                // 落地冲击压扁，险些踩空时由摇晃动画表现落地，不再压扁
+                if jump_state.wobble_axis.is_none() {
+                    commands.entity(player_entity).insert(LandingSquash {
+                        timer: Timer::from_seconds(LANDING_SQUASH_SECS, TimerMode::Once),
+                    });
+                }
             }
         } else {
             // 继续执行跳跃动画
-            player.translate_around(around_point, quat);
+            // 跳跃进度，0为起点，1为终点
+            let progress = jump_state.elapsed / jump_state.animation_duration;
 
-            // 角色自身旋转动画
-            player.rotate_local_axis(
-                Dir3::new_unchecked(rotate_axis),
-                -(1.0 / jump_state.animation_duration) * TAU * time.delta_secs(), // 完成360度旋转
-            );
+            // 绕中心点转过180度正好从起点到达终点
+            let quat = Quat::from_axis_angle(rotate_axis, -PI * progress);
+            player.translation = around_point + quat * (jump_state.start_pos - around_point);
+
+            // 角色自身旋转动画，起跳时为站立姿势，落地时完成整圈自转；
+            // 开启面朝跳跃方向时先转向落点，再在此基础上翻滚
+            let tumble = Quat::from_axis_angle(rotate_axis, -tuning.jump_spin_angle * progress);
+            player.rotation = if face_jump_direction.0 {
+                tumble * jump_state.facing
+            } else {
+                tumble
+            };
         }
     }
 }
 
-// 角色蓄力效果
-// TODO 蓄力过程中保持与平台相接触
-pub fn animate_player_accumulation(
+/// 落地压扁恢复的时长（秒）
+const LANDING_SQUASH_SECS: f32 = 0.15;
+/// 落地瞬间玩家Y轴被压扁的比例
+const LANDING_SQUASH_AMOUNT: f32 = 0.25;
+
+/// 输入被忽略时摇头动画的时长（秒）
+const REJECTED_INPUT_SHAKE_SECS: f32 = 0.3;
+/// 摇头的最大角度（弧度）
+const REJECTED_INPUT_SHAKE_ANGLE: f32 = 0.3;
+/// 摇头的角频率（弧度/秒）
+const REJECTED_INPUT_SHAKE_SPEED: f32 = 40.0;
+
+/// 输入被忽略反馈系统
+/// 
+/// 跳跃输入门控未打开（准备跳跃计时器、开局倒计时或入场动画未结束）时按下蓄力，
+/// 让玩家摇头提示现在还不能跳跃；只在真正被忽略的按下时触发，摇头过程中再次按下从头开始
+/// 
+/// # 参数
+/// - `commands`: 命令系统，用于添加摇头组件
+/// - `jump_input`: 跳跃输入
+/// - `jump_gate`: 跳跃输入门控
+/// - `q_player`: 玩家实体查询
+pub fn reject_early_input(
+    mut commands: Commands,
+    jump_input: JumpInput,
+    jump_gate: JumpGate,
+    q_player: Query<(Entity, &Transform, Option<&RejectedInputShake>), With<Player>>,
+) {
+    if jump_gate.is_open() || !jump_input.just_pressed() {
+        return;
+    }
+    let Ok((entity, player, shake)) = q_player.get_single() else {
+        return;
+    };
+    debug!("Charge ignored before the jump gate opened");
+    commands.entity(entity).insert(RejectedInputShake {
+        elapsed: 0.0,
+        // 摇头过程中再次按下时保留最初的朝向
+        base_rotation: shake.map_or(player.rotation, |shake| shake.base_rotation),
+    });
+}
+
+/// 输入被忽略时的摇头动画系统
+/// 
+/// 玩家绕竖直轴快速左右转动，幅度逐渐减小，结束时恢复原来的朝向
+/// 
+/// # 参数
+/// - `commands`: 命令系统，用于移除摇头组件
+/// - `time`: 时间资源
+/// - `q_player`: 正在摇头的玩家查询
+pub fn animate_rejected_input_shake(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut q_player: Query<(Entity, &mut Transform, &mut RejectedInputShake), With<Player>>,
+) {
+    for (entity, mut player, mut shake) in &mut q_player {
+        shake.elapsed += time.delta_secs();
+        if shake.elapsed >= REJECTED_INPUT_SHAKE_SECS {
+            player.rotation = shake.base_rotation;
+            commands.entity(entity).remove::<RejectedInputShake>();
+            continue;
+        }
+        let decay = 1.0 - shake.elapsed / REJECTED_INPUT_SHAKE_SECS;
+        let angle = REJECTED_INPUT_SHAKE_ANGLE * decay * (shake.elapsed * REJECTED_INPUT_SHAKE_SPEED).sin();
+        player.rotation = shake.base_rotation * Quat::from_rotation_y(angle);
+    }
+}
+
+/// 落地冲击压扁动画系统
+/// 
+/// 落地瞬间玩家压扁到最大程度，随后按缓出曲线恢复原始缩放，脚底始终贴在平台顶面；
+/// 恢复过程中开始蓄力时立即结束，由蓄力效果接管玩家缩放
+pub fn animate_landing_squash(
+    mut commands: Commands,
     accumulator: Res<Accumulator>,
+    jump_state: Res<JumpState>,
+    time: Res<Time>,
+    mut q_player: Query<(Entity, &mut Transform, &mut LandingSquash), With<Player>>,
+) {
+    let Ok((entity, mut player, mut squash)) = q_player.get_single_mut() else {
+        return;
+    };
+    if accumulator.0.is_some() {
+        commands.entity(entity).remove::<LandingSquash>();
+        return;
+    }
+
+    squash
+        .timer
+        .tick(Duration::from_secs_f32(animation_delta(&time)));
+    // 剩余压扁程度，从1平滑减小到0
+    let remaining = (1.0 - squash.timer.fraction()).powi(2);
+    let squash_y = LANDING_SQUASH_AMOUNT * remaining;
+    // 压扁时水平方向略微撑开，保持体积感
+    player.scale = Vec3::new(1.0 + squash_y * 0.8, 1.0 - squash_y, 1.0 + squash_y * 0.8);
+    // 跳跃终点为未压扁时的站立高度，压扁后中心随之下移，脚底保持不动
+    player.translation.y = jump_state.end_pos.y - PLAYER_STAND_OFFSET * squash_y;
+
+    if squash.timer.finished() {
+        player.scale = Vec3::ONE;
+        player.translation.y = jump_state.end_pos.y;
+        commands.entity(entity).remove::<LandingSquash>();
+    }
+}
+
+/// 险些踩空时摇晃动画的时长（秒），摇晃结束前不能开始下一次蓄力
+const WOBBLE_SECS: f32 = 0.8;
+/// 摇晃的最大倾斜角度（弧度）
+const WOBBLE_MAX_ANGLE: f32 = 0.35;
+/// 摇晃的角频率（弧度/秒）
+const WOBBLE_FREQUENCY: f32 = 14.0;
+/// 摇晃幅度的衰减速度
+const WOBBLE_DAMPING: f32 = 5.0;
+
+/// 落地摇晃动画系统
+/// 
+/// 险些踩空时，跳跃完成后玩家以脚底为支点先倒向平台边缘，再来回摇晃逐渐站稳；
+/// 摇晃幅度按指数衰减，结束后恢复站立姿势
+pub fn animate_wobble(
+    mut jump_state: ResMut<JumpState>,
+    time: Res<Time>,
     mut q_player: Query<&mut Transform, With<Player>>,
+) {
+    // 跳跃动画完成后才开始摇晃
+    if !jump_state.completed {
+        return;
+    }
+    let Some(axis) = jump_state.wobble_axis else {
+        return;
+    };
+    let Ok(mut player) = q_player.get_single_mut() else {
+        return;
+    };
+
+    jump_state.wobble_elapsed += animation_delta(&time);
+    let t = jump_state.wobble_elapsed;
+    if t >= WOBBLE_SECS {
+        // 摇晃结束，恢复站立姿势
+        player.translation = jump_state.end_pos;
+        player.rotation = Quat::IDENTITY;
+        jump_state.wobble_axis = None;
+        return;
+    }
+
+    // 衰减的正弦摆动，先倒向平台边缘
+    let angle = WOBBLE_MAX_ANGLE * (-WOBBLE_DAMPING * t).exp() * (WOBBLE_FREQUENCY * t).sin();
+    let rotation = Quat::from_axis_angle(axis, angle);
+    // 以脚底为支点旋转
+    let pivot = jump_state.end_pos - Vec3::Y * PLAYER_STAND_OFFSET;
+    player.translation = pivot + rotation * (Vec3::Y * PLAYER_STAND_OFFSET);
+    player.rotation = rotation;
+}
+
+/// 结束落地摇晃
+/// 
+/// 进入游戏时调用，避免上一局未结束的摇晃影响新的玩家
+pub fn settle_wobble(mut jump_state: ResMut<JumpState>) {
+    jump_state.wobble_axis = None;
+}
+
+/// 角色蓄力效果
+/// 
+/// 蓄力时玩家逐渐压扁，并随平台一起下沉，保持底部贴在被压缩的平台顶面上；
+/// 蓄力结束时恢复原始缩放和站立高度。开启过度蓄力取消时，
+/// 满蓄力后按住过久会直接取消蓄力，需要重新按下才能跳跃
+pub fn animate_player_accumulation(
+    mut accumulator: ResMut<Accumulator>,
+    max_charge: Res<MaxCharge>,
+    tuning: Res<PhysicsTuning>,
+    fizzle_on_overcharge: Res<FizzleOnOvercharge>,
+    mut q_player: Query<(&mut Transform, Has<LandingSquash>), With<Player>>,
+    q_current_platform: Query<&Transform, (With<CurrentPlatform>, Without<Player>)>,
     time: Res<Time>,
 ) {
-    let mut player = q_player.single_mut();
+    let Ok((mut player, landing_squash)) = q_player.get_single_mut() else {
+        return;
+    };
+    // 蓄力失效：清除蓄力后由下面的分支恢复玩家缩放，蓄力音效由stop_accumulation_sound停止，
+    // 平台随后自动回弹；松开按键时因为没有蓄力不会起跳
+    if fizzle_on_overcharge.0 && accumulator.is_overcharged(&max_charge, tuning.fizzle_delay) {
+        info!("Charge fizzled");
+        accumulator.0 = None;
+    }
     match accumulator.0 {
         Some(_) => {
-            player.scale.x = (player.scale.x + 0.12 * time.delta_secs()).min(1.3);
-            player.scale.y = (player.scale.y - 0.15 * time.delta_secs()).max(0.6);
-            player.scale.z = (player.scale.z + 0.12 * time.delta_secs()).min(1.3);
+            // 达到最大蓄力时长后停止压缩，让玩家知道继续按住已经没有效果
+            if !accumulator.is_full(&max_charge) {
+                player.scale.x = (player.scale.x + 0.12 * animation_delta(&time)).min(1.3);
+                player.scale.y = (player.scale.y - 0.15 * animation_delta(&time)).max(0.6);
+                player.scale.z = (player.scale.z + 0.12 * animation_delta(&time)).min(1.3);
+            }
+            // 平台压缩由animate_platform_accumulation完成，这里跟随平台顶面高度
+            if let Ok(platform) = q_current_platform.get_single() {
+                player.translation.y =
+                    standing_y(platform.translation.y, platform.scale.y, player.scale.y);
+            }
         }
-        None => {
+        // 只在蓄力刚结束（例如蓄力被取消）时恢复，跳跃过程中不修改玩家高度；
+        // 落地压扁由animate_landing_squash负责恢复
+        None if player.scale != Vec3::ONE && !landing_squash => {
             player.scale = Vec3::ONE;
+            if let Ok(platform) = q_current_platform.get_single() {
+                player.translation.y = standing_y(platform.translation.y, 1.0, 1.0);
+            }
+        }
+        None => {}
+    }
+}
+
+/// 落点预览使用的当前平台查询，返回平台位置和形状
+type PreviewCurrentPlatformQuery<'w, 's> = Query<
+    'w,
+    's,
+    (&'static Transform, &'static PlatformShape),
+    (With<CurrentPlatform>, Without<LandingMarker>),
+>;
+
+/// 落点预览使用的瞄准平台查询，返回平台位置、形状和可选的生成动画
+type PreviewNextPlatformQuery<'w, 's> = Query<
+    'w,
+    's,
+    (&'static Transform, &'static PlatformShape, Option<&'static SpawningPlatform>),
+    (With<NextPlatform>, With<AimedPlatform>, Without<LandingMarker>),
+>;
+
+/// 落点标记查询，返回标记实体、位置和材质
+type LandingMarkerQuery<'w, 's> = Query<
+    'w,
+    's,
+    (Entity, &'static mut Transform, &'static MeshMaterial3d<StandardMaterial>),
+    With<LandingMarker>,
+>;
+
+/// 落点预测系统参数
+///
+/// 组合跳跃规则、玩家、当前平台和瞄准的下一个平台，按与跳跃逻辑相同的公式和判定预测落点
+#[derive(SystemParam)]
+pub struct LandingPrediction<'w, 's> {
+    rules: JumpRules<'w>,
+    q_player: Query<'w, 's, &'static Transform, (With<Player>, Without<LandingMarker>)>,
+    q_current_platform: PreviewCurrentPlatformQuery<'w, 's>,
+    q_next_platform: PreviewNextPlatformQuery<'w, 's>,
+}
+
+impl LandingPrediction<'_, '_> {
+    /// 现在松开时的落点和是否会跳过下一个平台，缺少玩家或平台时返回`None`
+    fn predict(&self, accumulator: &Accumulator) -> Option<(Vec3, bool)> {
+        let (
+            Ok(player),
+            Ok((current_platform, current_platform_shape)),
+            Ok((next_platform, next_platform_shape, next_platform_spawning)),
+        ) = (
+            self.q_player.get_single(),
+            self.q_current_platform.get_single(),
+            self.q_next_platform.get_single(),
+        ) else {
+            return None;
+        };
+
+        // 使用与跳跃逻辑相同的公式计算落点
+        let landing_pos = landing_position(
+            player.translation,
+            current_platform.translation,
+            next_platform.translation,
+            accumulator.charge_secs(&self.rules.max_charge),
+            &self.rules.max_charge,
+            &self.rules.tuning,
+        );
+        // 使用与跳跃逻辑相同的落地判定，判断现在松开是否会跳过头；
+        // 生成动画中的平台与跳跃逻辑一样按完整大小判断
+        let next_platform_scale =
+            SpawningPlatform::full_scale(next_platform_spawning, next_platform);
+        let outcome = landing_outcome(
+            current_platform_shape,
+            current_platform,
+            next_platform_shape,
+            &next_platform.with_scale(next_platform_scale),
+            landing_pos,
+            self.rules.landing_tolerance.0,
+        );
+        let overshoot = is_overshoot(
+            outcome,
+            current_platform.translation,
+            next_platform.translation,
+            landing_pos,
+        );
+        Some((landing_pos, overshoot))
+    }
+}
+
+/// 落点预览系统
+/// 
+/// 蓄力过程中每帧计算预测落点，并在平台顶面高度显示半透明标记；蓄力结束或取消时移除标记。
+/// 同时判断现在松开是否会跳过下一个平台，跳过头时标记变红
+/// 
+/// # 参数
+/// - `commands`: 命令系统，用于生成和销毁标记实体
+/// - `meshes`: 网格资源管理器
+/// - `materials`: 材质资源管理器
+/// - `accumulator`: 蓄力状态资源
+/// - `charge_overshoot`: 蓄力过度资源
+/// - `prediction`: 落点预测，包含跳跃规则、玩家、当前平台和瞄准的下一个平台
+/// - `q_marker`: 落点标记查询
+pub fn update_landing_preview(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    accumulator: Res<Accumulator>,
+    mut charge_overshoot: ResMut<ChargeOvershoot>,
+    prediction: LandingPrediction,
+    mut q_marker: LandingMarkerQuery,
+) {
+    // 未蓄力时移除所有落点标记
+    if accumulator.0.is_none() {
+        for (entity, _, _) in &q_marker {
+            commands.entity(entity).despawn();
+        }
+        charge_overshoot.0 = false;
+        return;
+    }
+
+    let Some((landing_pos, overshoot)) = prediction.predict(&accumulator) else {
+        return;
+    };
+    // 只在变化时写入，避免每帧触发变更检测
+    if charge_overshoot.0 != overshoot {
+        charge_overshoot.0 = overshoot;
+    }
+    let marker_color = if overshoot { OVERSHOOT_COLOR } else { LANDING_MARKER_COLOR };
+    // 标记放在平台顶面上方一点，避免与平台表面重叠闪烁
+    let marker_pos = Vec3::new(landing_pos.x, landing_pos.y - 0.49, landing_pos.z);
+
+    if let Ok((_, mut marker, material)) = q_marker.get_single_mut() {
+        marker.translation = marker_pos;
+        if let Some(material) = materials.get_mut(&material.0) {
+            if material.base_color != marker_color {
+                material.base_color = marker_color;
+            }
+        }
+    } else {
+        commands.spawn((
+            Mesh3d(meshes.add(Cylinder::new(0.15, 0.02))), // 扁平圆盘
+            MeshMaterial3d(materials.add(StandardMaterial {
+                base_color: marker_color, // 半透明白色，跳过头时为红色
+                alpha_mode: AlphaMode::Blend,
+                unlit: true,
+                ..default()
+            })),
+            Transform::from_translation(marker_pos),
+            LandingMarker,
+        ));
+    }
+}
+
+/// 影子可以投射到的平台查询，返回平台位置和形状
+type ShadowSurfaceQuery<'w, 's> = Query<
+    'w,
+    's,
+    (&'static Transform, &'static PlatformShape),
+    (Without<Player>, Without<PlayerShadow>),
+>;
+
+/// 玩家影子查询，返回影子位置和材质
+type PlayerShadowQuery<'w, 's> = Query<
+    'w,
+    's,
+    (&'static mut Transform, &'static MeshMaterial3d<StandardMaterial>),
+    With<PlayerShadow>,
+>;
+
+/// 玩家影子系统
+/// 
+/// 影子投射在玩家正下方最高的平台顶面上，下方没有平台时投射在地面上；
+/// 玩家离表面越高，影子越小越淡，跳到最高点时最小，帮助判断角色离平台的高度
+pub fn update_player_shadow(
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    q_player: Query<&Transform, (With<Player>, Without<PlayerShadow>)>,
+    q_platforms: ShadowSurfaceQuery,
+    mut q_shadow: PlayerShadowQuery,
+) {
+    let (Ok(player), Ok((mut shadow, material))) = (q_player.get_single(), q_shadow.get_single_mut()) else {
+        return;
+    };
+
+    // 玩家脚底高度，只考虑位于脚底下方的平台顶面
+    let feet_y = player.translation.y - PLAYER_STAND_OFFSET;
+    let surface_y = q_platforms
+        .iter()
+        .filter(|(platform, shape)| {
+            shape.is_landed_on_platform(platform.translation, platform.scale.x, player.translation)
+        })
+        .map(|(platform, _)| platform.translation.y + PLATFORM_HALF_HEIGHT * platform.scale.y)
+        .filter(|&top| top <= feet_y + 0.01)
+        .fold(0.0, f32::max); // 下方没有平台时投射在地面（高度0）上
+
+    // 离表面的高度映射到0.0（贴地）到1.0（达到淡出高度）
+    let height = ((feet_y - surface_y) / PLAYER_SHADOW_FADE_HEIGHT).clamp(0.0, 1.0);
+    let scale = 1.0 - (1.0 - PLAYER_SHADOW_MIN_SCALE) * height;
+
+    // 影子放在表面上方一点，避免与表面重叠闪烁
+    shadow.translation = player.translation.with_y(surface_y + 0.01);
+    shadow.scale = Vec3::new(scale, 1.0, scale);
+
+    // 只在透明度有明显变化时修改材质，get_mut会触发材质重新上传，需要先用get判断
+    let alpha = PLAYER_SHADOW_ALPHA * scale;
+    let alpha_changed = materials
+        .get(&material.0)
+        .is_some_and(|material| (material.base_color.alpha() - alpha).abs() > 0.01);
+    if alpha_changed {
+        if let Some(material) = materials.get_mut(&material.0) {
+            material.base_color.set_alpha(alpha);
         }
     }
 }
 
+/// 笔直下落结束时玩家中心的高度
+const STRAIGHT_FALL_BOTTOM_Y: f32 = 0.5;
+/// 倾斜后下落结束时玩家中心的高度
+const TILT_FALL_BOTTOM_Y: f32 = 0.2;
+/// 倾斜阶段最短持续的时长（秒），倾斜速度调得很快时也能看清倾斜动作
+const MIN_TILT_FALL_SECS: f32 = 0.25;
+
 /// 摔落动画系统
 /// 
 /// 处理玩家摔落时的动画效果，包括笔直下落和倾斜后下落两种类型
 /// 
 /// # 参数
-/// - `commands`: 命令系统，用于播放音效
-/// - `fall_state`: 摔落状态资源，控制摔落动画的进程
-/// - `jump_state`: 跳跃状态资源，确保跳跃完成后才开始摔落
-/// - `time`: 时间资源，控制动画速度
-/// - `next_game_state`: 游戏状态资源，在摔落后切换到游戏结束状态
+/// - `animation`: 跳跃动画参数，摔落状态控制摔落动画的进程，跳跃完成后才开始摔落，重试时重置两者
+/// - `retries`: 剩余重试次数资源
+/// - `state_transition`: 状态切换参数，在摔落后切换到游戏结束状态
 /// - `q_player`: 玩家实体查询，更新玩家位置和旋转
-/// - `game_sounds`: 游戏音效资源，播放摔落音效
+/// - `q_current_platform`: 当前平台查询，重试时玩家回到该平台
+/// - `game_events`: 游戏事件写入器，发送摔落事件播放摔落音效
 pub fn animate_fall(
-    mut commands: Commands,
-    mut fall_state: ResMut<FallState>,
-    jump_state: Res<JumpState>,
-    time: Res<Time>,
-    mut next_game_state: ResMut<NextState<GameState>>,
+    animation: JumpAnimation,
+    mut retries: ResMut<Retries>,
+    mut state_transition: StateTransition,
     mut q_player: Query<&mut Transform, With<Player>>,
-    game_sounds: Res<GameSounds>,
+    q_current_platform: Query<&Transform, (With<CurrentPlatform>, Without<Player>)>,
+    mut game_events: EventWriter<GameEvent>,
 ) {
+    let JumpAnimation { mut jump_state, mut fall_state, tuning, time } = animation;
     // 只有当摔落未完成且跳跃已完成时执行摔落动画
     if !fall_state.completed && jump_state.completed {
         // 播放摔落音效（仅播放一次）
         if !fall_state.played_sound {
-            commands.spawn((
-                AudioPlayer(game_sounds.fall.clone()),
-                PlaybackSettings::DESPAWN,
-            ));
+            game_events.send(GameEvent::Fall);
             fall_state.played_sound = true;
         }
         
         // 获取玩家实体
-        let mut player = q_player.single_mut();
+        let Ok(mut player) = q_player.get_single_mut() else {
+            return;
+        };
         
+        // 本帧动画推进的时长，卡顿后的长帧也只推进一小段，避免玩家瞬移
+        // 位置和朝向按已进行的时长直接计算，不同帧率下的摔落过程完全相同
+        fall_state.elapsed += animation_delta(&time);
+        let elapsed = fall_state.elapsed;
+
         // 根据摔落类型执行不同的动画逻辑
         match fall_state.fall_type {
             // 笔直下落类型
             FallType::Straight => {
-                // 判断是否摔落到底部
-                if player.translation.y < 0.5 {
-                    // 标记摔落完成
+                let y = fall_state.pos.y - tuning.fall_speed * elapsed;
+                // 落到底部时停在底部高度，标记摔落完成
+                player.translation.y = y.max(STRAIGHT_FALL_BOTTOM_Y);
+                if y <= STRAIGHT_FALL_BOTTOM_Y {
                     fall_state.completed = true;
-                    info!("Game over!");
-                    // 切换到游戏结束状态
-                    next_game_state.set(GameState::GameOver);
-                } else {
-                    // 持续向下移动（速度为0.7单位/秒）
-                    player.translation.y -= 0.7 * time.delta_secs();
                 }
             }
             
             // 倾斜后下落类型
             FallType::Tilt(direction) => {
-                if !fall_state.tilt_completed {
-                    // 第一阶段：倾斜动作
-                    // 设置旋转中心点（略低于初始位置）
-                    let around_point = Vec3::new(
-                        fall_state.pos.x,
-                        INITIAL_PLAYER_POS.y - 0.5,
-                        fall_state.pos.z,
-                    );
-                    
-                    // 检查是否完成倾斜
-                    if player.translation.y < around_point.y {
-                        fall_state.tilt_completed = true;
-                    } else {
-                        // 计算旋转四元数（每秒旋转90度）
-                        let quat = 
-                            Quat::from_axis_angle(direction, 1.0 * FRAC_PI_2 * time.delta_secs());
-                        // 围绕指定点旋转玩家
-                        player.rotate_around(around_point, quat);
-                    }
-                } else {
-                    // 第二阶段：下坠动作
-                    // 判断是否摔落到底部
-                    if player.translation.y < 0.2 {
-                        // 标记摔落完成
+                // 旋转中心点（碰到的平台顶面边缘，摔落起点位于站立高度）
+                let around_point = Vec3::new(
+                    fall_state.pos.x,
+                    fall_state.pos.y - PLAYER_STAND_OFFSET,
+                    fall_state.pos.z,
+                );
+                // 倾斜90度所需的时长（默认每秒旋转90度），不短于最短倾斜时长
+                let tilt_secs = (FRAC_PI_2 / tuning.tilt_speed).max(MIN_TILT_FALL_SECS);
+
+                // 倾斜在本帧才完成时，截掉超出倾斜时长的部分：本帧停在完全倾斜的姿势，
+                // 下一帧才开始下坠，长帧不会让倾斜阶段一闪而过
+                let mut elapsed = elapsed;
+                if !fall_state.tilt_completed && elapsed >= tilt_secs {
+                    fall_state.elapsed = tilt_secs;
+                    elapsed = tilt_secs;
+                }
+                let was_tilt_completed = fall_state.tilt_completed;
+
+                // 第一阶段：绕平台边缘倾斜，倾斜到90度时玩家中心与旋转中心等高
+                let angle = FRAC_PI_2 * (elapsed / tilt_secs).min(1.0);
+                let quat = Quat::from_axis_angle(direction, angle);
+                player.translation = around_point + quat * (fall_state.pos - around_point);
+                player.rotation = quat;
+                fall_state.tilt_completed = elapsed >= tilt_secs;
+
+                // 第二阶段：保持倾斜姿势下坠，从倾斜完成后的下一帧开始
+                if was_tilt_completed {
+                    let y = player.translation.y - tuning.fall_speed * (elapsed - tilt_secs);
+                    // 落到底部时停在底部高度，标记摔落完成
+                    player.translation.y = y.max(TILT_FALL_BOTTOM_Y);
+                    if y <= TILT_FALL_BOTTOM_Y {
                         fall_state.completed = true;
-                        info!("Game over!");
-                        // 切换到游戏结束状态
-                        next_game_state.set(GameState::GameOver);
-                    } else {
-                        // 持续向下移动（速度为0.7单位/秒）
-                        player.translation.y -= 0.7 * time.delta_secs();
                     }
                 }
             }
         }
+
+        // 摔落到底部后，还有剩余重试次数时回到当前平台重新跳跃，否则游戏结束
+        if fall_state.completed {
+            if retries.0 > 0 {
+                retries.0 -= 1;
+                info!("Retry granted, {} left", retries.0);
+                if let Ok(current_platform) = q_current_platform.get_single() {
+                    player.translation = current_platform
+                        .translation
+                        .with_y(standing_y(current_platform.translation.y, 1.0, 1.0));
+                }
+                player.rotation = Quat::IDENTITY;
+                player.scale = Vec3::ONE;
+                // 完全重置跳跃和摔落状态，与刚站上平台时一致
+                *jump_state = JumpState::default();
+                *fall_state = FallState::default();
+            } else {
+                info!("Game over!");
+                // 切换到游戏结束状态
+                state_transition.go(GameState::GameOver);
+            }
+        }
+    }
+}
+
+/// 创建蓄力粒子效果资源
+/// 
+/// 粒子在以发射实体为中心的球形区域内生成，位置由实体变换决定，同一资源可以在任意位置复用；
+/// 粒子从白渐变到黄再到红，最后消失
+/// 
+/// # 参数
+/// - `particle_count`: 每次生成的粒子数量
+fn accumulation_effect_asset(particle_count: u32) -> EffectAsset {
+    // 定义粒子颜色渐变（由白渐变到黄再到红，最后消失）
+    let mut color_gradient = Gradient::new();
+    color_gradient.add_key(0.0, Vec4::new(4.0, 4.0, 4.0, 1.0)); // 白色（过亮）
+    color_gradient.add_key(0.1, Vec4::new(4.0, 4.0, 0.0, 1.0)); // 黄色
+    color_gradient.add_key(0.9, Vec4::new(4.0, 0.0, 0.0, 1.0)); // 红色
+    color_gradient.add_key(1.0, Vec4::new(4.0, 0.0, 0.0, 0.0)); // 完全透明
+
+    // 定义粒子大小渐变（保持初始大小一段时间后消失）
+    let mut size_gradient = Gradient::new();
+    size_gradient.add_key(0.0, Vec3::splat(0.05)); // 初始大小
+    size_gradient.add_key(0.3, Vec3::splat(0.05)); // 保持大小
+    size_gradient.add_key(1.0, Vec3::splat(0.0));  // 消失
+
+    // 创建粒子效果模块
+    let mut module = Module::default();
+
+    // 设置粒子初始位置（以发射实体为中心的球形区域内）
+    let init_pos = SetPositionSphereModifier {
+        center: module.lit(Vec3::ZERO),    // 中心点在发射实体位置
+        radius: module.lit(1.0),           // 半径1.0
+        dimension: ShapeDimension::Volume, // 体积维度
+    };
+
+    // 设置粒子生命周期（2秒）
+    let lifetime = module.lit(2.);
+    let init_lifetime = SetAttributeModifier::new(Attribute::LIFETIME, lifetime);
+
+    // 设置粒子线性阻力（8.0）
+    let update_linear_drag = LinearDragModifier::constant(&mut module, 8.0);
+
+    EffectAsset::new(
+        particle_count,
+        Spawner::once((particle_count as f32).into(), true),
+        module,
+    )
+        .init(init_pos)                    // 初始化位置
+        .init(init_lifetime)                // 初始化生命周期
+        .update(update_linear_drag)         // 更新线性阻力
+        .render(ColorOverLifetimeModifier { // 颜色随时间变化
+            gradient: color_gradient,
+        })
+        .render(SizeOverLifetimeModifier {  // 大小随时间变化
+            gradient: size_gradient,
+            screen_space_size: false,       // 使用世界空间大小
+        })
+}
+
+/// 蓄力粒子效果资源系统参数
+///
+/// 组合粒子效果资源管理器、粒子效果质量和缓存的粒子效果资源，
+/// 粒子效果资源按粒子效果质量只创建一次并缓存复用
+#[derive(SystemParam)]
+pub struct AccumulationEffect<'w, 's> {
+    effects: ResMut<'w, Assets<EffectAsset>>,
+    particle_quality: Res<'w, ParticleQuality>,
+    cache: Local<'s, Option<(ParticleQuality, Handle<EffectAsset>)>>,
+}
+
+impl AccumulationEffect<'_, '_> {
+    /// 当前粒子效果质量对应的粒子效果资源
+    ///
+    /// 粒子效果质量变化后重新创建，旧资源在句柄全部释放后自动移除
+    fn effect(&mut self) -> Handle<EffectAsset> {
+        match self.cache.as_ref() {
+            Some((quality, effect)) if quality == self.particle_quality.as_ref() => effect.clone(),
+            _ => {
+                let effect = self
+                    .effects
+                    .add(accumulation_effect_asset(self.particle_quality.particle_count()));
+                *self.cache = Some((*self.particle_quality, effect.clone()));
+                effect
+            }
+        }
     }
 }
 
 /// 蓄力粒子效果生成系统
 /// 
-/// 在玩家蓄力过程中生成粒子效果，提供视觉反馈，粒子从红渐变到黄再到白
+/// 在玩家蓄力过程中生成粒子效果，提供视觉反馈，粒子从白渐变到黄再到红；
+/// 粒子效果资源按粒子效果质量只创建一次并缓存复用，避免蓄力时每次生成都新增资源
 /// 
 /// # 参数
 /// - `commands`: 命令系统，用于生成粒子效果实体
-/// - `effects`: 粒子效果资源管理器
+/// - `accumulation_effect`: 蓄力粒子效果资源，粒子效果质量决定粒子数量、生成频率以及是否生成
 /// - `accumulator`: 蓄力状态资源，判断是否处于蓄力状态
 /// - `effect_timer`: 粒子效果生成计时器，控制生成频率
 /// - `time`: 时间资源
@@ -549,76 +2002,42 @@ pub fn animate_fall(
 /// - `q_player`: 玩家实体查询，获取玩家位置
 pub fn animate_accumulation_particle_effect(
     mut commands: Commands,
-    mut effects: ResMut<Assets<EffectAsset>>,
+    mut accumulation_effect: AccumulationEffect,
     accumulator: Res<Accumulator>,
     mut effect_timer: ResMut<GenerateAccumulationParticleEffectTimer>,
     time: Res<Time>,
-    mut q_effect: Query<(Entity, &mut ParticleEffect, &mut Transform)>,
+    mut q_effect: Query<(Entity, &mut ParticleEffect, &mut Transform), Without<TimedEffect>>,
     q_player: Query<&Transform, (With<Player>, Without<ParticleEffect>)>,
 ) {
-    // 当玩家正在蓄力时生成粒子效果
-    if accumulator.0.is_some() {
+    // 粒子效果质量变化时同步生成间隔
+    let particle_quality = &accumulation_effect.particle_quality;
+    if particle_quality.is_changed() {
+        effect_timer.0.set_duration(particle_quality.spawn_interval());
+    }
+    let particle_count = particle_quality.particle_count();
+
+    // 当玩家正在蓄力时生成粒子效果，关闭粒子效果时不生成
+    if accumulator.0.is_some() && particle_count > 0 {
         // 计时器控制粒子生成频率
         effect_timer.0.tick(time.delta());
         if effect_timer.0.just_finished() {
             // 获取玩家位置
-            let player = q_player.single();
-            
-            // 定义粒子颜色渐变（由白渐变到黄再到红，最后消失）
-            let mut color_gradient = Gradient::new();
-            color_gradient.add_key(0.0, Vec4::new(4.0, 4.0, 4.0, 1.0)); // 白色（过亮）
-            color_gradient.add_key(0.1, Vec4::new(4.0, 4.0, 0.0, 1.0)); // 黄色
-            color_gradient.add_key(0.9, Vec4::new(4.0, 0.0, 0.0, 1.0)); // 红色
-            color_gradient.add_key(1.0, Vec4::new(4.0, 0.0, 0.0, 0.0)); // 完全透明
-
-            // 定义粒子大小渐变（保持初始大小一段时间后消失）
-            let mut size_gradient = Gradient::new();
-            size_gradient.add_key(0.0, Vec3::splat(0.05)); // 初始大小
-            size_gradient.add_key(0.3, Vec3::splat(0.05)); // 保持大小
-            size_gradient.add_key(1.0, Vec3::splat(0.0));  // 消失
+            let Ok(player) = q_player.get_single() else {
+                return;
+            };
 
             // 为粒子效果实体创建唯一名称
             let name = format!("accumulation{}", time.elapsed_secs() as u32);
-            
-            // 创建粒子效果模块
-            let mut module = Module::default();
-
-            // 设置粒子初始位置（球形区域内）
-            let init_pos = SetPositionSphereModifier {
-                center: module.lit(player.translation), // 中心点在玩家位置
-                radius: module.lit(1.0),                // 半径1.0
-                dimension: ShapeDimension::Volume,      // 体积维度
-            };
 
-            // 设置粒子生命周期（2秒）
-            let lifetime = module.lit(2.);
-            let init_lifetime = SetAttributeModifier::new(Attribute::LIFETIME, lifetime);
-
-            // 设置粒子线性阻力（8.0）
-            let update_linear_drag = LinearDragModifier::constant(&mut module, 8.0);
-
-            // 创建并配置粒子效果资源
-            let effect = effects.add(
-                EffectAsset::new(3, Spawner::once(3.0.into(), true), module)
-                    .init(init_pos)                    // 初始化位置
-                    .init(init_lifetime)                // 初始化生命周期
-                    .update(update_linear_drag)         // 更新线性阻力
-                    // .update(update_force_field)       // 注释掉的力场效果
-                    .render(ColorOverLifetimeModifier { // 颜色随时间变化
-                        gradient: color_gradient.clone(),
-                    })
-                    .render(SizeOverLifetimeModifier {  // 大小随时间变化
-                        gradient: size_gradient.clone(),
-                        screen_space_size: false,       // 使用世界空间大小
-                    }),
-            );
-            
-            // 生成粒子效果实体
+            // 复用缓存的粒子效果资源
+            let effect = accumulation_effect.effect();
+
+            // 生成粒子效果实体，粒子围绕玩家位置生成
             commands.spawn((
                 Name::new(name),                      // 设置实体名称
                 ParticleEffectBundle {
                     effect: ParticleEffect::new(effect), // 设置粒子效果
-                    transform: Transform::IDENTITY,      // 设置变换
+                    transform: Transform::from_translation(player.translation), // 设置在玩家位置
                     ..Default::default()
                 },
             ));
@@ -627,13 +2046,485 @@ pub fn animate_accumulation_particle_effect(
             effect_timer.0.reset();
         }
     } else {
-        // 当蓄力结束时，清理所有粒子效果
+        // 当蓄力结束或关闭粒子效果时，清理所有蓄力粒子效果（落地扬尘由自身计时器清理）
         for (entity, _, _) in &mut q_effect {
             commands.entity(entity).despawn();
         }
     }
 }
 
+/// 蓄力火花组件，Web平台没有粒子效果插件时用小球体模拟蓄力粒子
+#[cfg(target_arch = "wasm32")]
+#[derive(Debug, Component)]
+pub struct ChargeSpark(pub Timer);
+
+/// 蓄力火花的生命周期（秒），与蓄力粒子一致
+#[cfg(target_arch = "wasm32")]
+const CHARGE_SPARK_LIFETIME: f32 = 2.0;
+
+/// 蓄力火花半径，与蓄力粒子大小一致
+#[cfg(target_arch = "wasm32")]
+const CHARGE_SPARK_RADIUS: f32 = 0.025;
+
+/// 蓄力火花效果系统（Web平台的蓄力粒子替代）
+/// 
+/// 蓄力时按与粒子效果相同的频率在玩家周围随机生成小球体，
+/// 颜色由白渐变到黄再到红并逐渐缩小消失；蓄力结束时清理所有火花
+/// 
+/// # 参数
+/// - `commands`: 命令系统，用于生成和销毁火花实体
+/// - `meshes`: 网格资源管理器
+/// - `materials`: 材质资源管理器，每个火花使用独立材质以便单独变色
+/// - `spark_mesh`: 所有火花共用的球体网格
+/// - `accumulator`: 蓄力状态资源，判断是否处于蓄力状态
+/// - `effect_timer`: 粒子效果生成计时器，控制生成频率
+/// - `time`: 时间资源
+/// - `particle_quality`: 粒子效果质量设置，决定每次生成的火花数量和生成频率
+/// - `q_player`: 玩家实体查询，获取玩家位置
+/// - `q_sparks`: 火花查询
+#[cfg(target_arch = "wasm32")]
+pub fn animate_charge_sparks(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut spark_mesh: Local<Option<Handle<Mesh>>>,
+    accumulator: Res<Accumulator>,
+    mut effect_timer: ResMut<GenerateAccumulationParticleEffectTimer>,
+    particle_quality: Res<ParticleQuality>,
+    time: Res<Time>,
+    q_player: Query<&Transform, (With<Player>, Without<ChargeSpark>)>,
+    mut q_sparks: Query<(
+        Entity,
+        &mut Transform,
+        &mut ChargeSpark,
+        &MeshMaterial3d<StandardMaterial>,
+    )>,
+) {
+    // 粒子效果质量变化时同步生成间隔
+    if particle_quality.is_changed() {
+        effect_timer.0.set_duration(particle_quality.spawn_interval());
+    }
+    let spark_count = particle_quality.particle_count();
+
+    // 蓄力结束或关闭粒子效果时清理所有火花
+    if accumulator.0.is_none() || spark_count == 0 {
+        for (entity, ..) in &q_sparks {
+            commands.entity(entity).despawn();
+        }
+        return;
+    }
+
+    // 计时器控制火花生成频率，每次生成的数量由粒子效果质量决定
+    effect_timer.0.tick(time.delta());
+    if effect_timer.0.just_finished() {
+        if let Ok(player) = q_player.get_single() {
+            let mesh = spark_mesh
+                .get_or_insert_with(|| meshes.add(Sphere::new(CHARGE_SPARK_RADIUS)))
+                .clone();
+            for _ in 0..spark_count {
+                // 在玩家周围半径1.0的球体内随机取点
+                let offset = Vec3::new(
+                    rand::random::<f32>() * 2.0 - 1.0,
+                    rand::random::<f32>() * 2.0 - 1.0,
+                    rand::random::<f32>() * 2.0 - 1.0,
+                )
+                .clamp_length_max(1.0);
+                commands.spawn((
+                    Mesh3d(mesh.clone()),
+                    MeshMaterial3d(materials.add(StandardMaterial {
+                        base_color: Color::WHITE,
+                        alpha_mode: AlphaMode::Blend,
+                        unlit: true,
+                        ..default()
+                    })),
+                    Transform::from_translation(player.translation + offset),
+                    ChargeSpark(Timer::from_seconds(CHARGE_SPARK_LIFETIME, TimerMode::Once)),
+                ));
+            }
+        }
+        effect_timer.0.reset();
+    }
+
+    // 火花颜色和大小随生命周期变化，生命周期结束后销毁
+    for (entity, mut transform, mut spark, material) in &mut q_sparks {
+        if spark.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        let t = spark.0.fraction();
+        // 由白渐变到黄再到红，最后淡出
+        let color = if t < 0.1 {
+            Color::WHITE.mix(&Color::srgb(1.0, 1.0, 0.0), t / 0.1)
+        } else {
+            Color::srgb(1.0, 1.0, 0.0).mix(&Color::srgb(1.0, 0.0, 0.0), (t - 0.1) / 0.9)
+        };
+        let alpha = if t < 0.9 { 1.0 } else { (1.0 - t) / 0.1 };
+        if let Some(material) = materials.get_mut(&material.0) {
+            material.base_color = color.with_alpha(alpha);
+        }
+        // 保持初始大小一段时间后缩小消失
+        transform.scale = Vec3::splat(if t < 0.3 { 1.0 } else { (1.0 - t) / 0.7 });
+    }
+}
+
+/// 创建落地扬尘粒子效果资源
+/// 
+/// 粒子在发射实体所在水平面的圆环上生成并向外扩散，灰白色逐渐淡出
+fn landing_dust_effect_asset() -> EffectAsset {
+    // 定义粒子颜色渐变（灰白色逐渐淡出）
+    let mut color_gradient = Gradient::new();
+    color_gradient.add_key(0.0, Vec4::new(1.0, 1.0, 1.0, 0.8)); // 半透明白色
+    color_gradient.add_key(1.0, Vec4::new(0.8, 0.8, 0.8, 0.0)); // 完全透明
+
+    // 定义粒子大小渐变（逐渐变大后消失）
+    let mut size_gradient = Gradient::new();
+    size_gradient.add_key(0.0, Vec3::splat(0.06)); // 初始大小
+    size_gradient.add_key(0.7, Vec3::splat(0.1));  // 扩散变大
+    size_gradient.add_key(1.0, Vec3::splat(0.0));  // 消失
+
+    // 创建粒子效果模块
+    let mut module = Module::default();
+
+    // 设置粒子初始位置（水平圆环上）
+    let init_pos = SetPositionCircleModifier {
+        center: module.lit(Vec3::ZERO),      // 中心点在发射实体位置
+        axis: module.lit(Vec3::Y),           // 圆环位于水平面
+        radius: module.lit(0.2),             // 半径0.2
+        dimension: ShapeDimension::Surface,  // 只在圆周上生成
+    };
+
+    // 设置粒子初始速度（沿水平方向向外扩散）
+    let init_vel = SetVelocityCircleModifier {
+        center: module.lit(Vec3::ZERO),
+        axis: module.lit(Vec3::Y),
+        speed: module.lit(1.5),
+    };
+
+    // 设置粒子生命周期
+    let lifetime = module.lit(LANDING_DUST_LIFETIME);
+    let init_lifetime = SetAttributeModifier::new(Attribute::LIFETIME, lifetime);
+
+    // 设置粒子线性阻力，使扬尘快速减速
+    let update_linear_drag = LinearDragModifier::constant(&mut module, 5.0);
+
+    // 一次性发射20个粒子
+    EffectAsset::new(20, Spawner::once(20.0.into(), true), module)
+        .init(init_pos)                    // 初始化位置
+        .init(init_vel)                    // 初始化速度
+        .init(init_lifetime)               // 初始化生命周期
+        .update(update_linear_drag)        // 更新线性阻力
+        .render(ColorOverLifetimeModifier { // 颜色随时间变化
+            gradient: color_gradient,
+        })
+        .render(SizeOverLifetimeModifier {  // 大小随时间变化
+            gradient: size_gradient,
+            screen_space_size: false,       // 使用世界空间大小
+        })
+}
+
+/// 落地扬尘粒子效果生成系统
+/// 
+/// 检测跳跃动画结束的时刻，成功落地时在脚下生成一次性向外扩散的扬尘粒子；
+/// 粒子效果资源只创建一次并缓存复用，通过实体位置放到落点
+/// 
+/// # 参数
+/// - `commands`: 命令系统，用于生成粒子效果实体
+/// - `effects`: 粒子效果资源管理器
+/// - `effect_cache`: 缓存的扬尘粒子效果资源
+/// - `jump_state`: 跳跃状态资源，判断跳跃是否刚刚完成以及是否摔落
+/// - `was_jumping`: 上一帧是否处于跳跃中
+pub fn spawn_landing_dust_effect(
+    mut commands: Commands,
+    mut effects: ResMut<Assets<EffectAsset>>,
+    mut effect_cache: Local<Option<Handle<EffectAsset>>>,
+    jump_state: Res<JumpState>,
+    mut was_jumping: Local<bool>,
+) {
+    let landed = *was_jumping && jump_state.completed && !jump_state.falled;
+    *was_jumping = !jump_state.completed;
+    if !landed {
+        return;
+    }
+
+    // 扬尘中心位于玩家脚下的平台顶面
+    let center = Vec3::new(
+        jump_state.end_pos.x,
+        jump_state.end_pos.y - 0.45,
+        jump_state.end_pos.z,
+    );
+
+    let effect = effect_cache
+        .get_or_insert_with(|| effects.add(landing_dust_effect_asset()))
+        .clone();
+
+    // 生成粒子效果实体，粒子消失后由计时器销毁
+    commands.spawn((
+        Name::new("landing_dust"),
+        ParticleEffectBundle {
+            effect: ParticleEffect::new(effect),
+            transform: Transform::from_translation(center), // 设置在落点
+            ..Default::default()
+        },
+        TimedEffect(Timer::from_seconds(LANDING_DUST_LIFETIME + 0.2, TimerMode::Once)),
+    ));
+}
+
+/// 里程碑礼花粒子的生命周期（秒）
+const MILESTONE_BURST_LIFETIME: f32 = 1.0;
+
+/// 创建里程碑礼花粒子效果资源
+/// 
+/// 粒子从发射实体附近的球面上向外飞散，受重力落下，颜色由金色渐变到洋红
+fn milestone_burst_effect_asset() -> EffectAsset {
+    // 定义粒子颜色渐变（金色到洋红，最后消失）
+    let mut color_gradient = Gradient::new();
+    color_gradient.add_key(0.0, Vec4::new(4.0, 3.0, 0.5, 1.0)); // 金色（过亮）
+    color_gradient.add_key(0.6, Vec4::new(3.0, 0.5, 3.0, 1.0)); // 洋红
+    color_gradient.add_key(1.0, Vec4::new(3.0, 0.5, 3.0, 0.0)); // 完全透明
+
+    // 定义粒子大小渐变
+    let mut size_gradient = Gradient::new();
+    size_gradient.add_key(0.0, Vec3::splat(0.08));
+    size_gradient.add_key(1.0, Vec3::splat(0.0));
+
+    let mut module = Module::default();
+
+    // 粒子从发射实体附近的球面上生成并向外飞散
+    let init_pos = SetPositionSphereModifier {
+        center: module.lit(Vec3::ZERO),
+        radius: module.lit(0.1),
+        dimension: ShapeDimension::Volume,
+    };
+    let init_vel = SetVelocitySphereModifier {
+        center: module.lit(Vec3::ZERO),
+        speed: module.lit(3.0),
+    };
+    let lifetime = module.lit(MILESTONE_BURST_LIFETIME);
+    let init_lifetime = SetAttributeModifier::new(Attribute::LIFETIME, lifetime);
+
+    // 重力使粒子飞起后落下
+    let accel = module.lit(Vec3::new(0.0, -4.0, 0.0));
+    let update_accel = AccelModifier::new(accel);
+
+    EffectAsset::new(60, Spawner::once(60.0.into(), true), module)
+        .init(init_pos)
+        .init(init_vel)
+        .init(init_lifetime)
+        .update(update_accel)
+        .render(ColorOverLifetimeModifier {
+            gradient: color_gradient,
+        })
+        .render(SizeOverLifetimeModifier {
+            gradient: size_gradient,
+            screen_space_size: false,
+        })
+}
+
+/// 里程碑礼花粒子效果生成系统
+/// 
+/// 达到分数里程碑时在落点向上喷出彩色粒子，粒子效果资源只创建一次并缓存复用
+pub fn spawn_milestone_burst_effect(
+    mut commands: Commands,
+    mut effects: ResMut<Assets<EffectAsset>>,
+    mut effect_cache: Local<Option<Handle<EffectAsset>>>,
+    mut milestone_events: EventReader<MilestoneEvent>,
+) {
+    for event in milestone_events.read() {
+        let effect = effect_cache
+            .get_or_insert_with(|| effects.add(milestone_burst_effect_asset()))
+            .clone();
+
+        // 粒子从玩家头顶附近喷出
+        commands.spawn((
+            Name::new("milestone_burst"),
+            ParticleEffectBundle {
+                effect: ParticleEffect::new(effect),
+                transform: Transform::from_translation(event.pos + Vec3::Y * 0.5),
+                ..Default::default()
+            },
+            TimedEffect(Timer::from_seconds(MILESTONE_BURST_LIFETIME + 0.2, TimerMode::Once)),
+        ));
+    }
+}
+
+/// 一次性粒子效果清理系统
+/// 
+/// 计时器结束后销毁落地扬尘、里程碑礼花等粒子效果实体
+pub fn despawn_timed_effects(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut q_effects: Query<(Entity, &mut TimedEffect)>,
+) {
+    for (entity, mut effect) in &mut q_effects {
+        if effect.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// 创建跳跃轨迹共用的网格和分级材质
+pub fn setup_jump_trail_assets(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    trail_color: Res<JumpTrailColor>,
+) {
+    commands.insert_resource(JumpTrailAssets {
+        mesh: meshes.add(Sphere::new(0.05)), // 小球网格
+        materials: (1..=JUMP_TRAIL_FADE_STEPS)
+            .map(|step| {
+                materials.add(StandardMaterial {
+                    base_color: jump_trail_step_color(trail_color.0, step),
+                    alpha_mode: AlphaMode::Blend,
+                    unlit: true,
+                    ..default()
+                })
+            })
+            .collect(),
+    });
+}
+
+/// 同步跳跃轨迹颜色
+/// 
+/// 皮肤变化时将轨迹颜色设置为皮肤颜色，并更新共用的轨迹材质
+pub fn sync_jump_trail_color(
+    player_skin: Res<PlayerSkin>,
+    mut trail_color: ResMut<JumpTrailColor>,
+    trail_assets: Res<JumpTrailAssets>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    if player_skin.is_changed() {
+        trail_color.0 = player_skin.color;
+    }
+    if !trail_color.is_changed() {
+        return;
+    }
+    for (index, handle) in trail_assets.materials.iter().enumerate() {
+        if let Some(material) = materials.get_mut(handle) {
+            material.base_color = jump_trail_step_color(trail_color.0, index + 1);
+        }
+    }
+}
+
+/// 玩家颜色随分数变化时，每得1分色相旋转的角度（度）
+const SCORE_HUE_STEP: f32 = 12.0;
+
+/// 玩家颜色随分数变化时的最低饱和度，保证灰白色皮肤也能看出色相变化
+const SCORE_COLOR_MIN_SATURATION: f32 = 0.6;
+
+/// 根据分数计算玩家颜色
+/// 
+/// 从皮肤颜色出发，分数越高色相旋转越多，每30分转完一圈色环
+/// 
+/// # 参数
+/// - `skin_color`: 皮肤颜色，0分时的颜色
+/// - `score`: 当前分数
+pub fn score_color(skin_color: Color, score: u32) -> Color {
+    if score == 0 {
+        return skin_color;
+    }
+    let hsla = Hsla::from(skin_color);
+    hsla.with_saturation(hsla.saturation.max(SCORE_COLOR_MIN_SATURATION))
+        .rotate_hue(score as f32 * SCORE_HUE_STEP)
+        .into()
+}
+
+/// 玩家颜色随分数变化系统
+/// 
+/// 只在分数、开关或玩家实体变化时写入材质；关闭开关时恢复皮肤颜色，新一局分数归零时自然回到皮肤颜色
+/// 
+/// # 参数
+/// - `score`: 当前分数
+/// - `score_color_progression`: 玩家颜色随分数变化开关
+/// - `player_skin`: 玩家皮肤，提供基础颜色
+/// - `materials`: 材质资源管理器
+/// - `q_player`: 玩家实体查询，获取玩家材质
+pub fn update_player_score_color(
+    score: Res<Score>,
+    score_color_progression: Res<ScoreColorProgression>,
+    player_skin: Res<PlayerSkin>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    q_player: Query<(Ref<Player>, &MeshMaterial3d<StandardMaterial>)>,
+) {
+    let Ok((player, material)) = q_player.get_single() else {
+        return;
+    };
+    if !score.is_changed() && !score_color_progression.is_changed() && !player.is_added() {
+        return;
+    }
+    let Some(material) = materials.get_mut(&material.0) else {
+        return;
+    };
+    material.base_color = if score_color_progression.0 {
+        score_color(player_skin.color, score.0)
+    } else {
+        player_skin.color
+    };
+}
+
+/// 跳跃轨迹生成系统
+/// 
+/// 跳跃过程中每帧在玩家位置生成一个半透明小球，连成跳跃弧线
+/// 
+/// # 参数
+/// - `commands`: 命令系统，用于生成轨迹实体
+/// - `trail_assets`: 跳跃轨迹共用的网格和材质
+/// - `jump_state`: 跳跃状态资源，只在跳跃中生成轨迹
+/// - `q_player`: 玩家实体查询，获取玩家位置
+pub fn spawn_jump_trail(
+    mut commands: Commands,
+    trail_assets: Res<JumpTrailAssets>,
+    jump_state: Res<JumpState>,
+    q_player: Query<&Transform, With<Player>>,
+) {
+    if jump_state.completed {
+        return;
+    }
+    let Ok(player) = q_player.get_single() else {
+        return;
+    };
+    commands.spawn((
+        Mesh3d(trail_assets.mesh.clone()), // 共用的小球网格
+        MeshMaterial3d(trail_assets.material(1.0).clone()), // 最不透明的一级材质
+        Transform::from_translation(player.translation),
+        JumpTrail(Timer::from_seconds(JUMP_TRAIL_LIFETIME, TimerMode::Once)),
+    ));
+}
+
+/// 跳跃轨迹淡出系统
+/// 
+/// 轨迹点随寿命切换到更透明的一级材质，寿命结束后销毁；开始蓄力时立即清除所有轨迹
+/// 
+/// # 参数
+/// - `commands`: 命令系统，用于销毁轨迹实体
+/// - `trail_assets`: 跳跃轨迹共用的分级材质
+/// - `accumulator`: 蓄力状态资源
+/// - `time`: 时间资源
+/// - `q_trail`: 跳跃轨迹查询
+pub fn fade_jump_trail(
+    mut commands: Commands,
+    trail_assets: Res<JumpTrailAssets>,
+    accumulator: Res<Accumulator>,
+    time: Res<Time>,
+    mut q_trail: Query<(Entity, &mut JumpTrail, &mut MeshMaterial3d<StandardMaterial>)>,
+) {
+    for (entity, mut trail, mut material) in &mut q_trail {
+        // 下一次蓄力开始时轨迹必须已经完全消失
+        if trail.0.tick(time.delta()).finished() || accumulator.0.is_some() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        let faded = trail_assets.material(trail.0.fraction_remaining());
+        if material.0 != *faded {
+            material.0 = faded.clone();
+        }
+    }
+}
+
+/// 玩家及其影子实体查询
+type PlayerAndShadowQuery<'w, 's> =
+    Query<'w, 's, Entity, Or<(With<Player>, With<PlayerShadow>)>>;
+
 /// 清理玩家实体系统
 /// 
 /// 在游戏结束或重置时销毁玩家实体
@@ -641,7 +2532,7 @@ pub fn animate_accumulation_particle_effect(
 /// # 参数
 /// - `commands`: 命令系统，用于销毁实体
 /// - `q_player`: 玩家实体查询
-pub fn clear_player(mut commands: Commands, q_player: Query<Entity, With<Player>>) {
+pub fn clear_player(mut commands: Commands, q_player: PlayerAndShadowQuery) {
     for player in &q_player {
         commands.entity(player).despawn();
     }
@@ -667,3 +2558,558 @@ pub fn prepare_jump(time: Res<Time>, mut prepare_timer: ResMut<PrepareJumpTimer>
 pub fn reset_prepare_jump_timer(mut prepare_timer: ResMut<PrepareJumpTimer>) {
     prepare_timer.0.reset();
 }
+
+/// 停止蓄力音效系统
+/// 
+/// 无论因为何种原因结束蓄力，只要蓄力状态为空就销毁所有蓄力音效，避免循环音效残留
+/// 
+/// # 参数
+/// - `commands`: 命令系统，用于销毁音效实体
+/// - `accumulator`: 蓄力状态资源
+/// - `q_accumulation_sound`: 蓄力音效实体查询
+pub fn stop_accumulation_sound(
+    mut commands: Commands,
+    accumulator: Res<Accumulator>,
+    q_accumulation_sound: Query<Entity, With<AccumulationSound>>,
+) {
+    if accumulator.0.is_none() {
+        for entity in &q_accumulation_sound {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// 清除蓄力状态系统
+/// 
+/// 退出游戏进行状态时清除蓄力状态和触摸记录，保证新的一局从干净的状态开始
+/// 
+/// # 参数
+/// - `accumulator`: 蓄力状态资源
+/// - `charge_touch`: 触摸蓄力资源
+pub fn clear_accumulator(mut accumulator: ResMut<Accumulator>, mut charge_touch: ResMut<ChargeTouch>) {
+    accumulator.0 = None;
+    charge_touch.0 = None;
+}
+
+/// 重置重试次数系统
+/// 
+/// 每局开始时根据安全网设置重置剩余重试次数，练习模式中重试次数无限
+/// 
+/// # 参数
+/// - `retries`: 剩余重试次数资源
+/// - `safety_net`: 安全网设置
+/// - `practice_mode`: 练习模式资源
+pub fn reset_retries(
+    mut retries: ResMut<Retries>,
+    safety_net: Res<SafetyNet>,
+    practice_mode: Res<PracticeMode>,
+) {
+    retries.0 = if practice_mode.0 {
+        u32::MAX
+    } else if safety_net.0 {
+        SAFETY_NET_RETRIES
+    } else {
+        0
+    };
+}
+
+/// 重置开局入场动画系统
+/// 
+/// 每局开始时重新播放入场动画，动画进行中重新开始游戏时从头播放
+/// 
+/// # 参数
+/// - `intro_animation`: 开局入场动画计时器资源
+pub fn reset_intro_animation(mut intro_animation: ResMut<IntroAnimation>) {
+    intro_animation.0.reset();
+}
+
+/// 开局入场动画系统
+/// 
+/// 第一个平台从下方升起并由小变大，随后玩家从空中落到平台上；
+/// 状态切换淡入过程中停在动画起点，淡入完成后才开始计时，保证玩家能看到完整动画
+/// 
+/// # 参数
+/// - `time`: 时间资源
+/// - `transition`: 状态切换过渡资源
+/// - `start_platform`: 第一个平台配置，提供平台和玩家的最终位置
+/// - `intro_animation`: 开局入场动画计时器资源
+/// - `q_player`: 玩家变换查询
+/// - `q_platform`: 第一个平台变换查询
+pub fn animate_intro(
+    time: Res<Time>,
+    transition: Res<Transition>,
+    start_platform: Res<StartPlatformConfig>,
+    mut intro_animation: ResMut<IntroAnimation>,
+    mut q_player: Query<&mut Transform, With<Player>>,
+    mut q_platform: Query<&mut Transform, (With<CurrentPlatform>, Without<Player>)>,
+) {
+    if intro_animation.0.finished() {
+        return;
+    }
+    if transition.phase == TransitionPhase::Idle {
+        intro_animation.0.tick(time.delta());
+    }
+    let t = intro_animation.0.fraction();
+
+    // 平台升起阶段的进度，先快后慢地停在最终位置
+    let rise = (t / INTRO_RISE_FRACTION).min(1.0);
+    let rise_eased = 1.0 - (1.0 - rise).powi(2);
+    for mut platform in &mut q_platform {
+        platform.translation.y = start_platform.pos.y - INTRO_RISE_DEPTH * (1.0 - rise_eased);
+        // 只缩放水平方向，高度缩放由平台蓄力效果使用
+        let footprint = rise_eased.max(0.01);
+        platform.scale.x = footprint;
+        platform.scale.z = footprint;
+    }
+
+    // 玩家下落阶段的进度，先慢后快地落到平台上
+    let drop = ((t - INTRO_RISE_FRACTION) / (1.0 - INTRO_RISE_FRACTION)).clamp(0.0, 1.0);
+    let player_pos = start_platform.player_pos();
+    for mut player in &mut q_player {
+        player.translation.y = player_pos.y + INTRO_DROP_HEIGHT * (1.0 - drop * drop);
+    }
+}
+
+/// 重置开局倒计时系统
+/// 
+/// 每局开始时重新开始倒计时
+/// 
+/// # 参数
+/// - `countdown_timer`: 开局倒计时计时器资源
+pub fn reset_countdown_timer(mut countdown_timer: ResMut<CountdownTimer>) {
+    countdown_timer.0.reset();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platform::PLATFORM_BASE_Y;
+    use crate::input::InputBindings;
+    use crate::settings::ComboChains;
+    use crate::ui::{ChainSpeed, RunStats, Score, ScoreUpEvent};
+    use std::time::Duration;
+
+    /// 站在`platform_pos`平台上时的玩家位置
+    fn standing_on(platform_pos: Vec3) -> Vec3 {
+        platform_pos.with_y(standing_y(platform_pos.y, 1.0, 1.0))
+    }
+
+    /// 用默认手感从`current`跳`distance`距离到`next`时的落点
+    fn jump_towards(current: Vec3, next: Vec3, distance: f32) -> Vec3 {
+        let tuning = PhysicsTuning::default();
+        let max_charge = MaxCharge::default();
+        let charge_secs = tuning.charge_for_distance(distance, &max_charge);
+        landing_position(standing_on(current), current, next, charge_secs, &max_charge, &tuning)
+    }
+
+    #[test]
+    fn direction_towards_negative_x_platform() {
+        let current = Vec3::new(0.0, PLATFORM_BASE_Y, 0.0);
+        let next = Vec3::new(-3.0, PLATFORM_BASE_Y, 0.0);
+        assert_eq!(horizontal_direction(current, next), Vec3::NEG_X);
+    }
+
+    #[test]
+    fn direction_towards_positive_z_platform() {
+        let current = Vec3::new(0.0, PLATFORM_BASE_Y, 0.0);
+        let next = Vec3::new(0.0, PLATFORM_BASE_Y, 3.0);
+        assert_eq!(horizontal_direction(current, next), Vec3::Z);
+    }
+
+    #[test]
+    fn direction_with_negative_coordinates() {
+        // 两个平台都在负坐标区域，X差值为负时仍判断为沿X轴跳跃
+        let current = Vec3::new(-5.0, PLATFORM_BASE_Y, -2.0);
+        let next = Vec3::new(-8.5, PLATFORM_BASE_Y, -2.0);
+        assert_eq!(horizontal_direction(current, next), Vec3::NEG_X);
+        // 高度不同不影响水平方向
+        let higher = Vec3::new(-5.0, PLATFORM_BASE_Y + 0.4, 1.0);
+        assert_eq!(horizontal_direction(current, higher), Vec3::Z);
+    }
+
+    #[test]
+    fn landing_on_negative_x_platform() {
+        let current = Vec3::new(0.0, PLATFORM_BASE_Y, 0.0);
+        let next = Vec3::new(-3.0, PLATFORM_BASE_Y, 0.0);
+        let landing_pos = jump_towards(current, next, 3.0);
+        assert!(landing_pos.xz().distance(next.xz()) < 1e-4);
+        let outcome = landing_outcome(
+            &PlatformShape::Box,
+            &Transform::from_translation(current),
+            &PlatformShape::Box,
+            &Transform::from_translation(next),
+            landing_pos,
+            1.0,
+        );
+        assert_eq!(outcome, LandingOutcome::NextPlatform { perfect: true, near_miss: false });
+    }
+
+    #[test]
+    fn landing_on_positive_z_platform() {
+        let current = Vec3::new(0.0, PLATFORM_BASE_Y, 0.0);
+        let next = Vec3::new(0.0, PLATFORM_BASE_Y, 3.0);
+        let landing_pos = jump_towards(current, next, 3.0);
+        assert!(landing_pos.xz().distance(next.xz()) < 1e-4);
+        let outcome = landing_outcome(
+            &PlatformShape::Cylinder,
+            &Transform::from_translation(current),
+            &PlatformShape::Cylinder,
+            &Transform::from_translation(next),
+            landing_pos,
+            1.0,
+        );
+        assert_eq!(outcome, LandingOutcome::NextPlatform { perfect: true, near_miss: false });
+    }
+
+    /// 已经走完的计时器，用于跳过开局准备、倒计时和入场动画
+    fn finished_timer(secs: f32) -> Timer {
+        let mut timer = Timer::from_seconds(secs, TimerMode::Once);
+        timer.tick(timer.duration());
+        timer
+    }
+
+    /// 只运行`player_jump`的最小App，不加载DefaultPlugins
+    /// 
+    /// 玩家站在`current`处的方形当前平台上，`next`为按生成顺序排列的候选平台
+    fn jump_app(current: Vec3, next: &[Vec3]) -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_event::<GameEvent>()
+            .add_event::<ScoreUpEvent>()
+            .init_resource::<InputBindings>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<ButtonInput<MouseButton>>()
+            .init_resource::<Touches>()
+            .init_resource::<ChargeTouch>()
+            .init_resource::<DemoJump>()
+            .init_resource::<ReplayPlayback>()
+            .init_resource::<ReplayLog>()
+            .insert_resource(Score(0))
+            .init_resource::<Combo>()
+            .init_resource::<RunStats>()
+            .init_resource::<ChainSpeed>()
+            .insert_resource(ComboChains(false))
+            .insert_resource(Accumulator(None))
+            .init_resource::<MaxCharge>()
+            .init_resource::<PhysicsTuning>()
+            .init_resource::<LandingTolerance>()
+            .init_resource::<ShortTapBehavior>()
+            .init_resource::<JumpState>()
+            .init_resource::<FallState>()
+            .insert_resource(PrepareJumpTimer(finished_timer(0.5)))
+            .insert_resource(CountdownTimer(finished_timer(COUNTDOWN_SECS + 0.5)))
+            .insert_resource(IntroAnimation(finished_timer(INTRO_SECS)))
+            .init_resource::<WidePerfectBoost>()
+            .add_systems(Update, player_jump);
+        app.world_mut()
+            .spawn((Player, Transform::from_translation(standing_on(current))));
+        app.world_mut()
+            .spawn((PlatformShape::Box, Transform::from_translation(current), CurrentPlatform));
+        for (index, pos) in next.iter().enumerate() {
+            app.world_mut().spawn((
+                PlatformShape::Box,
+                Transform::from_translation(*pos),
+                NextPlatform,
+                SpawnIndex(index as u32),
+            ));
+        }
+        app
+    }
+
+    /// 模拟一次蓄力后松开：蓄力开始时间设在跳出`distance`所需的蓄力时长之前，演示松开不受快速点按设置影响
+    fn charge_and_release(app: &mut App, distance: f32) {
+        let world = app.world_mut();
+        let charge_secs = world
+            .resource::<PhysicsTuning>()
+            .charge_for_distance(distance, world.resource::<MaxCharge>());
+        world.resource_mut::<Accumulator>().0 =
+            Instant::now().checked_sub(Duration::from_secs_f32(charge_secs));
+        world.resource_mut::<DemoJump>().release = true;
+        app.update();
+    }
+
+    /// 只运行`compensate_charge_stall`的最小App，真实时间由测试手动推进
+    fn stall_app(charge_start: Instant) -> App {
+        let mut app = App::new();
+        let mut real_time = Time::<Real>::new(charge_start);
+        real_time.update_with_instant(charge_start);
+        app.insert_resource(real_time)
+            .insert_resource(Accumulator(Some(charge_start)))
+            .add_systems(Update, compensate_charge_stall);
+        app
+    }
+
+    /// 推进一帧真实时间并运行一次系统，返回之后的蓄力开始时刻
+    fn step_real_time(app: &mut App, delta: Duration) -> Instant {
+        app.world_mut()
+            .resource_mut::<Time<Real>>()
+            .update_with_duration(delta);
+        app.update();
+        app.world().resource::<Accumulator>().0.unwrap()
+    }
+
+    #[test]
+    fn steady_low_frame_rate_does_not_shorten_charge() {
+        let charge_start = Instant::now();
+        let mut app = stall_app(charge_start);
+        // 20 FPS持续按住一秒，每帧都超过动画截断时长，但都不是卡顿
+        for _ in 0..20 {
+            assert_eq!(step_real_time(&mut app, Duration::from_millis(50)), charge_start);
+        }
+    }
+
+    #[test]
+    fn stalled_frame_skips_only_the_excess() {
+        let charge_start = Instant::now();
+        let mut app = stall_app(charge_start);
+        let start = step_real_time(&mut app, Duration::from_secs_f32(1.0));
+        let skipped = start - charge_start;
+        let expected = Duration::from_secs_f32(1.0 - CHARGE_STALL_THRESHOLD_SECS);
+        assert!(skipped.abs_diff(expected) < Duration::from_millis(1));
+    }
+
+    /// 以`fps`的固定帧率逐帧运行`animate_jump`直到跳跃完成
+    ///
+    /// 返回跳跃终点、跳跃途中每一帧的玩家姿态和跳跃完成后的玩家姿态
+    fn run_jump_animation(fps: u32) -> (Vec3, Vec<Transform>, Transform) {
+        let current = Vec3::new(0.0, PLATFORM_BASE_Y, 0.0);
+        let next = Vec3::new(3.0, PLATFORM_BASE_Y + 0.4, 0.0);
+        let tuning = PhysicsTuning::default();
+        let duration = tuning.jump_duration(tuning.charge_for_distance(3.0, &MaxCharge::default()));
+
+        let mut app = App::new();
+        app.add_event::<GameEvent>()
+            .insert_resource(Time::<()>::default())
+            .init_resource::<PerfectFlash>()
+            .init_resource::<Combo>()
+            .insert_resource(tuning)
+            .insert_resource(FaceJumpDirection(true))
+            .init_resource::<JumpState>()
+            .init_resource::<FallState>()
+            .add_systems(Update, animate_jump);
+        let start_pos = standing_on(current);
+        let player = app
+            .world_mut()
+            .spawn((Player, Transform::from_translation(start_pos)))
+            .id();
+        app.world_mut().resource_mut::<JumpState>().animate_jump(
+            start_pos,
+            jump_towards(current, next, 3.0),
+            duration,
+        );
+
+        let mut path = Vec::new();
+        while !app.world().resource::<JumpState>().completed {
+            app.world_mut()
+                .resource_mut::<Time>()
+                .advance_by(Duration::from_secs_f64(1.0 / fps as f64));
+            app.update();
+            // 跳跃完成的那一帧由累加时长的浮点误差决定，只记录跳跃途中的姿态
+            if !app.world().resource::<JumpState>().completed {
+                path.push(*app.world().get::<Transform>(player).unwrap());
+            }
+        }
+        let end_pos = app.world().resource::<JumpState>().end_pos;
+        (end_pos, path, *app.world().get::<Transform>(player).unwrap())
+    }
+
+    /// 比较两个帧率下跳跃途中同一时刻的玩家姿态，只比较两个帧率都有画面的时刻
+    ///
+    /// 同一时刻的跳跃姿态相同，只有逐帧累加时长带来的浮点误差
+    fn assert_same_jump_path(fps: u32, path: &[Transform], reference_fps: u32, reference: &[Transform]) {
+        let mut compared = 0;
+        for (frame, pose) in path.iter().enumerate() {
+            // 第frame+1帧的时刻在参考帧率下不是整数帧时跳过
+            let reference_frames = (frame as u32 + 1) * reference_fps;
+            if !reference_frames.is_multiple_of(fps) {
+                continue;
+            }
+            let Some(reference_pose) = reference.get((reference_frames / fps) as usize - 1) else {
+                continue;
+            };
+            assert!(pose.translation.distance(reference_pose.translation) < 1e-4);
+            assert!(pose.rotation.abs_diff_eq(reference_pose.rotation, 1e-4));
+            compared += 1;
+        }
+        assert!(compared > 0);
+    }
+
+    #[test]
+    fn jump_animation_matches_across_frame_rates() {
+        let (end_144, path_144, final_144) = run_jump_animation(144);
+        assert_eq!(final_144.translation, end_144);
+        // 低于30帧时每帧仍按真实时长推进，落点和途中姿态与高帧率相同
+        for fps in [15, 30] {
+            let (end, path, final_pose) = run_jump_animation(fps);
+            assert_eq!(end, end_144);
+            assert_eq!(final_pose, final_144);
+            assert_same_jump_path(fps, &path, 144, &path_144);
+        }
+    }
+
+    #[test]
+    fn landing_position_scales_with_charge() {
+        let tuning = PhysicsTuning::default();
+        let max_charge = MaxCharge::default();
+        let current = Vec3::new(0.0, PLATFORM_BASE_Y, 0.0);
+        let next = Vec3::new(3.0, PLATFORM_BASE_Y + 0.4, 0.0);
+        let player = standing_on(current);
+        // 不蓄力时原地落下，高度为下一个平台顶面上的站立高度
+        let still = landing_position(player, current, next, 0.0, &max_charge, &tuning);
+        assert_eq!(still.xz(), player.xz());
+        assert_eq!(still.y, standing_y(next.y, 1.0, 1.0));
+        // 超过最大蓄力时长的部分不再生效
+        let full = landing_position(player, current, next, max_charge.0 * 2.0, &max_charge, &tuning);
+        let max_distance = tuning.max_jump_distance(&max_charge);
+        assert!((full.x - max_distance).abs() < 1e-4);
+        assert_eq!(full.z, 0.0);
+    }
+
+    #[test]
+    fn landing_outcome_for_each_region() {
+        let current = Transform::from_translation(Vec3::new(0.0, PLATFORM_BASE_Y, 0.0));
+        let next = Transform::from_translation(Vec3::new(4.0, PLATFORM_BASE_Y, 0.0));
+        let outcome = |x: f32| {
+            landing_outcome(
+                &PlatformShape::Box,
+                &current,
+                &PlatformShape::Box,
+                &next,
+                Vec3::new(x, 0.0, 0.0),
+                1.0,
+            )
+        };
+        assert_eq!(outcome(4.0), LandingOutcome::NextPlatform { perfect: true, near_miss: false });
+        assert_eq!(outcome(4.7), LandingOutcome::NextPlatform { perfect: false, near_miss: true });
+        assert_eq!(outcome(0.4), LandingOutcome::CurrentPlatform);
+        assert_eq!(outcome(0.85), LandingOutcome::TouchedCurrentPlatform);
+        assert_eq!(outcome(3.15), LandingOutcome::TouchedNextPlatform { overshoot: false });
+        assert_eq!(outcome(4.85), LandingOutcome::TouchedNextPlatform { overshoot: true });
+        assert_eq!(outcome(2.0), LandingOutcome::Missed);
+    }
+
+    #[test]
+    fn jump_lands_on_next_platform() {
+        let current = Vec3::new(0.0, PLATFORM_BASE_Y, 0.0);
+        let next = Vec3::new(3.0, PLATFORM_BASE_Y, 0.0);
+        let mut app = jump_app(current, &[next]);
+        charge_and_release(&mut app, 3.0);
+
+        let world = app.world();
+        let jump_state = world.resource::<JumpState>();
+        assert!(!jump_state.completed);
+        assert!(!jump_state.falled);
+        assert!(jump_state.end_pos.xz().distance(next.xz()) < 0.1);
+        assert_eq!(world.resource::<Score>().0, 1);
+        assert!(world.resource::<FallState>().completed);
+        assert!(world.resource::<Accumulator>().0.is_none());
+    }
+
+    #[test]
+    fn jump_lands_on_current_platform() {
+        let current = Vec3::new(0.0, PLATFORM_BASE_Y, 0.0);
+        let mut app = jump_app(current, &[Vec3::new(3.0, PLATFORM_BASE_Y, 0.0)]);
+        charge_and_release(&mut app, 0.4);
+
+        let world = app.world();
+        let jump_state = world.resource::<JumpState>();
+        assert!(!jump_state.completed);
+        assert!(!jump_state.falled);
+        assert_eq!(jump_state.end_pos.y, standing_y(current.y, 1.0, 1.0));
+        assert_eq!(world.resource::<Score>().0, 0);
+        assert!(world.resource::<FallState>().completed);
+    }
+
+    #[test]
+    fn jump_misses_both_platforms() {
+        let current = Vec3::new(0.0, PLATFORM_BASE_Y, 0.0);
+        let mut app = jump_app(current, &[Vec3::new(4.0, PLATFORM_BASE_Y, 0.0)]);
+        charge_and_release(&mut app, 2.0);
+
+        let world = app.world();
+        assert!(world.resource::<JumpState>().falled);
+        assert_eq!(world.resource::<Score>().0, 0);
+        let fall_state = world.resource::<FallState>();
+        assert!(!fall_state.completed);
+        assert!(matches!(fall_state.fall_type, FallType::Straight));
+    }
+
+    /// 从方形平台`outward`一侧的边缘摔落时，倾倒后的玩家朝上方向偏向远离平台中心的一侧
+    fn assert_tilts_away_from_box_edge(outward: Vec3) {
+        let platform_pos = Vec3::new(2.0, PLATFORM_BASE_Y, -1.0);
+        // 落点在方形平台边缘外、玩家接触半径以内
+        let landing_pos = standing_on(platform_pos) + outward * 0.85;
+        let outcome = landing_outcome(
+            &PlatformShape::Box,
+            &Transform::from_translation(Vec3::new(-10.0, PLATFORM_BASE_Y, 10.0)),
+            &PlatformShape::Box,
+            &Transform::from_translation(platform_pos),
+            landing_pos,
+            1.0,
+        );
+        assert!(matches!(outcome, LandingOutcome::TouchedNextPlatform { .. }));
+
+        let axis = edge_tilt_axis(platform_pos, landing_pos);
+        assert!(axis.dot(outward).abs() < 1e-5);
+        let tilted_up = Quat::from_axis_angle(axis, FRAC_PI_2 / 2.0) * Vec3::Y;
+        assert!(tilted_up.dot(outward) > 0.5);
+    }
+
+    #[test]
+    fn tilt_away_from_positive_x_edge() {
+        assert_tilts_away_from_box_edge(Vec3::X);
+    }
+
+    #[test]
+    fn tilt_away_from_negative_x_edge() {
+        assert_tilts_away_from_box_edge(Vec3::NEG_X);
+    }
+
+    #[test]
+    fn tilt_away_from_positive_z_edge() {
+        assert_tilts_away_from_box_edge(Vec3::Z);
+    }
+
+    #[test]
+    fn tilt_away_from_negative_z_edge() {
+        assert_tilts_away_from_box_edge(Vec3::NEG_Z);
+    }
+
+    #[test]
+    fn forked_jump_promotes_landed_candidate_and_despawns_other() {
+        let current = Vec3::new(0.0, PLATFORM_BASE_Y, 0.0);
+        let mut app = jump_app(
+            current,
+            &[Vec3::new(3.0, PLATFORM_BASE_Y, 0.0), Vec3::new(0.0, PLATFORM_BASE_Y, 3.0)],
+        );
+        let world = app.world_mut();
+        let candidates: Vec<(Entity, u32)> = world
+            .query::<(Entity, &SpawnIndex)>()
+            .iter(world)
+            .map(|(entity, spawn_index)| (entity, spawn_index.0))
+            .collect();
+        let find = |index: u32| candidates.iter().find(|(_, i)| *i == index).unwrap().0;
+        let (skipped, aimed) = (find(0), find(1));
+        let old_current = world
+            .query_filtered::<Entity, With<CurrentPlatform>>()
+            .single(world);
+        // 瞄准生成较晚的候选平台，跳跃方向沿+Z而不是默认的第一个候选
+        world.entity_mut(aimed).insert(AimedPlatform);
+
+        charge_and_release(&mut app, 3.0);
+
+        let world = app.world();
+        assert_eq!(world.resource::<Score>().0, 1);
+        assert!(!world.resource::<JumpState>().falled);
+        // 落上的候选平台成为当前平台，移除候选和瞄准标记
+        let landed = world.entity(aimed);
+        assert!(landed.contains::<CurrentPlatform>());
+        assert!(!landed.contains::<NextPlatform>());
+        assert!(!landed.contains::<AimedPlatform>());
+        // 没选的候选平台被销毁，原来的当前平台标记为已跳过
+        assert!(world.get_entity(skipped).is_err());
+        let passed = world.entity(old_current);
+        assert!(!passed.contains::<CurrentPlatform>());
+        assert!(passed.contains::<PassedPlatform>());
+    }
+}
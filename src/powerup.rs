@@ -0,0 +1,222 @@
+// 导入Bevy的主要组件
+use bevy::prelude::*;
+// 导入哈希表，用于按道具种类缓存网格和材质
+use bevy::utils::HashMap;
+// 导入随机数生成器trait
+use rand::Rng;
+
+// 导入平台模块中的平台标记、随机数生成器和完美落地容差
+use crate::platform::{CurrentPlatform, GameRng, NextPlatform, PERFECT_LANDING_TOLERANCE};
+// 导入玩家模块中的跳跃状态和重试次数
+use crate::player::{JumpState, Retries};
+
+/// 道具种类
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PowerUpKind {
+    /// 额外获得一次摔落后回到当前平台重试的机会
+    ExtraRetry,
+    /// 之后几次落地的完美落地判定范围变大
+    WidePerfect,
+}
+
+impl PowerUpKind {
+    /// 道具颜色，不同种类使用不同颜色区分
+    fn color(self) -> Color {
+        match self {
+            PowerUpKind::ExtraRetry => Color::srgb(0.3, 0.9, 0.4),
+            PowerUpKind::WidePerfect => Color::srgb(0.3, 0.8, 1.0),
+        }
+    }
+
+    /// 道具网格，额外重试为小球，放宽完美判定为圆环
+    fn mesh(self) -> Mesh {
+        match self {
+            PowerUpKind::ExtraRetry => Sphere::new(0.15).mesh().uv(16, 8),
+            PowerUpKind::WidePerfect => Torus::new(0.08, 0.2).mesh().build(),
+        }
+    }
+}
+
+/// 道具资源池
+///
+/// 每种道具共用一个网格和一个材质，避免每生成一个道具就新建网格和材质
+#[derive(Debug, Default, Resource)]
+pub struct PowerUpAssetPool {
+    /// 按道具种类缓存的网格和材质
+    assets: HashMap<PowerUpKind, (Handle<Mesh>, Handle<StandardMaterial>)>,
+}
+
+impl PowerUpAssetPool {
+    /// 获取道具种类对应的网格和材质，首次使用时创建
+    pub fn get(
+        &mut self,
+        kind: PowerUpKind,
+        meshes: &mut Assets<Mesh>,
+        materials: &mut Assets<StandardMaterial>,
+    ) -> (Handle<Mesh>, Handle<StandardMaterial>) {
+        self.assets
+            .entry(kind)
+            .or_insert_with(|| {
+                let material = StandardMaterial {
+                    base_color: kind.color(),
+                    emissive: LinearRgba::from(kind.color()) * 0.5, // 微弱自发光，在各种平台颜色上都醒目
+                    ..default()
+                };
+                (meshes.add(kind.mesh()), materials.add(material))
+            })
+            .clone()
+    }
+}
+
+/// 道具配置资源
+#[derive(Debug, Resource)]
+pub struct PowerUpConfig {
+    /// 每个新平台上出现道具的概率
+    pub spawn_chance: f32,
+    /// 可以出现的道具种类，等概率随机选择，为空时不生成道具
+    pub kinds: Vec<PowerUpKind>,
+    /// 放宽完美判定持续的成功落地次数
+    pub wide_perfect_landings: u32,
+    /// 放宽后的完美落地容差
+    pub wide_perfect_tolerance: f32,
+}
+
+impl Default for PowerUpConfig {
+    fn default() -> Self {
+        Self {
+            spawn_chance: 0.1,
+            kinds: vec![PowerUpKind::ExtraRetry, PowerUpKind::WidePerfect],
+            wide_perfect_landings: 3,
+            wide_perfect_tolerance: PERFECT_LANDING_TOLERANCE * 2.0,
+        }
+    }
+}
+
+/// 道具组件，道具是平台的子实体，平台销毁时一起销毁
+#[derive(Debug, Component)]
+pub struct PowerUp {
+    /// 道具种类
+    pub kind: PowerUpKind,
+    /// 漂浮动画已进行的时长（秒）
+    pub elapsed: f32,
+}
+
+/// 放宽完美判定的剩余效果资源
+#[derive(Debug, Default, Resource)]
+pub struct WidePerfectBoost {
+    /// 剩余的成功落地次数，为0时效果结束
+    pub landings_left: u32,
+    /// 放宽后的完美落地容差
+    pub tolerance: f32,
+}
+
+impl WidePerfectBoost {
+    /// 成功落在下一个平台上时调用，消耗一次效果并判断落点是否在放宽后的完美范围内
+    ///
+    /// # 参数
+    /// - `platform_pos`: 平台的位置坐标
+    /// - `landing_pos`: 玩家的落地点坐标
+    ///
+    /// # 返回值
+    /// 效果生效且落点在放宽后的完美范围内时返回true
+    pub fn consume(&mut self, platform_pos: Vec3, landing_pos: Vec3) -> bool {
+        if self.landings_left == 0 {
+            return false;
+        }
+        self.landings_left -= 1;
+        (landing_pos - platform_pos).xz().length() < self.tolerance
+    }
+}
+
+/// 道具在平台顶面上方漂浮的高度
+const POWER_UP_FLOAT_HEIGHT: f32 = 0.9;
+/// 道具上下漂浮的幅度
+const POWER_UP_BOB_AMPLITUDE: f32 = 0.1;
+/// 道具上下漂浮的角速度（弧度/秒）
+const POWER_UP_BOB_SPEED: f32 = 3.0;
+/// 道具自转的角速度（弧度/秒）
+const POWER_UP_SPIN_SPEED: f32 = 2.0;
+
+/// 道具生成系统
+///
+/// 新生成的下一个平台按概率在上方放置一个道具，种类从配置中随机选择；
+/// 使用游戏随机数生成器，相同种子的道具位置和种类相同
+pub fn spawn_power_ups(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut pool: ResMut<PowerUpAssetPool>,
+    config: Res<PowerUpConfig>,
+    mut game_rng: ResMut<GameRng>,
+    q_new_platforms: Query<Entity, Added<NextPlatform>>,
+) {
+    for platform in &q_new_platforms {
+        if config.kinds.is_empty()
+            || !game_rng.0.gen_bool(config.spawn_chance.clamp(0.0, 1.0) as f64)
+        {
+            continue;
+        }
+        let kind = config.kinds[game_rng.0.gen_range(0..config.kinds.len())];
+        let (mesh, material) = pool.get(kind, &mut meshes, &mut materials);
+        commands.entity(platform).with_child((
+            Mesh3d(mesh),
+            MeshMaterial3d(material),
+            Transform::from_xyz(0.0, POWER_UP_FLOAT_HEIGHT, 0.0),
+            PowerUp { kind, elapsed: 0.0 },
+        ));
+    }
+}
+
+/// 道具漂浮动画系统，道具在平台上方上下漂浮并自转
+pub fn animate_power_ups(time: Res<Time>, mut q_power_ups: Query<(&mut Transform, &mut PowerUp)>) {
+    for (mut transform, mut power_up) in &mut q_power_ups {
+        power_up.elapsed += time.delta_secs();
+        transform.translation.y = POWER_UP_FLOAT_HEIGHT
+            + POWER_UP_BOB_AMPLITUDE * (power_up.elapsed * POWER_UP_BOB_SPEED).sin();
+        transform.rotation = Quat::from_rotation_y(power_up.elapsed * POWER_UP_SPIN_SPEED);
+    }
+}
+
+/// 道具拾取系统
+///
+/// 成功落在带有道具的平台上、跳跃动画完成后拾取道具并生效
+pub fn collect_power_ups(
+    mut commands: Commands,
+    jump_state: Res<JumpState>,
+    config: Res<PowerUpConfig>,
+    mut retries: ResMut<Retries>,
+    mut wide_perfect: ResMut<WidePerfectBoost>,
+    q_power_ups: Query<(Entity, &PowerUp, &Parent)>,
+    q_current_platform: Query<Entity, With<CurrentPlatform>>,
+) {
+    if !jump_state.completed || jump_state.falled {
+        return;
+    }
+    for (entity, power_up, parent) in &q_power_ups {
+        if !q_current_platform.contains(parent.get()) {
+            continue;
+        }
+        match power_up.kind {
+            PowerUpKind::ExtraRetry => {
+                retries.0 = retries.0.saturating_add(1);
+                info!("Power-up collected: extra retry, {} left", retries.0);
+            }
+            PowerUpKind::WidePerfect => {
+                wide_perfect.landings_left = config.wide_perfect_landings;
+                wide_perfect.tolerance = config.wide_perfect_tolerance;
+                info!(
+                    "Power-up collected: wide perfect zone for {} landings",
+                    wide_perfect.landings_left
+                );
+            }
+        }
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// 重置道具效果
+///
+/// 进入游戏时调用，上一局未用完的效果不带入新的一局
+pub fn reset_power_ups(mut wide_perfect: ResMut<WidePerfectBoost>) {
+    *wide_perfect = WidePerfectBoost::default();
+}
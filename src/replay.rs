@@ -0,0 +1,294 @@
+// 导入Bevy的主要组件
+use bevy::prelude::*;
+// 导入时间戳功能，用于按录像中的蓄力时长设置蓄力开始时刻
+use bevy::utils::Instant;
+// 导入时长类型
+use std::time::Duration;
+
+// 导入平台模块中的游戏模式
+use crate::platform::GameMode;
+// 导入玩家模块中的蓄力、跳跃和摔落相关资源
+use crate::player::{Accumulator, FallState, JumpGate, JumpState};
+// 导入UI模块中的游戏状态切换和按钮交互查询
+use crate::ui::{ButtonInteractions, GameState, PracticeMode, StateTransition};
+
+/// 录像文件路径，与存档文件放在同一目录
+const REPLAY_FILE_PATH: &str = "replay.txt";
+
+/// 录像中的一次跳跃
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReplayJump {
+    /// 松开蓄力的时刻，从本局开始计算的游戏时间（秒），暂停期间不计时
+    pub time: f32,
+    /// 蓄力时长（秒）
+    pub charge_secs: f32,
+    /// 瞄准的下一个平台在候选平台中的序号（按生成顺序），没有分岔路径时为0
+    pub target: usize,
+}
+
+/// 录像资源，记录本局的种子和每次跳跃的蓄力时长
+///
+/// 游戏进行中每次松开蓄力时追加一条记录，游戏结束后可以写入录像文件
+#[derive(Debug, Clone, Default, Resource)]
+pub struct ReplayLog {
+    /// 本局平台序列的种子
+    pub seed: u64,
+    /// 本局是否开启分岔路径，影响平台序列
+    pub forked_paths: bool,
+    /// 本局开始时的游戏时间（秒），只在运行时使用，不写入文件
+    pub start_secs: f32,
+    /// 按时间顺序排列的跳跃记录
+    pub jumps: Vec<ReplayJump>,
+}
+
+impl ReplayLog {
+    /// 从本局开始到现在经过的游戏时间（秒）
+    pub fn elapsed(&self, time: &Time) -> f32 {
+        time.elapsed_secs() - self.start_secs
+    }
+
+    /// 记录一次跳跃
+    ///
+    /// # 参数
+    /// - `time`: 游戏时间，用于计算松开蓄力的时刻
+    /// - `charge_secs`: 蓄力时长（秒）
+    /// - `target`: 瞄准的下一个平台在候选平台中的序号
+    pub fn record(&mut self, time: &Time, charge_secs: f32, target: usize) {
+        let jump = ReplayJump {
+            time: self.elapsed(time),
+            charge_secs,
+            target,
+        };
+        self.jumps.push(jump);
+    }
+
+    /// 转换为录像文件内容
+    ///
+    /// 第一行为种子，开启分岔路径时第二行为`forked`，之后每行一次跳跃：松开时刻和蓄力时长，以空格分隔；
+    /// 分岔路径时跳向第一个候选以外的平台，再追加瞄准的平台序号
+    pub fn to_text(&self) -> String {
+        let mut text = format!("seed {}\n", self.seed);
+        if self.forked_paths {
+            text += "forked\n";
+        }
+        for jump in &self.jumps {
+            text += &format!("{:.4} {:.4}", jump.time, jump.charge_secs);
+            if jump.target > 0 {
+                text += &format!(" {}", jump.target);
+            }
+            text += "\n";
+        }
+        text
+    }
+
+    /// 解析录像文件内容
+    ///
+    /// # 返回值
+    /// 格式不正确时返回`None`
+    pub fn from_text(text: &str) -> Option<Self> {
+        let mut lines = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .peekable();
+        let seed = lines.next()?.strip_prefix("seed ")?.trim().parse().ok()?;
+        // 没有分岔路径标记的旧录像按关闭分岔路径处理
+        let forked_paths = lines.next_if_eq(&"forked").is_some();
+        let jumps = lines
+            .map(|line| {
+                let mut fields = line.split_whitespace();
+                let time = fields.next()?.parse().ok()?;
+                let charge_secs = fields.next()?.parse().ok()?;
+                // 没有瞄准序号的旧录像跳向第一个候选平台
+                let target = match fields.next() {
+                    Some(target) => target.parse().ok()?,
+                    None => 0,
+                };
+                Some(ReplayJump {
+                    time,
+                    charge_secs,
+                    target,
+                })
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(Self {
+            seed,
+            forked_paths,
+            start_secs: 0.0,
+            jumps,
+        })
+    }
+
+    /// 从录像文件读取录像，文件不存在或格式错误时返回`None`
+    pub fn load() -> Option<Self> {
+        if cfg!(target_arch = "wasm32") {
+            return None;
+        }
+        let text = std::fs::read_to_string(REPLAY_FILE_PATH).ok()?;
+        let replay = Self::from_text(&text);
+        if replay.is_none() {
+            warn!("Failed to parse replay file");
+        }
+        replay
+    }
+
+    /// 将录像写入录像文件
+    pub fn save(&self) {
+        if cfg!(target_arch = "wasm32") {
+            return;
+        }
+        match std::fs::write(REPLAY_FILE_PATH, self.to_text()) {
+            Ok(()) => info!("Replay saved with {} jumps", self.jumps.len()),
+            Err(err) => warn!("Failed to write replay file: {}", err),
+        }
+    }
+}
+
+/// 录像回放状态资源
+///
+/// 回放时使用录像中的种子生成平台，并按录像中的时刻和蓄力时长模拟按下和松开蓄力
+#[derive(Debug, Default, Resource)]
+pub struct ReplayPlayback {
+    /// 正在回放的录像，未在回放时为`None`
+    pub replay: Option<ReplayLog>,
+    /// 下一次要回放的跳跃序号
+    pub next: usize,
+    /// 是否正在模拟蓄力
+    pub charging: bool,
+    /// 本帧模拟按下蓄力
+    pub press: bool,
+    /// 本帧模拟松开蓄力
+    pub release: bool,
+    /// 本帧松开蓄力时跳向的候选平台序号
+    pub target: usize,
+}
+
+impl ReplayPlayback {
+    /// 是否正在回放，回放时忽略玩家的蓄力输入
+    pub fn is_active(&self) -> bool {
+        self.replay.is_some()
+    }
+
+    /// 回放录像使用的种子
+    pub fn seed(&self) -> Option<u64> {
+        self.replay.as_ref().map(|replay| replay.seed)
+    }
+
+    /// 回放录像是否开启分岔路径
+    pub fn forked_paths(&self) -> Option<bool> {
+        self.replay.as_ref().map(|replay| replay.forked_paths)
+    }
+}
+
+/// 录像按钮功能组件
+#[derive(Component)]
+pub enum ReplayButtonAction {
+    Save,  // 保存本局录像
+    Watch, // 回放录像文件
+}
+
+/// 开始记录本局录像
+///
+/// 进入游戏时清空跳跃记录并记录开始时刻，种子由`seed_game_rng`写入；
+/// 回放时重新从第一次跳跃开始
+pub fn start_replay_log(
+    time: Res<Time>,
+    mut replay_log: ResMut<ReplayLog>,
+    mut playback: ResMut<ReplayPlayback>,
+) {
+    replay_log.jumps.clear();
+    replay_log.start_secs = time.elapsed_secs();
+    playback.next = 0;
+    playback.charging = false;
+    playback.press = false;
+    playback.release = false;
+}
+
+/// 结束录像回放
+///
+/// 回放结束后返回主菜单或重新开始时恢复正常游戏
+pub fn stop_replay(mut playback: ResMut<ReplayPlayback>) {
+    if playback.is_active() {
+        info!("Replay finished");
+        *playback = ReplayPlayback::default();
+    }
+}
+
+/// 驱动录像回放
+///
+/// 到达录像中按下蓄力的时刻时模拟按下，到达松开时刻时把蓄力开始时刻设置为录像中的蓄力时长之前，
+/// 再模拟松开，跳跃距离与录制时完全相同；蓄力和跳跃完全复用`player_jump`的逻辑
+pub fn drive_replay(
+    time: Res<Time>,
+    replay_log: Res<ReplayLog>,
+    mut playback: ResMut<ReplayPlayback>,
+    mut accumulator: ResMut<Accumulator>,
+    jump_gate: JumpGate,
+    jump_state: Res<JumpState>,
+    fall_state: Res<FallState>,
+) {
+    let Some(jump) = playback
+        .replay
+        .as_ref()
+        .and_then(|replay| replay.jumps.get(playback.next).copied())
+    else {
+        return;
+    };
+    // 与player_jump一致，只有允许跳跃时才模拟输入
+    if !jump_gate.is_open() || !jump_state.is_settled() || !fall_state.completed {
+        return;
+    }
+
+    let elapsed = replay_log.elapsed(&time);
+    if !playback.charging {
+        if elapsed >= jump.time - jump.charge_secs {
+            playback.press = true;
+            playback.charging = true;
+        }
+    } else if elapsed >= jump.time {
+        accumulator.0 = Instant::now().checked_sub(Duration::from_secs_f32(jump.charge_secs));
+        playback.release = true;
+        playback.target = jump.target;
+        playback.charging = false;
+        playback.next += 1;
+    }
+}
+
+/// 处理录像按钮点击事件
+///
+/// 游戏结束界面保存本局录像，主菜单读取录像文件并开始回放
+pub fn click_replay_button(
+    interaction_query: ButtonInteractions<ReplayButtonAction>,
+    replay_log: Res<ReplayLog>,
+    mut playback: ResMut<ReplayPlayback>,
+    mut game_mode: ResMut<GameMode>,
+    mut practice_mode: ResMut<PracticeMode>,
+    mut state_transition: StateTransition,
+) {
+    for (interaction, replay_button_action) in &interaction_query {
+        // 只有在按钮被按下时处理
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match replay_button_action {
+            ReplayButtonAction::Save => {
+                info!("SaveReplay button clicked");
+                replay_log.save();
+            }
+            ReplayButtonAction::Watch => {
+                info!("WatchReplay button clicked");
+                let Some(replay) = ReplayLog::load() else {
+                    warn!("No replay to watch");
+                    continue;
+                };
+                *playback = ReplayPlayback {
+                    replay: Some(replay),
+                    ..default()
+                };
+                *game_mode = GameMode::Endless; // 回放使用录像中的种子
+                practice_mode.0 = false; // 回放按正式游戏进行
+                state_transition.go(GameState::Playing); // 切换到游戏进行状态
+            }
+        }
+    }
+}
@@ -0,0 +1,1850 @@
+// 导入Bevy的主要组件
+use bevy::prelude::*;
+// 导入系统参数派生宏，用于组合多个设置资源
+use bevy::ecs::system::SystemParam;
+// 导入窗口呈现模式，用于切换垂直同步
+use bevy::window::PresentMode;
+// 导入泛光效果，用于泛光强度设置
+use bevy::core_pipeline::bloom::Bloom;
+// 导入方向光的阴影贴图和级联设置，用于阴影质量设置
+use bevy::pbr::{CascadeShadowConfig, CascadeShadowConfigBuilder, DirectionalLightShadowMap};
+// 导入序列化库，用于读写存档文件
+use serde::{Deserialize, Serialize};
+
+// 导入相机模块中的光照主题
+use crate::camera::LightingTheme;
+// 导入最佳成绩幽灵，随存档一起保存
+use crate::ghost::{BestRunGhost, GhostRun};
+// 导入输入模块中的按键绑定
+use crate::input::{ActionInput, InputAction, InputBindings};
+// 导入玩家模块中的蓄力音效标记、玩家皮肤和物理手感参数
+use crate::player::{AccumulationSound, ChargeCurve, PhysicsTuning, PlayerSkin};
+// 导入UI模块中的最高分资源、背景音乐标记、文字按钮和按钮交互查询
+use crate::ui::{
+    spawn_text_button, BackgroundMusic, ButtonInteractions, DailyHighScore, ForkedHighScore,
+    HighScore, Leaderboard, MenuButtonAction, TimeAttackHighScore, TEXT_BUTTON_COLOR,
+};
+
+/// 存档文件路径，保存在游戏运行目录下
+const SAVE_FILE_PATH: &str = "save.json";
+/// 写入存档时使用的临时文件路径，写入完成后替换存档文件
+const SAVE_TEMP_FILE_PATH: &str = "save.json.tmp";
+
+/// 全局音量资源，取值0.0到1.0
+#[derive(Debug, Resource)]
+pub struct Volume(pub f32);
+
+impl Volume {
+    /// 将全局音量应用到播放设置上
+    ///
+    /// # 参数
+    /// - `settings`: 原始播放设置
+    pub fn apply(&self, settings: PlaybackSettings) -> PlaybackSettings {
+        settings.with_volume(bevy::audio::Volume::new(self.0))
+    }
+}
+
+/// 是否静音，与音量大小无关，按M键切换
+#[derive(Debug, Resource)]
+pub struct AudioMuted(pub bool);
+
+/// 标记右下角静音状态图标的组件
+#[derive(Component)]
+pub struct MuteIndicator;
+
+/// 是否在下一个平台中心显示完美落地标记
+#[derive(Debug, Resource)]
+pub struct ShowTargetMarker(pub bool);
+
+/// 是否在切换游戏状态时使用淡入淡出过渡
+#[derive(Debug, Resource)]
+pub struct FadeTransitions(pub bool);
+
+/// 是否开启安全网，开启后每局有若干次摔落重试机会
+#[derive(Debug, Resource)]
+pub struct SafetyNet(pub bool);
+
+/// 是否已经看过新手引导
+#[derive(Debug, Resource)]
+pub struct SeenTutorial(pub bool);
+
+/// 是否开启过度蓄力取消，开启后保持满蓄力过久会取消本次蓄力
+#[derive(Debug, Resource)]
+pub struct FizzleOnOvercharge(pub bool);
+
+/// 是否使用正交相机
+#[derive(Debug, Resource)]
+pub struct OrthographicCamera(pub bool);
+
+/// 是否在窗口失去焦点时自动暂停游戏
+#[derive(Debug, Resource)]
+pub struct AutoPause(pub bool);
+
+/// 是否让玩家颜色随分数沿色环逐渐变化，关闭时保持皮肤颜色
+#[derive(Debug, Resource)]
+pub struct ScoreColorProgression(pub bool);
+
+/// 是否开启连击加速：连续完美落地时整局节奏逐渐加快，非完美落地或摔落后恢复
+#[derive(Debug, Resource)]
+pub struct ComboChains(pub bool);
+
+/// 是否在摔落时显示逐渐加深的屏幕暗角
+#[derive(Debug, Resource)]
+pub struct FallVignette(pub bool);
+
+/// 是否让下一个平台周期性发光，吸引玩家注意跳跃目标
+#[derive(Debug, Resource)]
+pub struct PulseNextPlatform(pub bool);
+
+/// 是否播放新平台的生成动画，关闭时新平台直接以完整大小出现
+#[derive(Debug, Resource)]
+pub struct PlatformSpawnAnimation(pub bool);
+
+/// 跳跃时角色是否先转向落点方向再翻滚，使用有朝向的皮肤时更自然
+#[derive(Debug, Resource)]
+pub struct FaceJumpDirection(pub bool);
+
+/// 简单模式的落地容差倍率
+const EASY_LANDING_TOLERANCE: f32 = 1.3;
+
+/// 落地容差倍率，落地判定时平台占地面积乘以该倍率，1.0为平台实际大小
+///
+/// 简单模式放大判定范围，落在平台边缘外一点也算成功落地
+#[derive(Debug, Clone, Copy, PartialEq, Resource)]
+pub struct LandingTolerance(pub f32);
+
+impl Default for LandingTolerance {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+impl LandingTolerance {
+    /// 是否为简单模式
+    pub fn is_easy(&self) -> bool {
+        self.0 > 1.0
+    }
+
+    /// 在普通和简单模式之间切换
+    pub fn next(&self) -> Self {
+        if self.is_easy() {
+            Self::default()
+        } else {
+            Self(EASY_LANDING_TOLERANCE)
+        }
+    }
+
+    /// 落地难度名称
+    pub fn name(&self) -> &'static str {
+        if self.is_easy() {
+            "Easy"
+        } else {
+            "Normal"
+        }
+    }
+}
+
+/// 快速点按的处理方式，蓄力时长低于最短蓄力时长就松开时生效
+///
+/// 极短的蓄力也会跳出一小段距离，站在平台边缘时容易直接摔落；
+/// 默认取消这次跳跃，也可以改为原地小跳或保持原来的行为
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Resource)]
+pub enum ShortTapBehavior {
+    Jump,   // 按实际蓄力时长跳跃
+    #[default]
+    Cancel, // 取消这次跳跃，需要重新蓄力
+    Hop,    // 原地小跳，不离开当前平台
+}
+
+/// 可选的快速点按处理方式，按设置界面中的切换顺序排列
+const SHORT_TAP_BEHAVIORS: [ShortTapBehavior; 3] =
+    [ShortTapBehavior::Jump, ShortTapBehavior::Cancel, ShortTapBehavior::Hop];
+
+impl ShortTapBehavior {
+    /// 根据序号获取快速点按处理方式，序号超出范围时使用默认方式
+    pub fn from_index(index: usize) -> Self {
+        SHORT_TAP_BEHAVIORS.get(index).copied().unwrap_or_default()
+    }
+
+    /// 快速点按处理方式的序号，用于写入存档
+    pub fn index(&self) -> usize {
+        SHORT_TAP_BEHAVIORS
+            .iter()
+            .position(|behavior| behavior == self)
+            .unwrap_or(1)
+    }
+
+    /// 切换顺序中的下一个快速点按处理方式
+    pub fn next(&self) -> Self {
+        Self::from_index((self.index() + 1) % SHORT_TAP_BEHAVIORS.len())
+    }
+
+    /// 快速点按处理方式名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Jump => "Jump",
+            Self::Cancel => "Cancel",
+            Self::Hop => "Hop",
+        }
+    }
+}
+
+/// 蓄力粒子效果质量，控制粒子数量和生成频率，性能较弱的设备可以关闭
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Resource)]
+pub enum ParticleQuality {
+    Off,  // 不生成蓄力粒子
+    #[default]
+    Low,  // 每200毫秒生成3个粒子
+    High, // 每100毫秒生成8个粒子
+}
+
+/// 可选的粒子效果质量，按设置界面中的切换顺序排列
+const PARTICLE_QUALITIES: [ParticleQuality; 3] =
+    [ParticleQuality::Off, ParticleQuality::Low, ParticleQuality::High];
+
+impl ParticleQuality {
+    /// 根据序号获取粒子效果质量，序号超出范围时使用默认质量
+    pub fn from_index(index: usize) -> Self {
+        PARTICLE_QUALITIES.get(index).copied().unwrap_or_default()
+    }
+
+    /// 粒子效果质量的序号，用于写入存档
+    pub fn index(&self) -> usize {
+        PARTICLE_QUALITIES
+            .iter()
+            .position(|quality| quality == self)
+            .unwrap_or(1)
+    }
+
+    /// 切换顺序中的下一个粒子效果质量
+    pub fn next(&self) -> Self {
+        Self::from_index((self.index() + 1) % PARTICLE_QUALITIES.len())
+    }
+
+    /// 粒子效果质量名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Off => "Off",
+            Self::Low => "Low",
+            Self::High => "High",
+        }
+    }
+
+    /// 每次生成的粒子数量，关闭时为0
+    pub fn particle_count(&self) -> u32 {
+        match self {
+            Self::Off => 0,
+            Self::Low => 3,
+            Self::High => 8,
+        }
+    }
+
+    /// 两次生成粒子之间的间隔
+    pub fn spawn_interval(&self) -> std::time::Duration {
+        match self {
+            Self::Off | Self::Low => std::time::Duration::from_millis(200),
+            Self::High => std::time::Duration::from_millis(100),
+        }
+    }
+}
+
+/// 抗锯齿（多重采样）等级，等级越高模型边缘越平滑，渲染开销也越大
+///
+/// 桌面端默认4倍采样；网页版只支持关闭或4倍采样，为了性能默认关闭
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Resource)]
+pub enum MsaaLevel {
+    Off, // 关闭抗锯齿
+    X2,  // 2倍多重采样
+    X4,  // 4倍多重采样
+}
+
+/// 可选的抗锯齿等级，按设置界面中的切换顺序排列
+#[cfg(not(target_arch = "wasm32"))]
+const MSAA_LEVELS: [MsaaLevel; 3] = [MsaaLevel::Off, MsaaLevel::X2, MsaaLevel::X4];
+
+/// 可选的抗锯齿等级，WebGL2不支持2倍采样
+#[cfg(target_arch = "wasm32")]
+const MSAA_LEVELS: [MsaaLevel; 2] = [MsaaLevel::Off, MsaaLevel::X4];
+
+impl Default for MsaaLevel {
+    fn default() -> Self {
+        if cfg!(target_arch = "wasm32") {
+            Self::Off
+        } else {
+            Self::X4
+        }
+    }
+}
+
+impl MsaaLevel {
+    /// 根据序号获取抗锯齿等级，序号超出范围时使用默认等级
+    pub fn from_index(index: usize) -> Self {
+        MSAA_LEVELS.get(index).copied().unwrap_or_default()
+    }
+
+    /// 抗锯齿等级的序号，用于写入存档
+    pub fn index(&self) -> usize {
+        MSAA_LEVELS
+            .iter()
+            .position(|level| level == self)
+            .unwrap_or_default()
+    }
+
+    /// 切换顺序中的下一个抗锯齿等级
+    pub fn next(&self) -> Self {
+        Self::from_index((self.index() + 1) % MSAA_LEVELS.len())
+    }
+
+    /// 抗锯齿等级名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Off => "Off",
+            Self::X2 => "x2",
+            Self::X4 => "x4",
+        }
+    }
+
+    /// 对应的相机多重采样组件
+    pub fn msaa(&self) -> Msaa {
+        match self {
+            Self::Off => Msaa::Off,
+            Self::X2 => Msaa::Sample2,
+            Self::X4 => Msaa::Sample4,
+        }
+    }
+}
+
+/// 帧率限制，默认开启垂直同步，避免简单场景下帧率过高浪费电量和发热
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Resource)]
+pub enum FrameRateLimit {
+    #[default]
+    Vsync,     // 垂直同步，帧率与显示器刷新率一致
+    Unlimited, // 关闭垂直同步，不限制帧率
+    Fps30,     // 关闭垂直同步，限制为每秒30帧
+    Fps60,     // 关闭垂直同步，限制为每秒60帧
+    Fps120,    // 关闭垂直同步，限制为每秒120帧
+}
+
+/// 可选的帧率限制，按设置界面中的切换顺序排列
+#[cfg(not(target_arch = "wasm32"))]
+const FRAME_RATE_LIMITS: [FrameRateLimit; 5] = [
+    FrameRateLimit::Vsync,
+    FrameRateLimit::Unlimited,
+    FrameRateLimit::Fps30,
+    FrameRateLimit::Fps60,
+    FrameRateLimit::Fps120,
+];
+
+/// 可选的帧率限制，网页版由浏览器按显示器刷新率驱动，只能使用垂直同步
+#[cfg(target_arch = "wasm32")]
+const FRAME_RATE_LIMITS: [FrameRateLimit; 1] = [FrameRateLimit::Vsync];
+
+impl FrameRateLimit {
+    /// 根据序号获取帧率限制，序号超出范围时使用默认设置
+    pub fn from_index(index: usize) -> Self {
+        FRAME_RATE_LIMITS.get(index).copied().unwrap_or_default()
+    }
+
+    /// 帧率限制的序号，用于写入存档
+    pub fn index(&self) -> usize {
+        FRAME_RATE_LIMITS
+            .iter()
+            .position(|limit| limit == self)
+            .unwrap_or_default()
+    }
+
+    /// 切换顺序中的下一个帧率限制
+    pub fn next(&self) -> Self {
+        Self::from_index((self.index() + 1) % FRAME_RATE_LIMITS.len())
+    }
+
+    /// 帧率限制名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Vsync => "Vsync",
+            Self::Unlimited => "Off",
+            Self::Fps30 => "30 FPS",
+            Self::Fps60 => "60 FPS",
+            Self::Fps120 => "120 FPS",
+        }
+    }
+
+    /// 对应的窗口呈现模式，限制帧率时关闭垂直同步，由帧率限制器控制节奏
+    pub fn present_mode(&self) -> PresentMode {
+        match self {
+            Self::Vsync => PresentMode::AutoVsync,
+            _ => PresentMode::AutoNoVsync,
+        }
+    }
+
+    /// 帧率上限，不限制时返回`None`
+    pub fn frame_cap(&self) -> Option<u32> {
+        match self {
+            Self::Vsync | Self::Unlimited => None,
+            Self::Fps30 => Some(30),
+            Self::Fps60 => Some(60),
+            Self::Fps120 => Some(120),
+        }
+    }
+}
+
+/// 泛光强度，关闭时同时关闭HDR渲染，减少渲染开销
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Resource)]
+pub enum BloomLevel {
+    Off,  // 关闭泛光和HDR
+    Low,  // 较弱的泛光
+    #[default]
+    Normal, // 默认泛光强度
+    High, // 较强的泛光
+}
+
+/// 可选的泛光强度，按设置界面中的切换顺序排列
+const BLOOM_LEVELS: [BloomLevel; 4] =
+    [BloomLevel::Off, BloomLevel::Low, BloomLevel::Normal, BloomLevel::High];
+
+impl BloomLevel {
+    /// 根据序号获取泛光强度，序号超出范围时使用默认强度
+    pub fn from_index(index: usize) -> Self {
+        BLOOM_LEVELS.get(index).copied().unwrap_or_default()
+    }
+
+    /// 泛光强度的序号，用于写入存档
+    pub fn index(&self) -> usize {
+        BLOOM_LEVELS
+            .iter()
+            .position(|level| level == self)
+            .unwrap_or(2)
+    }
+
+    /// 切换顺序中的下一个泛光强度
+    pub fn next(&self) -> Self {
+        Self::from_index((self.index() + 1) % BLOOM_LEVELS.len())
+    }
+
+    /// 泛光强度名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Off => "Off",
+            Self::Low => "Low",
+            Self::Normal => "Normal",
+            Self::High => "High",
+        }
+    }
+
+    /// 是否启用HDR渲染，泛光需要HDR
+    pub fn hdr(&self) -> bool {
+        *self != Self::Off
+    }
+
+    /// 对应的泛光组件，关闭时返回`None`
+    pub fn bloom(&self) -> Option<Bloom> {
+        let intensity = match self {
+            Self::Off => return None,
+            Self::Low => Bloom::default().intensity * 0.5,
+            Self::Normal => Bloom::default().intensity,
+            Self::High => Bloom::default().intensity * 2.0,
+        };
+        Some(Bloom {
+            intensity,
+            ..default()
+        })
+    }
+}
+
+/// 平台材质风格，只影响之后新生成的平台
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Resource)]
+pub enum PlatformStyle {
+    #[default]
+    Matte,    // 哑光，粗糙无金属感
+    Glossy,   // 光泽，低粗糙度带轻微金属感
+    Emissive, // 自发光，开启泛光时平台会发出柔光
+}
+
+/// 可选的平台材质风格，按设置界面中的切换顺序排列
+const PLATFORM_STYLES: [PlatformStyle; 3] =
+    [PlatformStyle::Matte, PlatformStyle::Glossy, PlatformStyle::Emissive];
+
+impl PlatformStyle {
+    /// 根据序号获取平台材质风格，序号超出范围时使用默认风格
+    pub fn from_index(index: usize) -> Self {
+        PLATFORM_STYLES.get(index).copied().unwrap_or_default()
+    }
+
+    /// 平台材质风格的序号，用于写入存档
+    pub fn index(&self) -> usize {
+        PLATFORM_STYLES
+            .iter()
+            .position(|style| style == self)
+            .unwrap_or_default()
+    }
+
+    /// 切换顺序中的下一个平台材质风格
+    pub fn next(&self) -> Self {
+        Self::from_index((self.index() + 1) % PLATFORM_STYLES.len())
+    }
+
+    /// 平台材质风格名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Matte => "Matte",
+            Self::Glossy => "Glossy",
+            Self::Emissive => "Emissive",
+        }
+    }
+
+    /// 按风格创建指定颜色的平台材质
+    ///
+    /// # 参数
+    /// - `color`: 平台基础颜色
+    ///
+    /// # 返回值
+    /// 设置好金属度、粗糙度和自发光的标准材质
+    pub fn material(&self, color: Color) -> StandardMaterial {
+        match self {
+            Self::Matte => StandardMaterial {
+                base_color: color,
+                perceptual_roughness: 0.9,
+                metallic: 0.0,
+                ..default()
+            },
+            Self::Glossy => StandardMaterial {
+                base_color: color,
+                perceptual_roughness: 0.2,
+                metallic: 0.3,
+                reflectance: 0.7,
+                ..default()
+            },
+            Self::Emissive => StandardMaterial {
+                base_color: color,
+                emissive: LinearRgba::from(color) * 0.6,
+                perceptual_roughness: 0.5,
+                ..default()
+            },
+        }
+    }
+}
+
+/// 阴影质量，控制方向光是否投射阴影以及阴影贴图的分辨率和级联数量
+///
+/// 桌面端默认高质量；网页版渲染开销较大，默认关闭阴影
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Resource)]
+pub enum ShadowQuality {
+    Off,  // 关闭阴影
+    Low,  // 1024分辨率、单级联的阴影
+    High, // 2048分辨率、多级联的阴影
+}
+
+/// 可选的阴影质量，按设置界面中的切换顺序排列
+const SHADOW_QUALITIES: [ShadowQuality; 3] =
+    [ShadowQuality::Off, ShadowQuality::Low, ShadowQuality::High];
+
+impl Default for ShadowQuality {
+    fn default() -> Self {
+        if cfg!(target_arch = "wasm32") {
+            Self::Off
+        } else {
+            Self::High
+        }
+    }
+}
+
+impl ShadowQuality {
+    /// 根据序号获取阴影质量，序号超出范围时使用默认质量
+    pub fn from_index(index: usize) -> Self {
+        SHADOW_QUALITIES.get(index).copied().unwrap_or_default()
+    }
+
+    /// 阴影质量的序号，用于写入存档
+    pub fn index(&self) -> usize {
+        SHADOW_QUALITIES
+            .iter()
+            .position(|quality| quality == self)
+            .unwrap_or_default()
+    }
+
+    /// 切换顺序中的下一个阴影质量
+    pub fn next(&self) -> Self {
+        Self::from_index((self.index() + 1) % SHADOW_QUALITIES.len())
+    }
+
+    /// 阴影质量名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Off => "Off",
+            Self::Low => "Low",
+            Self::High => "High",
+        }
+    }
+
+    /// 方向光是否投射阴影
+    pub fn shadows_enabled(&self) -> bool {
+        *self != Self::Off
+    }
+
+    /// 方向光阴影贴图的分辨率
+    pub fn shadow_map(&self) -> DirectionalLightShadowMap {
+        match self {
+            Self::Off | Self::Low => DirectionalLightShadowMap { size: 1024 },
+            Self::High => DirectionalLightShadowMap { size: 2048 },
+        }
+    }
+
+    /// 方向光的阴影级联设置
+    ///
+    /// 低质量只使用一个级联并缩短阴影距离；高质量使用默认级联数量（网页版只支持一个级联）
+    pub fn cascade_shadow_config(&self) -> CascadeShadowConfig {
+        match self {
+            Self::Off | Self::Low => CascadeShadowConfigBuilder {
+                num_cascades: 1,
+                maximum_distance: 40.0,
+                ..default()
+            }
+            .build(),
+            Self::High => CascadeShadowConfigBuilder {
+                maximum_distance: 100.0,
+                ..default()
+            }
+            .build(),
+        }
+    }
+}
+
+/// 色盲友好配色模式，把平台调色板和界面强调色换成对应色觉缺陷下仍能区分的颜色
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Resource)]
+pub enum ColorblindMode {
+    #[default]
+    Off,          // 使用默认配色
+    Protanopia,   // 红色盲
+    Deuteranopia, // 绿色盲
+    Tritanopia,   // 蓝黄色盲
+}
+
+/// 可选的色盲配色模式，按设置界面中的切换顺序排列
+const COLORBLIND_MODES: [ColorblindMode; 4] = [
+    ColorblindMode::Off,
+    ColorblindMode::Protanopia,
+    ColorblindMode::Deuteranopia,
+    ColorblindMode::Tritanopia,
+];
+
+impl ColorblindMode {
+    /// 根据序号获取配色模式，序号超出范围时使用默认配色
+    pub fn from_index(index: usize) -> Self {
+        COLORBLIND_MODES.get(index).copied().unwrap_or_default()
+    }
+
+    /// 配色模式的序号，用于写入存档
+    pub fn index(&self) -> usize {
+        COLORBLIND_MODES
+            .iter()
+            .position(|mode| mode == self)
+            .unwrap_or_default()
+    }
+
+    /// 切换顺序中的下一个配色模式
+    pub fn next(&self) -> Self {
+        Self::from_index((self.index() + 1) % COLORBLIND_MODES.len())
+    }
+
+    /// 配色模式名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Off => "Off",
+            Self::Protanopia => "Protanopia",
+            Self::Deuteranopia => "Deuteranopia",
+            Self::Tritanopia => "Tritanopia",
+        }
+    }
+
+    /// 平台调色板，默认配色时返回`None`使用原有调色板
+    ///
+    /// 各调色板颜色数量与默认调色板相同，相同种子下随机选色的结果一一对应；
+    /// 红色盲和绿色盲使用Okabe-Ito配色，蓝黄色盲避开蓝色与黄色、改用红粉和青色系
+    pub fn platform_palette(&self) -> Option<Vec<Color>> {
+        match self {
+            Self::Off => None,
+            Self::Protanopia | Self::Deuteranopia => Some(vec![
+                Color::srgb(0.90, 0.62, 0.00), // 橙色
+                Color::srgb(0.34, 0.71, 0.91), // 天蓝
+                Color::srgb(0.00, 0.62, 0.45), // 蓝绿
+                Color::srgb(0.94, 0.89, 0.26), // 黄色
+                Color::srgb(0.00, 0.45, 0.70), // 深蓝
+                Color::srgb(0.84, 0.37, 0.00), // 朱红
+                Color::srgb(0.80, 0.47, 0.65), // 紫红
+                Color::srgb(0.60, 0.60, 0.60), // 灰色
+            ]),
+            Self::Tritanopia => Some(vec![
+                Color::srgb(0.86, 0.15, 0.15), // 红色
+                Color::srgb(0.95, 0.55, 0.65), // 粉色
+                Color::srgb(0.00, 0.60, 0.60), // 青色
+                Color::srgb(0.10, 0.35, 0.45), // 深青
+                Color::srgb(0.55, 0.55, 0.55), // 灰色
+                Color::srgb(0.90, 0.90, 0.90), // 浅灰
+                Color::srgb(0.50, 0.10, 0.20), // 酒红
+                Color::srgb(0.50, 0.85, 0.85), // 浅青
+            ]),
+        }
+    }
+
+    /// 飘分文字颜色
+    pub fn score_up_color(&self) -> Color {
+        match self {
+            Self::Off => Color::srgb(0.5, 0.5, 1.0),
+            Self::Protanopia | Self::Deuteranopia => Color::srgb(0.34, 0.71, 0.91),
+            Self::Tritanopia => Color::srgb(0.95, 0.35, 0.45),
+        }
+    }
+
+    /// 下一个平台指示箭头等高亮提示的颜色
+    pub fn highlight_color(&self) -> Color {
+        match self {
+            Self::Off => Color::srgb(1.0, 0.45, 0.1),
+            Self::Protanopia | Self::Deuteranopia => Color::srgb(0.90, 0.62, 0.0),
+            Self::Tritanopia => Color::srgb(0.9, 0.2, 0.3),
+        }
+    }
+}
+
+/// 存档数据，保存在磁盘上的设置和分数
+///
+/// 缺失的字段使用默认值，保证旧存档可以正常读取
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SaveData {
+    /// 全局音量
+    pub volume: f32,
+    /// 是否静音
+    pub audio_muted: bool,
+    /// 最高分
+    pub high_score: u32,
+    /// 无尽模式排行榜，按分数从高到低排列
+    pub leaderboard: Vec<u32>,
+    /// 每日挑战最高分所属日期的种子
+    pub daily_date_seed: u64,
+    /// 每日挑战最高分
+    pub daily_high_score: u32,
+    /// 玩家皮肤预设序号
+    pub skin: usize,
+    /// 是否显示平台中心标记
+    pub show_target_marker: bool,
+    /// 是否使用状态切换过渡效果
+    pub fade_transitions: bool,
+    /// 是否开启安全网
+    pub safety_net: bool,
+    /// 是否已经看过新手引导
+    pub seen_tutorial: bool,
+    /// 是否使用正交相机
+    pub orthographic_camera: bool,
+    /// 是否开启过度蓄力取消
+    pub fizzle_on_overcharge: bool,
+    /// 光照主题序号
+    pub lighting_theme: usize,
+    /// 按键绑定
+    pub input_bindings: InputBindings,
+    /// 是否在窗口失去焦点时自动暂停
+    pub auto_pause: bool,
+    /// 蓄力粒子效果质量序号
+    pub particle_quality: usize,
+    /// 玩家颜色是否随分数变化
+    pub score_color_progression: bool,
+    /// 是否开启连击加速
+    pub combo_chains: bool,
+    /// 抗锯齿等级序号
+    pub msaa_level: usize,
+    /// 蓄力曲线序号
+    pub charge_curve: usize,
+    /// 是否显示摔落暗角
+    pub fall_vignette: bool,
+    /// 下一个平台是否发光脉冲
+    pub pulse_next_platform: bool,
+    /// 色盲配色模式序号
+    pub colorblind_mode: usize,
+    /// 跳跃时是否面朝落点方向
+    pub face_jump_direction: bool,
+    /// 帧率限制序号
+    pub frame_rate_limit: usize,
+    /// 使用固定种子时各种子和模式得分最高一局的幽灵轨迹
+    pub best_run_ghosts: Vec<GhostRun>,
+    /// 落地容差倍率
+    pub landing_tolerance: f32,
+    /// 阴影质量序号
+    pub shadow_quality: usize,
+    /// 泛光强度序号
+    pub bloom_level: usize,
+    /// 平台材质风格序号
+    pub platform_style: usize,
+    /// 快速点按处理方式序号
+    pub short_tap: usize,
+    /// 是否播放新平台的生成动画
+    pub platform_spawn_animation: bool,
+    /// 限时挑战最高分
+    pub time_attack_high_score: u32,
+    /// 分岔路径挑战最高分
+    pub forked_high_score: u32,
+}
+
+impl Default for SaveData {
+    fn default() -> Self {
+        Self {
+            volume: 1.0,
+            audio_muted: false,
+            high_score: 0,
+            leaderboard: Vec::new(),
+            daily_date_seed: 0,
+            daily_high_score: 0,
+            skin: 0,
+            show_target_marker: true,
+            fade_transitions: true,
+            safety_net: false,
+            seen_tutorial: false,
+            orthographic_camera: false,
+            fizzle_on_overcharge: false,
+            lighting_theme: 0,
+            input_bindings: InputBindings::default(),
+            auto_pause: true,
+            particle_quality: ParticleQuality::default().index(),
+            score_color_progression: false,
+            combo_chains: false,
+            msaa_level: MsaaLevel::default().index(),
+            charge_curve: ChargeCurve::default().index(),
+            fall_vignette: true,
+            pulse_next_platform: true,
+            colorblind_mode: ColorblindMode::default().index(),
+            face_jump_direction: false,
+            frame_rate_limit: FrameRateLimit::default().index(),
+            best_run_ghosts: Vec::new(),
+            landing_tolerance: LandingTolerance::default().0,
+            shadow_quality: ShadowQuality::default().index(),
+            bloom_level: BloomLevel::default().index(),
+            platform_style: PlatformStyle::default().index(),
+            short_tap: ShortTapBehavior::default().index(),
+            platform_spawn_animation: true,
+            time_attack_high_score: 0,
+            forked_high_score: 0,
+        }
+    }
+}
+
+impl SaveData {
+    /// 从存档文件读取数据
+    ///
+    /// 文件不存在或格式错误时返回默认值；Web平台没有文件系统，始终返回默认值
+    pub fn load() -> Self {
+        if cfg!(target_arch = "wasm32") {
+            return Self::default();
+        }
+        match std::fs::read_to_string(SAVE_FILE_PATH) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|err| {
+                warn!("Failed to parse save file: {}", err);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// 将数据写入存档文件
+    pub fn save(&self) {
+        if cfg!(target_arch = "wasm32") {
+            return;
+        }
+        match serde_json::to_string_pretty(self) {
+            Ok(content) => {
+                // 先写入临时文件再替换存档，写入中途退出也不会留下不完整的存档
+                let result = std::fs::write(SAVE_TEMP_FILE_PATH, content)
+                    .and_then(|()| std::fs::rename(SAVE_TEMP_FILE_PATH, SAVE_FILE_PATH));
+                if let Err(err) = result {
+                    warn!("Failed to write save file: {}", err);
+                }
+            }
+            Err(err) => warn!("Failed to serialize save data: {}", err),
+        }
+    }
+}
+
+/// 标记设置界面元素的组件
+#[derive(Component)]
+pub struct OnSettingsScreen;
+
+/// 设置界面按钮功能组件
+#[derive(Component)]
+pub enum SettingsButtonAction {
+    VolumeDown, // 降低音量
+    VolumeUp,   // 提高音量
+    ToggleTargetMarker, // 切换平台中心标记显示
+    ToggleFadeTransitions, // 切换状态过渡效果
+    ToggleSafetyNet, // 切换安全网
+    ToggleOrthographicCamera, // 切换正交相机
+    ToggleFizzleOnOvercharge, // 切换过度蓄力取消
+    CycleLightingTheme, // 切换光照主题
+    ToggleAutoPause, // 切换失去焦点时自动暂停
+    CycleParticleQuality, // 切换蓄力粒子效果质量
+    CycleMsaaLevel, // 切换抗锯齿等级
+    ToggleScoreColor, // 切换玩家颜色随分数变化
+    ToggleComboChains, // 切换连击加速
+    CycleChargeCurve, // 切换蓄力曲线
+    ToggleFallVignette, // 切换摔落暗角
+    TogglePulseNextPlatform, // 切换下一个平台发光脉冲
+    CycleColorblindMode, // 切换色盲配色模式
+    ToggleFaceJumpDirection, // 切换跳跃时面朝落点方向
+    CycleFrameRateLimit, // 切换帧率限制
+    CycleLandingTolerance, // 切换落地难度
+    CycleShadowQuality, // 切换阴影质量
+    CycleBloomLevel, // 切换泛光强度
+    CyclePlatformStyle, // 切换平台材质风格
+    CycleShortTap, // 切换快速点按处理方式
+    TogglePlatformSpawnAnimation, // 切换新平台生成动画
+}
+
+/// 标记音量数值文本的组件
+#[derive(Component)]
+pub struct VolumeText;
+
+/// 开关显示文本
+fn toggle_label(enabled: bool) -> &'static str {
+    if enabled {
+        "On"
+    } else {
+        "Off"
+    }
+}
+
+/// 音量显示文本，以百分比表示
+fn volume_label(volume: &Volume) -> String {
+    format!("{}%", (volume.0 * 100.0).round() as u32)
+}
+
+/// 生成一行开关设置，包含名称和显示开关状态的按钮
+///
+/// # 参数
+/// - `parent`: 父节点构建器
+/// - `label`: 设置名称
+/// - `enabled`: 开关当前状态
+/// - `action`: 按钮功能，开关状态文本按按钮功能更新
+fn spawn_toggle_row(
+    parent: &mut ChildBuilder,
+    label: &str,
+    enabled: bool,
+    action: SettingsButtonAction,
+) {
+    spawn_option_row(parent, label, toggle_label(enabled), action);
+}
+
+/// 生成一行选项设置，包含名称和显示当前选项的按钮，点击按钮切换选项
+///
+/// # 参数
+/// - `parent`: 父节点构建器
+/// - `label`: 设置名称
+/// - `value`: 当前选项的显示文本
+/// - `action`: 按钮功能，选项文本按按钮功能更新
+fn spawn_option_row(
+    parent: &mut ChildBuilder,
+    label: &str,
+    value: &str,
+    action: SettingsButtonAction,
+) {
+    parent
+        .spawn((Node { // 水平排列的开关容器
+            flex_direction: FlexDirection::Row,
+            align_items: AlignItems::Center,
+            ..default()
+        },))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(label),
+                TextColor(Color::BLACK),
+                TextFont {
+                    font_size: 30.0,
+                    ..default()
+                },
+            ));
+            parent
+                .spawn((
+                    Button, // 按钮交互组件
+                    Node { // 按钮样式
+                        min_width: Val::Px(60.),
+                        height: Val::Px(40.),
+                        padding: UiRect::horizontal(Val::Px(12.0)),
+                        margin: UiRect::all(Val::Px(6.0)),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    BackgroundColor(TEXT_BUTTON_COLOR),
+                    BorderRadius::all(Val::Px(8.0)),
+                    action, // 按钮功能标记
+                ))
+                .with_child((
+                    Text::new(value), // 选项文本，设置变化时由update_setting_texts更新
+                    TextColor(Color::WHITE),
+                    TextFont {
+                        font_size: 24.0,
+                        ..default()
+                    },
+                ));
+        });
+}
+
+/// 设置界面
+///
+/// 创建设置界面布局，包含音量调节按钮和返回按钮
+pub fn setup_settings_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    volume: Res<Volume>,
+    toggles: GameplayToggles,
+    orthographic_camera: Res<OrthographicCamera>,
+    graphics: GraphicsSettings,
+    colorblind_mode: Res<ColorblindMode>,
+) {
+    commands
+        .spawn((
+            Node { // 主容器节点，全屏覆盖
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            OnSettingsScreen, // 标记为属于设置界面的元素
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((Node { // 垂直排列的内容容器
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },))
+                .with_children(|parent| {
+                    // 标题
+                    parent.spawn((
+                        Text::new("Settings"),
+                        TextColor(Color::BLACK),
+                        TextFont {
+                            font_size: 50.0,
+                            ..default()
+                        },
+                        Node {
+                            margin: UiRect::all(Val::Px(20.0)),
+                            ..default()
+                        },
+                    ));
+
+                    // 设置项容器，设置项较多时自动换到下一列，避免超出窗口
+                    parent
+                        .spawn((Node {
+                            flex_direction: FlexDirection::Column,
+                            flex_wrap: FlexWrap::Wrap,
+                            align_items: AlignItems::Center,
+                            align_content: AlignContent::Center,
+                            max_height: Val::Vh(65.0),
+                            column_gap: Val::Px(40.0),
+                            ..default()
+                        },))
+                        .with_children(|parent| {
+                            // 音量调节行
+                            parent
+                                .spawn((Node { // 水平排列的音量调节容器
+                                    flex_direction: FlexDirection::Row,
+                                    align_items: AlignItems::Center,
+                                    ..default()
+                                },))
+                                .with_children(|parent| {
+                                    parent.spawn((
+                                        Text::new("Volume"),
+                                        TextColor(Color::BLACK),
+                                        TextFont {
+                                            font_size: 30.0,
+                                            ..default()
+                                        },
+                                    ));
+                                    spawn_text_button(parent, "-", SettingsButtonAction::VolumeDown);
+                                    parent.spawn((
+                                        Text::new(volume_label(&volume)),
+                                        TextColor(Color::BLACK),
+                                        TextFont {
+                                            font_size: 30.0,
+                                            ..default()
+                                        },
+                                        VolumeText, // 标记为音量数值文本
+                                    ));
+                                    spawn_text_button(parent, "+", SettingsButtonAction::VolumeUp);
+                                });
+
+                            // 平台中心标记开关行
+                            spawn_toggle_row(
+                                parent,
+                                "Target",
+                                toggles.show_target_marker.0,
+                                SettingsButtonAction::ToggleTargetMarker,
+                            );
+
+                            // 状态切换过渡效果开关行
+                            spawn_toggle_row(
+                                parent,
+                                "Fade",
+                                toggles.fade_transitions.0,
+                                SettingsButtonAction::ToggleFadeTransitions,
+                            );
+
+                            // 安全网开关行
+                            spawn_toggle_row(
+                                parent,
+                                "Safety net",
+                                toggles.safety_net.0,
+                                SettingsButtonAction::ToggleSafetyNet,
+                            );
+
+                            // 落地难度选择行，简单模式放大落地判定范围
+                            spawn_option_row(
+                                parent,
+                                "Landing",
+                                toggles.landing_tolerance.name(),
+                                SettingsButtonAction::CycleLandingTolerance,
+                            );
+
+                            // 快速点按处理方式选择行，蓄力过短就松开时取消或原地小跳
+                            spawn_option_row(
+                                parent,
+                                "Quick tap",
+                                toggles.short_tap.name(),
+                                SettingsButtonAction::CycleShortTap,
+                            );
+
+                            // 正交相机开关行
+                            spawn_toggle_row(
+                                parent,
+                                "Orthographic",
+                                orthographic_camera.0,
+                                SettingsButtonAction::ToggleOrthographicCamera,
+                            );
+
+                            // 过度蓄力取消开关行
+                            spawn_toggle_row(
+                                parent,
+                                "Overcharge fizzle",
+                                toggles.fizzle_on_overcharge.0,
+                                SettingsButtonAction::ToggleFizzleOnOvercharge,
+                            );
+
+                            // 光照主题选择行
+                            spawn_option_row(
+                                parent,
+                                "Lighting",
+                                graphics.lighting_theme.name(),
+                                SettingsButtonAction::CycleLightingTheme,
+                            );
+
+                            // 玩家颜色随分数变化开关行
+                            spawn_toggle_row(
+                                parent,
+                                "Rainbow player",
+                                toggles.score_color.0,
+                                SettingsButtonAction::ToggleScoreColor,
+                            );
+
+                            // 连击加速开关行
+                            spawn_toggle_row(
+                                parent,
+                                "Chain speed",
+                                toggles.combo_chains.0,
+                                SettingsButtonAction::ToggleComboChains,
+                            );
+
+                            // 蓄力曲线选择行，非线性曲线下短按跳得更近
+                            spawn_option_row(
+                                parent,
+                                "Charge curve",
+                                toggles.tuning.charge_curve.name(),
+                                SettingsButtonAction::CycleChargeCurve,
+                            );
+
+                            // 摔落暗角开关行
+                            spawn_toggle_row(
+                                parent,
+                                "Fall vignette",
+                                toggles.fall_vignette.0,
+                                SettingsButtonAction::ToggleFallVignette,
+                            );
+
+                            // 下一个平台发光脉冲开关行
+                            spawn_toggle_row(
+                                parent,
+                                "Target pulse",
+                                toggles.pulse_next_platform.0,
+                                SettingsButtonAction::TogglePulseNextPlatform,
+                            );
+
+                            // 新平台生成动画开关行
+                            spawn_toggle_row(
+                                parent,
+                                "Platform pop-in",
+                                toggles.platform_spawn_animation.0,
+                                SettingsButtonAction::TogglePlatformSpawnAnimation,
+                            );
+
+                            // 跳跃时面朝落点方向开关行
+                            spawn_toggle_row(
+                                parent,
+                                "Face jump",
+                                toggles.face_jump_direction.0,
+                                SettingsButtonAction::ToggleFaceJumpDirection,
+                            );
+
+                            // 粒子效果质量选择行
+                            spawn_option_row(
+                                parent,
+                                "Particles",
+                                graphics.particle_quality.name(),
+                                SettingsButtonAction::CycleParticleQuality,
+                            );
+
+                            // 抗锯齿等级选择行
+                            spawn_option_row(
+                                parent,
+                                "Anti-aliasing",
+                                graphics.msaa_level.name(),
+                                SettingsButtonAction::CycleMsaaLevel,
+                            );
+
+                            // 阴影质量选择行
+                            spawn_option_row(
+                                parent,
+                                "Shadows",
+                                graphics.shadow_quality.name(),
+                                SettingsButtonAction::CycleShadowQuality,
+                            );
+
+                            // 泛光强度选择行
+                            spawn_option_row(
+                                parent,
+                                "Bloom",
+                                graphics.bloom_level.name(),
+                                SettingsButtonAction::CycleBloomLevel,
+                            );
+
+                            // 平台材质风格选择行
+                            spawn_option_row(
+                                parent,
+                                "Platforms",
+                                graphics.platform_style.name(),
+                                SettingsButtonAction::CyclePlatformStyle,
+                            );
+
+                            // 帧率限制选择行
+                            spawn_option_row(
+                                parent,
+                                "Frame rate",
+                                graphics.frame_rate_limit.name(),
+                                SettingsButtonAction::CycleFrameRateLimit,
+                            );
+
+                            // 色盲配色模式选择行
+                            spawn_option_row(
+                                parent,
+                                "Colorblind",
+                                colorblind_mode.name(),
+                                SettingsButtonAction::CycleColorblindMode,
+                            );
+
+                            // 失去焦点时自动暂停开关行
+                            spawn_toggle_row(
+                                parent,
+                                "Auto pause",
+                                toggles.auto_pause.0,
+                                SettingsButtonAction::ToggleAutoPause,
+                            );
+                        });
+
+                    // 按键设置按钮
+                    spawn_text_button(parent, "Controls", MenuButtonAction::Controls);
+
+                    // 返回按钮
+                    parent.spawn((
+                        Button, // 按钮交互组件
+                        Node { // 按钮样式
+                            width: Val::Px(40.),
+                            height: Val::Px(40.),
+                            margin: UiRect::all(Val::Px(20.0)),
+                            ..default()
+                        },
+                        ImageNode::new(asset_server.load("image/btn_back.png")),
+                        MenuButtonAction::BackToMainMenu, // 按钮功能标记
+                    ));
+                });
+        });
+}
+
+/// 处理设置界面按钮点击事件
+///
+/// 每次点击将音量调整10%，范围限制在0%到100%
+pub fn click_settings_button(
+    interaction_query: ButtonInteractions<SettingsButtonAction>,
+    mut volume: ResMut<Volume>,
+    mut toggles: GameplayTogglesMut,
+    mut orthographic_camera: ResMut<OrthographicCamera>,
+    mut graphics: GraphicsSettingsMut,
+    mut colorblind_mode: ResMut<ColorblindMode>,
+) {
+    for (interaction, settings_button_action) in &interaction_query {
+        // 只有在按钮被按下时处理
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match settings_button_action {
+            SettingsButtonAction::VolumeDown => {
+                volume.0 = ((volume.0 - 0.1) * 10.0).round().max(0.0) / 10.0;
+            }
+            SettingsButtonAction::VolumeUp => {
+                volume.0 = ((volume.0 + 0.1) * 10.0).round().min(10.0) / 10.0;
+            }
+            SettingsButtonAction::ToggleTargetMarker => {
+                toggles.show_target_marker.0 = !toggles.show_target_marker.0;
+            }
+            SettingsButtonAction::ToggleFadeTransitions => {
+                toggles.fade_transitions.0 = !toggles.fade_transitions.0;
+            }
+            SettingsButtonAction::ToggleSafetyNet => {
+                toggles.safety_net.0 = !toggles.safety_net.0;
+            }
+            SettingsButtonAction::ToggleOrthographicCamera => {
+                orthographic_camera.0 = !orthographic_camera.0;
+            }
+            SettingsButtonAction::ToggleFizzleOnOvercharge => {
+                toggles.fizzle_on_overcharge.0 = !toggles.fizzle_on_overcharge.0;
+            }
+            SettingsButtonAction::CycleLightingTheme => {
+                *graphics.lighting_theme = graphics.lighting_theme.next();
+            }
+            SettingsButtonAction::ToggleAutoPause => {
+                toggles.auto_pause.0 = !toggles.auto_pause.0;
+            }
+            SettingsButtonAction::CycleParticleQuality => {
+                *graphics.particle_quality = graphics.particle_quality.next();
+            }
+            SettingsButtonAction::CycleMsaaLevel => {
+                *graphics.msaa_level = graphics.msaa_level.next();
+            }
+            SettingsButtonAction::CycleColorblindMode => {
+                *colorblind_mode = colorblind_mode.next();
+            }
+            SettingsButtonAction::CycleLandingTolerance => {
+                *toggles.landing_tolerance = toggles.landing_tolerance.next();
+            }
+            SettingsButtonAction::CycleShortTap => {
+                *toggles.short_tap = toggles.short_tap.next();
+            }
+            SettingsButtonAction::CycleFrameRateLimit => {
+                *graphics.frame_rate_limit = graphics.frame_rate_limit.next();
+            }
+            SettingsButtonAction::CycleShadowQuality => {
+                *graphics.shadow_quality = graphics.shadow_quality.next();
+            }
+            SettingsButtonAction::CycleBloomLevel => {
+                *graphics.bloom_level = graphics.bloom_level.next();
+            }
+            SettingsButtonAction::CyclePlatformStyle => {
+                *graphics.platform_style = graphics.platform_style.next();
+            }
+            SettingsButtonAction::ToggleFaceJumpDirection => {
+                toggles.face_jump_direction.0 = !toggles.face_jump_direction.0;
+            }
+            SettingsButtonAction::ToggleScoreColor => {
+                toggles.score_color.0 = !toggles.score_color.0;
+            }
+            SettingsButtonAction::ToggleComboChains => {
+                toggles.combo_chains.0 = !toggles.combo_chains.0;
+            }
+            SettingsButtonAction::CycleChargeCurve => {
+                toggles.tuning.charge_curve = toggles.tuning.charge_curve.next();
+            }
+            SettingsButtonAction::ToggleFallVignette => {
+                toggles.fall_vignette.0 = !toggles.fall_vignette.0;
+            }
+            SettingsButtonAction::TogglePulseNextPlatform => {
+                toggles.pulse_next_platform.0 = !toggles.pulse_next_platform.0;
+            }
+            SettingsButtonAction::TogglePlatformSpawnAnimation => {
+                toggles.platform_spawn_animation.0 = !toggles.platform_spawn_animation.0;
+            }
+        }
+    }
+}
+
+/// 更新音量数值显示
+pub fn update_volume_text(volume: Res<Volume>, mut q_volume_text: Query<&mut Text, With<VolumeText>>) {
+    if volume.is_changed() {
+        for mut text in &mut q_volume_text {
+            text.0 = volume_label(&volume);
+        }
+    }
+}
+
+/// 设置界面选项数值系统参数
+///
+/// 组合设置界面中以按钮显示当前值的全部选项，按按钮功能生成显示文本
+#[derive(SystemParam)]
+pub struct SettingValues<'w> {
+    show_target_marker: Res<'w, ShowTargetMarker>,
+    fade_transitions: Res<'w, FadeTransitions>,
+    safety_net: Res<'w, SafetyNet>,
+    orthographic_camera: Res<'w, OrthographicCamera>,
+    fizzle_on_overcharge: Res<'w, FizzleOnOvercharge>,
+    lighting_theme: Res<'w, LightingTheme>,
+    auto_pause: Res<'w, AutoPause>,
+    particle_quality: Res<'w, ParticleQuality>,
+    score_color: Res<'w, ScoreColorProgression>,
+    combo_chains: Res<'w, ComboChains>,
+    msaa_level: Res<'w, MsaaLevel>,
+    tuning: Res<'w, PhysicsTuning>,
+    fall_vignette: Res<'w, FallVignette>,
+    pulse_next_platform: Res<'w, PulseNextPlatform>,
+    colorblind_mode: Res<'w, ColorblindMode>,
+    face_jump_direction: Res<'w, FaceJumpDirection>,
+    frame_rate_limit: Res<'w, FrameRateLimit>,
+    landing_tolerance: Res<'w, LandingTolerance>,
+    shadow_quality: Res<'w, ShadowQuality>,
+    bloom_level: Res<'w, BloomLevel>,
+    platform_style: Res<'w, PlatformStyle>,
+    short_tap: Res<'w, ShortTapBehavior>,
+    platform_spawn_animation: Res<'w, PlatformSpawnAnimation>,
+}
+
+impl SettingValues<'_> {
+    /// 是否有选项被修改过
+    fn changed(&self) -> bool {
+        self.show_target_marker.is_changed()
+            || self.fade_transitions.is_changed()
+            || self.safety_net.is_changed()
+            || self.orthographic_camera.is_changed()
+            || self.fizzle_on_overcharge.is_changed()
+            || self.lighting_theme.is_changed()
+            || self.auto_pause.is_changed()
+            || self.particle_quality.is_changed()
+            || self.score_color.is_changed()
+            || self.combo_chains.is_changed()
+            || self.msaa_level.is_changed()
+            || self.tuning.is_changed()
+            || self.fall_vignette.is_changed()
+            || self.pulse_next_platform.is_changed()
+            || self.colorblind_mode.is_changed()
+            || self.face_jump_direction.is_changed()
+            || self.frame_rate_limit.is_changed()
+            || self.landing_tolerance.is_changed()
+            || self.shadow_quality.is_changed()
+            || self.bloom_level.is_changed()
+            || self.platform_style.is_changed()
+            || self.short_tap.is_changed()
+            || self.platform_spawn_animation.is_changed()
+    }
+
+    /// 按钮功能对应选项的显示文本，音量按钮不显示数值，返回`None`
+    fn label(&self, action: &SettingsButtonAction) -> Option<String> {
+        let label = match action {
+            SettingsButtonAction::VolumeDown | SettingsButtonAction::VolumeUp => return None,
+            SettingsButtonAction::ToggleTargetMarker => toggle_label(self.show_target_marker.0),
+            SettingsButtonAction::ToggleFadeTransitions => toggle_label(self.fade_transitions.0),
+            SettingsButtonAction::ToggleSafetyNet => toggle_label(self.safety_net.0),
+            SettingsButtonAction::ToggleOrthographicCamera => toggle_label(self.orthographic_camera.0),
+            SettingsButtonAction::ToggleFizzleOnOvercharge => toggle_label(self.fizzle_on_overcharge.0),
+            SettingsButtonAction::CycleLightingTheme => self.lighting_theme.name(),
+            SettingsButtonAction::ToggleAutoPause => toggle_label(self.auto_pause.0),
+            SettingsButtonAction::CycleParticleQuality => self.particle_quality.name(),
+            SettingsButtonAction::ToggleScoreColor => toggle_label(self.score_color.0),
+            SettingsButtonAction::ToggleComboChains => toggle_label(self.combo_chains.0),
+            SettingsButtonAction::CycleMsaaLevel => self.msaa_level.name(),
+            SettingsButtonAction::CycleChargeCurve => self.tuning.charge_curve.name(),
+            SettingsButtonAction::ToggleFallVignette => toggle_label(self.fall_vignette.0),
+            SettingsButtonAction::TogglePulseNextPlatform => toggle_label(self.pulse_next_platform.0),
+            SettingsButtonAction::CycleColorblindMode => self.colorblind_mode.name(),
+            SettingsButtonAction::ToggleFaceJumpDirection => toggle_label(self.face_jump_direction.0),
+            SettingsButtonAction::CycleFrameRateLimit => self.frame_rate_limit.name(),
+            SettingsButtonAction::CycleLandingTolerance => self.landing_tolerance.name(),
+            SettingsButtonAction::CycleShadowQuality => self.shadow_quality.name(),
+            SettingsButtonAction::CycleBloomLevel => self.bloom_level.name(),
+            SettingsButtonAction::CyclePlatformStyle => self.platform_style.name(),
+            SettingsButtonAction::CycleShortTap => self.short_tap.name(),
+            SettingsButtonAction::TogglePlatformSpawnAnimation => toggle_label(self.platform_spawn_animation.0),
+        };
+        Some(label.to_string())
+    }
+}
+
+/// 更新设置选项显示
+/// 
+/// 任一设置变化时，按每个选项按钮的功能重新生成按钮上的文本
+pub fn update_setting_texts(
+    values: SettingValues,
+    q_buttons: Query<(&SettingsButtonAction, &Children)>,
+    mut q_text: Query<&mut Text>,
+) {
+    if !values.changed() {
+        return;
+    }
+    for (action, children) in &q_buttons {
+        let Some(label) = values.label(action) else {
+            continue;
+        };
+        for &child in children {
+            if let Ok(mut text) = q_text.get_mut(child) {
+                text.0.clone_from(&label);
+            }
+        }
+    }
+}
+
+/// 正在循环播放的蓄力音效和背景音乐查询，附带是否为蓄力音效
+type LoopingSoundQuery<'w, 's> = Query<
+    'w,
+    's,
+    (&'static AudioSink, Has<AccumulationSound>),
+    Or<(With<AccumulationSound>, With<BackgroundMusic>)>,
+>;
+
+/// 将音量和静音变化实时应用到正在循环播放的蓄力音效和背景音乐
+///
+/// 静音时全局音量设为0，之后生成的所有音效都不会发声；
+/// 正在循环的蓄力音效暂停，背景音乐音量设为0，取消静音时恢复
+pub fn apply_volume_to_sinks(
+    volume: Res<Volume>,
+    muted: Res<AudioMuted>,
+    mut global_volume: ResMut<GlobalVolume>,
+    q_looping_sound: LoopingSoundQuery,
+) {
+    if muted.is_changed() {
+        global_volume.volume = bevy::audio::Volume::new(if muted.0 { 0.0 } else { 1.0 });
+    }
+    if volume.is_changed() || muted.is_changed() {
+        for (sink, is_accumulation) in &q_looping_sound {
+            sink.set_volume(if muted.0 { 0.0 } else { volume.0 });
+            if is_accumulation {
+                if muted.0 {
+                    sink.pause();
+                } else {
+                    sink.play();
+                }
+            }
+        }
+    }
+}
+
+/// 静音状态显示文本
+///
+/// # 参数
+/// - `muted`: 是否静音
+/// - `bindings`: 按键绑定，用于显示静音键
+fn mute_label(muted: &AudioMuted, bindings: &InputBindings) -> String {
+    let key = bindings.get(InputAction::Mute).label();
+    if muted.0 {
+        format!("[{}] Muted", key)
+    } else {
+        format!("[{}] Sound On", key)
+    }
+}
+
+/// 设置静音状态图标
+///
+/// 在右下角创建显示静音状态的文本，所有界面都可见
+pub fn setup_mute_indicator(
+    mut commands: Commands,
+    muted: Res<AudioMuted>,
+    bindings: Res<InputBindings>,
+) {
+    commands.spawn((
+        Text::new(mute_label(&muted, &bindings)), // 静音状态文本
+        TextColor(Color::BLACK), // 文本颜色
+        TextFont { // 文本字体设置
+            font_size: 16.0,
+            ..default()
+        },
+        Node { // 位置设置
+            position_type: PositionType::Absolute, // 绝对定位
+            bottom: Val::Px(8.0), // 距离底部8像素
+            right: Val::Px(8.0), // 距离右侧8像素
+            ..default()
+        },
+        GlobalZIndex(100), // 显示在其他界面之上
+        MuteIndicator, // 标记为静音状态图标
+    ));
+}
+
+/// 按静音键（默认M）切换静音
+pub fn toggle_mute(input: ActionInput, mut muted: ResMut<AudioMuted>) {
+    if input.just_pressed(InputAction::Mute) {
+        muted.0 = !muted.0;
+    }
+}
+
+/// 更新静音状态图标
+///
+/// 静音状态或静音键绑定变化时刷新文本
+pub fn update_mute_indicator(
+    muted: Res<AudioMuted>,
+    bindings: Res<InputBindings>,
+    mut q_indicator: Query<&mut Text, With<MuteIndicator>>,
+) {
+    if muted.is_changed() || bindings.is_changed() {
+        for mut text in &mut q_indicator {
+            text.0 = mute_label(&muted, &bindings);
+        }
+    }
+}
+
+/// 判断资源是否在启动之后被修改过
+///
+/// 启动时插入资源也会被视为修改，需要排除，避免启动时写入存档
+fn changed_since_startup<T: Resource>(resource: &Res<T>) -> bool {
+    resource.is_changed() && !resource.is_added()
+}
+
+/// 玩法开关系统参数
+///
+/// 把设置界面中的各个开关、落地难度和蓄力曲线组合在一起，避免保存系统的参数过多
+#[derive(SystemParam)]
+pub struct GameplayToggles<'w> {
+    show_target_marker: Res<'w, ShowTargetMarker>,
+    fade_transitions: Res<'w, FadeTransitions>,
+    safety_net: Res<'w, SafetyNet>,
+    fizzle_on_overcharge: Res<'w, FizzleOnOvercharge>,
+    auto_pause: Res<'w, AutoPause>,
+    score_color: Res<'w, ScoreColorProgression>,
+    combo_chains: Res<'w, ComboChains>,
+    tuning: Res<'w, PhysicsTuning>,
+    fall_vignette: Res<'w, FallVignette>,
+    pulse_next_platform: Res<'w, PulseNextPlatform>,
+    face_jump_direction: Res<'w, FaceJumpDirection>,
+    landing_tolerance: Res<'w, LandingTolerance>,
+    short_tap: Res<'w, ShortTapBehavior>,
+    platform_spawn_animation: Res<'w, PlatformSpawnAnimation>,
+}
+
+/// 可修改的玩法开关系统参数
+///
+/// 与[`GameplayToggles`]包含相同的开关，供设置界面按钮点击时修改，避免点击处理系统的参数过多
+#[derive(SystemParam)]
+pub struct GameplayTogglesMut<'w> {
+    show_target_marker: ResMut<'w, ShowTargetMarker>,
+    fade_transitions: ResMut<'w, FadeTransitions>,
+    safety_net: ResMut<'w, SafetyNet>,
+    fizzle_on_overcharge: ResMut<'w, FizzleOnOvercharge>,
+    auto_pause: ResMut<'w, AutoPause>,
+    score_color: ResMut<'w, ScoreColorProgression>,
+    combo_chains: ResMut<'w, ComboChains>,
+    tuning: ResMut<'w, PhysicsTuning>,
+    fall_vignette: ResMut<'w, FallVignette>,
+    pulse_next_platform: ResMut<'w, PulseNextPlatform>,
+    face_jump_direction: ResMut<'w, FaceJumpDirection>,
+    landing_tolerance: ResMut<'w, LandingTolerance>,
+    short_tap: ResMut<'w, ShortTapBehavior>,
+    platform_spawn_animation: ResMut<'w, PlatformSpawnAnimation>,
+}
+
+impl GameplayToggles<'_> {
+    /// 是否有开关在启动之后被修改过
+    fn changed(&self) -> bool {
+        changed_since_startup(&self.show_target_marker)
+            || changed_since_startup(&self.fade_transitions)
+            || changed_since_startup(&self.safety_net)
+            || changed_since_startup(&self.fizzle_on_overcharge)
+            || changed_since_startup(&self.auto_pause)
+            || changed_since_startup(&self.score_color)
+            || changed_since_startup(&self.combo_chains)
+            || changed_since_startup(&self.tuning)
+            || changed_since_startup(&self.fall_vignette)
+            || changed_since_startup(&self.pulse_next_platform)
+            || changed_since_startup(&self.face_jump_direction)
+            || changed_since_startup(&self.landing_tolerance)
+            || changed_since_startup(&self.short_tap)
+            || changed_since_startup(&self.platform_spawn_animation)
+    }
+}
+
+/// 画面设置系统参数
+///
+/// 把设置界面中的光照主题和影响渲染开销的选项组合在一起，避免保存系统和设置界面的参数过多
+#[derive(SystemParam)]
+pub struct GraphicsSettings<'w> {
+    lighting_theme: Res<'w, LightingTheme>,
+    particle_quality: Res<'w, ParticleQuality>,
+    msaa_level: Res<'w, MsaaLevel>,
+    frame_rate_limit: Res<'w, FrameRateLimit>,
+    shadow_quality: Res<'w, ShadowQuality>,
+    bloom_level: Res<'w, BloomLevel>,
+    platform_style: Res<'w, PlatformStyle>,
+}
+
+/// 可修改的画面设置系统参数
+///
+/// 与[`GraphicsSettings`]包含相同的选项，供设置界面按钮点击时修改，避免点击处理系统的参数过多
+#[derive(SystemParam)]
+pub struct GraphicsSettingsMut<'w> {
+    lighting_theme: ResMut<'w, LightingTheme>,
+    particle_quality: ResMut<'w, ParticleQuality>,
+    msaa_level: ResMut<'w, MsaaLevel>,
+    frame_rate_limit: ResMut<'w, FrameRateLimit>,
+    shadow_quality: ResMut<'w, ShadowQuality>,
+    bloom_level: ResMut<'w, BloomLevel>,
+    platform_style: ResMut<'w, PlatformStyle>,
+}
+
+impl GraphicsSettings<'_> {
+    /// 是否有画面设置在启动之后被修改过
+    fn changed(&self) -> bool {
+        changed_since_startup(&self.lighting_theme)
+            || changed_since_startup(&self.particle_quality)
+            || changed_since_startup(&self.msaa_level)
+            || changed_since_startup(&self.frame_rate_limit)
+            || changed_since_startup(&self.shadow_quality)
+            || changed_since_startup(&self.bloom_level)
+            || changed_since_startup(&self.platform_style)
+    }
+}
+
+/// 两次写入存档之间的默认最短间隔（秒）
+const SAVE_INTERVAL_SECS: f32 = 1.0;
+
+/// 存档待写入标记资源
+///
+/// 需要保存的数据变化时标记为待写入，距离上次写入超过最短间隔才写入存档文件，
+/// 短时间内连续修改设置只写入一次；退出游戏时立即写入尚未保存的修改
+#[derive(Debug, Resource)]
+pub struct SettingsDirty {
+    /// 是否有尚未写入存档的修改
+    pub dirty: bool,
+    /// 两次写入之间的最短间隔（秒）
+    pub interval: f32,
+    /// 距离允许下一次写入的剩余时间（秒）
+    cooldown: f32,
+}
+
+impl Default for SettingsDirty {
+    fn default() -> Self {
+        Self {
+            dirty: false,
+            interval: SAVE_INTERVAL_SECS,
+            cooldown: 0.0,
+        }
+    }
+}
+
+/// 需要持久化的全部数据系统参数
+///
+/// 把写入存档的设置、皮肤、最高分、排行榜、按键绑定等组合在一起，
+/// 统一判断是否有修改并生成存档数据，保存系统和退出时的写入共用
+#[derive(SystemParam)]
+pub struct PersistedState<'w> {
+    volume: Res<'w, Volume>,
+    muted: Res<'w, AudioMuted>,
+    high_score: Res<'w, HighScore>,
+    player_skin: Res<'w, PlayerSkin>,
+    toggles: GameplayToggles<'w>,
+    seen_tutorial: Res<'w, SeenTutorial>,
+    orthographic_camera: Res<'w, OrthographicCamera>,
+    daily_high_score: Res<'w, DailyHighScore>,
+    leaderboard: Res<'w, Leaderboard>,
+    input_bindings: Res<'w, InputBindings>,
+    graphics: GraphicsSettings<'w>,
+    colorblind_mode: Res<'w, ColorblindMode>,
+    best_run_ghost: Res<'w, BestRunGhost>,
+    time_attack_high_score: Res<'w, TimeAttackHighScore>,
+    forked_high_score: Res<'w, ForkedHighScore>,
+}
+
+impl PersistedState<'_> {
+    /// 是否有数据在系统上次运行之后被修改过，启动时插入资源不算修改
+    fn changed(&self) -> bool {
+        changed_since_startup(&self.volume)
+            || changed_since_startup(&self.muted)
+            || changed_since_startup(&self.high_score)
+            || changed_since_startup(&self.player_skin)
+            || self.toggles.changed()
+            || changed_since_startup(&self.seen_tutorial)
+            || changed_since_startup(&self.orthographic_camera)
+            || changed_since_startup(&self.daily_high_score)
+            || changed_since_startup(&self.leaderboard)
+            || changed_since_startup(&self.input_bindings)
+            || self.graphics.changed()
+            || changed_since_startup(&self.colorblind_mode)
+            || changed_since_startup(&self.best_run_ghost)
+            || changed_since_startup(&self.time_attack_high_score)
+            || changed_since_startup(&self.forked_high_score)
+    }
+
+    /// 生成当前的存档数据
+    fn save_data(&self) -> SaveData {
+        SaveData {
+            volume: self.volume.0,
+            audio_muted: self.muted.0,
+            high_score: self.high_score.0,
+            leaderboard: self.leaderboard.0.clone(),
+            daily_date_seed: self.daily_high_score.date_seed,
+            daily_high_score: self.daily_high_score.score,
+            skin: self.player_skin.index(),
+            show_target_marker: self.toggles.show_target_marker.0,
+            fade_transitions: self.toggles.fade_transitions.0,
+            safety_net: self.toggles.safety_net.0,
+            seen_tutorial: self.seen_tutorial.0,
+            orthographic_camera: self.orthographic_camera.0,
+            fizzle_on_overcharge: self.toggles.fizzle_on_overcharge.0,
+            lighting_theme: self.graphics.lighting_theme.index(),
+            input_bindings: self.input_bindings.clone(),
+            auto_pause: self.toggles.auto_pause.0,
+            particle_quality: self.graphics.particle_quality.index(),
+            msaa_level: self.graphics.msaa_level.index(),
+            score_color_progression: self.toggles.score_color.0,
+            combo_chains: self.toggles.combo_chains.0,
+            charge_curve: self.toggles.tuning.charge_curve.index(),
+            fall_vignette: self.toggles.fall_vignette.0,
+            pulse_next_platform: self.toggles.pulse_next_platform.0,
+            colorblind_mode: self.colorblind_mode.index(),
+            face_jump_direction: self.toggles.face_jump_direction.0,
+            frame_rate_limit: self.graphics.frame_rate_limit.index(),
+            best_run_ghosts: self.best_run_ghost.runs(),
+            landing_tolerance: self.toggles.landing_tolerance.0,
+            shadow_quality: self.graphics.shadow_quality.index(),
+            bloom_level: self.graphics.bloom_level.index(),
+            platform_style: self.graphics.platform_style.index(),
+            short_tap: self.toggles.short_tap.index(),
+            platform_spawn_animation: self.toggles.platform_spawn_animation.0,
+            time_attack_high_score: self.time_attack_high_score.0,
+            forked_high_score: self.forked_high_score.0,
+        }
+    }
+}
+
+/// 保存设置和最高分
+///
+/// 设置、皮肤或最高分发生变化时标记为待写入，距离上次写入超过最短间隔时才写入存档文件，
+/// 启动时插入资源不会触发写入
+pub fn save_settings(
+    persisted: PersistedState,
+    mut settings_dirty: ResMut<SettingsDirty>,
+    time: Res<Time<Real>>,
+) {
+    if persisted.changed() {
+        settings_dirty.dirty = true;
+    }
+    settings_dirty.cooldown = (settings_dirty.cooldown - time.delta_secs()).max(0.0);
+    if settings_dirty.dirty && settings_dirty.cooldown <= 0.0 {
+        persisted.save_data().save();
+        settings_dirty.dirty = false;
+        settings_dirty.cooldown = settings_dirty.interval;
+    }
+}
+
+/// 退出游戏时写入尚未保存的修改
+///
+/// 在`Last`阶段运行，保证同一帧中其他系统的修改和退出事件都已产生
+pub fn flush_settings_on_exit(
+    persisted: PersistedState,
+    mut settings_dirty: ResMut<SettingsDirty>,
+    mut exit_events: EventReader<AppExit>,
+) {
+    if exit_events.read().last().is_none() {
+        return;
+    }
+    if settings_dirty.dirty || persisted.changed() {
+        info!("Saving unsaved settings before exit");
+        persisted.save_data().save();
+        settings_dirty.dirty = false;
+    }
+}
@@ -1,7 +1,24 @@
+use bevy::diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin};
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
-use bevy::window::PrimaryWindow;
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy::ui::FocusPolicy;
+use bevy::window::{PrimaryWindow, WindowFocused};
 
-use crate::player::{JumpState, INITIAL_PLAYER_POS};
+use crate::audio::GameEvent;
+use crate::input::{ActionInput, InputAction, Rebinding};
+use crate::player::{
+    AccumulationSound, Accumulator, ChargeOvershoot, CountdownTimer, FallState, JumpGate,
+    JumpState, MaxCharge, Player, PlayerSkin, Retries, COUNTDOWN_SECS, OVERSHOOT_COLOR,
+    PLAYER_SKINS,
+};
+use crate::platform::{AimedPlatform, GameMode, NextPlatform, UtcDate, BONUS_PLATFORM_COLOR};
+use crate::replay::{ReplayButtonAction, ReplayPlayback};
+use crate::settings::{
+    AutoPause, ColorblindMode, ComboChains, FadeTransitions, FallVignette, SafetyNet,
+    SeenTutorial, Volume,
+};
 
 /// 游戏状态枚举，控制游戏流程的不同阶段
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Default, States)]
@@ -10,6 +27,87 @@ pub enum GameState {
     MainMenu,  // 主菜单界面
     Playing,   // 游戏进行中
     GameOver,  // 游戏结束界面
+    Settings,  // 设置界面
+    Leaderboard, // 排行榜界面
+    Controls,  // 按键设置界面
+}
+
+/// 游戏进行中的暂停状态，只在游戏进行状态下存在
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Default, SubStates)]
+#[source(GameState = GameState::Playing)]
+pub enum PauseState {
+    #[default]
+    Running, // 正常进行
+    Paused,  // 已暂停，游戏时间停止
+}
+
+/// 状态切换过渡阶段
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionPhase {
+    Idle,    // 没有进行中的过渡
+    FadeOut, // 淡出到黑屏，结束后切换状态
+    FadeIn,  // 从黑屏淡入
+}
+
+/// 状态切换过渡资源，驱动全屏遮罩的透明度
+#[derive(Debug, Resource)]
+pub struct Transition {
+    pub timer: Timer,                // 当前阶段的计时器
+    pub from: Option<GameState>,     // 正在离开的状态
+    pub to: Option<GameState>,       // 黑屏后要进入的状态
+    pub phase: TransitionPhase,      // 当前过渡阶段
+}
+
+impl Default for Transition {
+    fn default() -> Self {
+        Self {
+            timer: Timer::from_seconds(0.3, TimerMode::Once),
+            from: None,
+            to: None,
+            phase: TransitionPhase::Idle,
+        }
+    }
+}
+
+/// 标记状态切换全屏遮罩的组件
+#[derive(Debug, Component)]
+pub struct FadeOverlay;
+
+/// 状态切换系统参数
+/// 
+/// 统一处理游戏状态切换，开启过渡效果时先淡出再切换状态，关闭时立即切换
+#[derive(SystemParam)]
+pub struct StateTransition<'w> {
+    transition: ResMut<'w, Transition>,
+    game_state: Res<'w, State<GameState>>,
+    next_game_state: ResMut<'w, NextState<GameState>>,
+    fade_transitions: Res<'w, FadeTransitions>,
+}
+
+impl StateTransition<'_> {
+    /// 切换到指定的游戏状态
+    /// 
+    /// # 参数
+    /// - `to`: 要进入的游戏状态
+    pub fn go(&mut self, to: GameState) {
+        if !self.fade_transitions.0 {
+            self.next_game_state.set(to);
+            return;
+        }
+        // 已经在淡出时只更新目标状态
+        if self.transition.phase != TransitionPhase::FadeOut {
+            // 淡入过程中再次切换时，从当前遮罩透明度继续淡出，避免画面跳变
+            let elapsed = match self.transition.phase {
+                TransitionPhase::FadeIn => self.transition.timer.remaining(),
+                _ => std::time::Duration::ZERO,
+            };
+            self.transition.timer.reset();
+            self.transition.timer.set_elapsed(elapsed);
+            self.transition.phase = TransitionPhase::FadeOut;
+            self.transition.from = Some(self.game_state.get().clone());
+        }
+        self.transition.to = Some(to);
+    }
 }
 
 /// 游戏音效资源，管理所有游戏中的音频文件
@@ -19,20 +117,52 @@ pub struct GameSounds {
     pub accumulation: Handle<AudioSource>, // 蓄力音效
     pub fall: Handle<AudioSource>,         // 摔落音效
     pub success: Handle<AudioSource>,      // 成功跳跃音效
+    pub score: Handle<AudioSource>,        // 得分音效
+    pub music: Handle<AudioSource>,        // 背景音乐
 }
 
+/// 背景音乐组件标记
+#[derive(Debug, Component)]
+pub struct BackgroundMusic;
+
 /// 菜单按钮功能组件，定义按钮的点击行为
 #[derive(Component)]
 pub enum MenuButtonAction {
     StartGame,       // 开始游戏
     RestartGame,     // 重新开始游戏
     BackToMainMenu,  // 返回主菜单
+    Settings,        // 打开设置界面
+    DailyChallenge,  // 开始每日挑战
+    Leaderboard,     // 打开排行榜界面
+    Controls,        // 打开按键设置界面
+    BackToSettings,  // 返回设置界面
+    Practice,        // 开始练习模式
+    TimeAttack,      // 开始限时挑战
+    ForkedPaths,     // 开始分岔路径挑战
+    Quit,            // 打开退出确认对话框
+    ConfirmQuit,     // 确认退出游戏
+    CancelQuit,      // 取消退出，关闭确认对话框
 }
 
 /// 标记主菜单界面元素的组件
 #[derive(Component)]
 pub struct OnMainMenuScreen;
 
+/// 标记退出确认对话框的组件
+#[derive(Component)]
+pub struct QuitConfirmDialog;
+
+/// 主菜单皮肤选择按钮功能组件
+#[derive(Component)]
+pub enum SkinButtonAction {
+    Previous, // 上一个皮肤
+    Next,     // 下一个皮肤
+}
+
+/// 标记皮肤名称文本的组件
+#[derive(Component)]
+pub struct SkinText;
+
 /// 标记游戏结束菜单界面元素的组件
 #[derive(Component)]
 pub struct OnGameOverMenuScreen;
@@ -41,23 +171,350 @@ pub struct OnGameOverMenuScreen;
 #[derive(Debug, Resource)]
 pub struct Score(pub u32);
 
+/// 连续完美落地次数资源，普通落地或重新开始时清零
+#[derive(Debug, Default, Resource)]
+pub struct Combo(pub u32);
+
+/// 练习模式资源，从主菜单的练习按钮进入时为true
+/// 
+/// 练习模式中摔落总是回到当前平台重试，不显示分数，也不记录最高分和排行榜
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Resource)]
+pub struct PracticeMode(pub bool);
+
+/// 本局统计资源，在游戏结束界面显示，重新开始时清零
+#[derive(Debug, Default, Resource)]
+pub struct RunStats {
+    pub jumps: u32,            // 跳跃次数，包括摔落的跳跃
+    pub perfect_landings: u32, // 完美落地次数
+    pub best_combo: u32,       // 最长连续完美落地次数
+}
+
+/// 连击加速时每次连续完美落地增加的节奏倍率
+const CHAIN_SPEED_STEP: f32 = 0.08;
+
+/// 连击加速最多累计的连续完美落地次数，超过后节奏不再加快
+const CHAIN_SPEED_MAX_CHAIN: u32 = 6;
+
+/// 连击加速资源，记录开启连击加速后未被打断的连续完美落地次数
+///
+/// 连击期间跳跃动画和相机跟随按倍率加快，非完美落地、摔落或重新开始时清零
+#[derive(Debug, Default, Resource)]
+pub struct ChainSpeed(pub u32);
+
+impl ChainSpeed {
+    /// 当前节奏倍率，没有连击时为1.0
+    pub fn multiplier(&self) -> f32 {
+        1.0 + CHAIN_SPEED_STEP * self.0.min(CHAIN_SPEED_MAX_CHAIN) as f32
+    }
+}
+
+/// 最高分资源，跨局保留并写入存档
+#[derive(Debug, Resource)]
+pub struct HighScore(pub u32);
+
+/// 每日挑战最高分资源，与无尽模式最高分分开保存
+#[derive(Debug, Resource)]
+pub struct DailyHighScore {
+    pub date_seed: u64, // 最高分所属日期的种子，日期变化后重新计算
+    pub score: u32,     // 当天的最高分
+}
+
+/// 限时挑战最高分资源，与无尽模式最高分分开保存
+#[derive(Debug, Resource)]
+pub struct TimeAttackHighScore(pub u32);
+
+/// 分岔路径挑战最高分资源，与无尽模式最高分分开保存，不计入无尽模式排行榜
+#[derive(Debug, Resource)]
+pub struct ForkedHighScore(pub u32);
+
+/// 限时挑战每局的时长（秒）
+pub const TIME_ATTACK_SECS: f32 = 60.0;
+
+/// 限时挑战计时资源，只在限时挑战模式中计时，其他模式不使用
+/// 
+/// 开局倒计时和入场动画结束后开始计时，暂停时停止，蓄力和跳跃过程中持续计时
+#[derive(Debug, Resource)]
+pub struct TimeAttack {
+    pub remaining: Timer, // 本局剩余时间计时器
+}
+
+impl Default for TimeAttack {
+    fn default() -> Self {
+        Self {
+            remaining: Timer::from_seconds(TIME_ATTACK_SECS, TimerMode::Once),
+        }
+    }
+}
+
+/// 排行榜最多保留的分数条数
+pub const LEADERBOARD_SIZE: usize = 10;
+
+/// 无尽模式本地排行榜资源，按分数从高到低保存前10名，跨局保留并写入存档
+#[derive(Debug, Default, Resource)]
+pub struct Leaderboard(pub Vec<u32>);
+
+impl Leaderboard {
+    /// 从存档的分数列表创建排行榜
+    ///
+    /// 存档可能被手动修改，重新排序并截断以保证顺序和长度正确
+    ///
+    /// # 参数
+    /// - `scores`: 存档中的分数列表
+    pub fn from_scores(mut scores: Vec<u32>) -> Self {
+        scores.sort_unstable_by(|a, b| b.cmp(a));
+        scores.truncate(LEADERBOARD_SIZE);
+        Self(scores)
+    }
+
+    /// 按从高到低的顺序插入新分数，超出的条目被移除
+    ///
+    /// # 参数
+    /// - `score`: 本局分数
+    ///
+    /// # 返回值
+    /// 新分数在排行榜中的名次（从0开始），没有进入排行榜时返回None
+    pub fn insert(&mut self, score: u32) -> Option<usize> {
+        // 同分时新分数排在旧分数之后
+        let rank = self.0.partition_point(|&entry| entry >= score);
+        if rank >= LEADERBOARD_SIZE {
+            return None;
+        }
+        self.0.insert(rank, score);
+        self.0.truncate(LEADERBOARD_SIZE);
+        Some(rank)
+    }
+}
+
+/// 标记排行榜界面元素的组件
+#[derive(Component)]
+pub struct OnLeaderboardScreen;
+
+/// 标记每日挑战日期文本的组件
+#[derive(Debug, Component)]
+pub struct OnDailyLabel;
+
+/// 标记练习模式提示界面元素的组件
+#[derive(Debug, Component)]
+pub struct OnPracticeLabel;
+
+/// 标记限时挑战剩余时间文本的组件
+#[derive(Debug, Component)]
+pub struct OnTimeAttackLabel;
+
+/// 文字按钮的背景颜色
+pub const TEXT_BUTTON_COLOR: Color = Color::srgb(0.35, 0.35, 0.45);
+
+/// 本帧交互状态发生变化的按钮查询，`T`为按钮功能组件
+pub type ButtonInteractions<'w, 's, T> =
+    Query<'w, 's, (&'static Interaction, &'static T), (Changed<Interaction>, With<Button>)>;
+
 /// 标记分数显示文本的组件
 #[derive(Debug, Component)]
 pub struct Scoreboard;
 
-/// 飘分效果队列资源，存储待显示的飘分事件
+/// 标记计分板根节点的组件
+#[derive(Debug, Component)]
+pub struct OnScoreboard;
+
+/// 分数里程碑间隔资源，分数每越过一次该值的整数倍就庆祝一次
 #[derive(Debug, Resource)]
-pub struct ScoreUpQueue(pub Vec<ScoreUpEvent>);
+pub struct MilestoneInterval(pub u32);
+
+impl Default for MilestoneInterval {
+    fn default() -> Self {
+        Self(10)
+    }
+}
+
+/// 分数里程碑事件，在达到里程碑的那次跳跃落地时发送
+#[derive(Debug, Clone, Event)]
+pub struct MilestoneEvent {
+    pub pos: Vec3, // 落地位置，用于生成庆祝粒子
+}
+
+/// 计分板缩放脉冲组件，计时结束后移除
+#[derive(Debug, Component)]
+pub struct MilestonePulse(pub Timer);
+
+/// 计分板脉冲动画时长（秒）
+const MILESTONE_PULSE_SECS: f32 = 0.5;
+
+/// 标记剩余重试次数文本的组件
+#[derive(Debug, Component)]
+pub struct RetriesText;
+
+/// 标记开局倒计时文本的组件
+#[derive(Debug, Component)]
+pub struct OnCountdown;
+
+/// 完美落地闪白计时器资源，计时期间屏幕遮罩透明度逐渐衰减
+#[derive(Debug, Resource)]
+pub struct PerfectFlash(pub Timer);
+
+impl Default for PerfectFlash {
+    fn default() -> Self {
+        // 初始为已结束状态，不显示闪白
+        let mut timer = Timer::from_seconds(0.25, TimerMode::Once);
+        timer.tick(timer.duration());
+        Self(timer)
+    }
+}
+
+/// 完美落地闪白的最大透明度
+const PERFECT_FLASH_ALPHA: f32 = 0.35;
+
+/// 标记完美落地闪白遮罩的组件
+#[derive(Debug, Component)]
+pub struct OnPerfectFlash;
+
+/// 标记摔落暗角遮罩的组件
+#[derive(Debug, Component)]
+pub struct OnFallVignette;
+
+/// 摔落暗角的最大透明度，保持克制，不遮挡摔落动画
+const FALL_VIGNETTE_MAX_ALPHA: f32 = 0.6;
+
+/// 摔落暗角透明度趋近目标值的速度
+const FALL_VIGNETTE_FADE_SPEED: f32 = 6.0;
+
+/// 摔落暗角纹理的边长（像素），线性采样拉伸到全屏
+const FALL_VIGNETTE_TEXTURE_SIZE: u32 = 64;
+
+/// 标记新手引导提示文本的组件
+#[derive(Debug, Component)]
+pub struct OnTutorialHint;
+
+/// 标记蓄力条界面元素的组件
+#[derive(Debug, Component)]
+pub struct OnChargeBar;
+
+/// 标记蓄力条背景（带边框）的组件
+#[derive(Debug, Component)]
+pub struct ChargeBarBackground;
+
+/// 标记蓄力条填充部分的组件
+#[derive(Debug, Component)]
+pub struct ChargeBarFill;
+
+/// 标记下一个平台屏幕外指示箭头的组件
+#[derive(Debug, Component)]
+pub struct OnNextPlatformArrow;
+
+/// 下一个平台指示箭头的边长（像素）
+const NEXT_PLATFORM_ARROW_SIZE: f32 = 24.0;
+
+/// 下一个平台指示箭头与窗口边缘的距离（像素）
+const NEXT_PLATFORM_ARROW_MARGIN: f32 = 40.0;
+
+/// 平台距离窗口边缘小于该值（像素）时视为不容易看到，显示指示箭头
+const NEXT_PLATFORM_ON_SCREEN_MARGIN: f32 = 60.0;
+
+/// 标记调试信息文本的组件，按F3显示或隐藏
+#[derive(Debug, Component)]
+pub struct DebugOverlay;
 
-/// 飘分事件结构，包含飘分起始位置信息
-#[derive(Debug)]
+/// 得分事件，包含飘分起始位置和本次得分
+#[derive(Debug, Clone, Event)]
 pub struct ScoreUpEvent {
     pub landing_pos: Vec3, // 着陆位置，用于显示飘分效果
+    pub points: u32,       // 本次得分
+    pub combo: u32,        // 得分后的连续完美落地次数，用于提高得分音效的音调
+    pub bonus: bool,       // 是否落在双倍得分平台上，飘分显示为金色
+}
+
+/// 计分系统参数
+/// 
+/// 统一处理得分、连续完美落地计数、连击加速、本局统计和飘分事件；
+/// 这些资源只在落地计分时一起修改，组合成一个参数使`player_jump`不超过Bevy系统参数数量上限
+#[derive(SystemParam)]
+pub struct ScoreKeeper<'w> {
+    score: ResMut<'w, Score>,
+    combo: ResMut<'w, Combo>,
+    run_stats: ResMut<'w, RunStats>,
+    chain_speed: ResMut<'w, ChainSpeed>,
+    combo_chains: Res<'w, ComboChains>,
+    score_up_events: EventWriter<'w, ScoreUpEvent>,
+}
+
+impl ScoreKeeper<'_> {
+    /// 记录一次成功落在下一个平台上的得分
+    /// 
+    /// 每次落地得1分，落在双倍得分平台上时得2分；完美落地不额外加分，只累计连续完美落地次数
+    /// 
+    /// # 参数
+    /// - `perfect`: 是否为完美落地
+    /// - `bonus`: 是否落在双倍得分平台上
+    /// - `landing_pos`: 落地点位置，用于显示飘分效果
+    /// 
+    /// # 返回值
+    /// 本次得分
+    pub fn land(&mut self, perfect: bool, bonus: bool, landing_pos: Vec3) -> u32 {
+        if perfect {
+            self.combo.0 += 1;
+            info!("Perfect landing! Combo: {}", self.combo.0);
+            self.run_stats.perfect_landings += 1;
+            self.run_stats.best_combo = self.run_stats.best_combo.max(self.combo.0);
+            // 开启连击加速时，连击越长节奏越快
+            if self.combo_chains.0 {
+                self.chain_speed.0 += 1;
+            }
+        } else {
+            self.combo.0 = 0;
+            self.break_chain();
+        }
+        let points = if bonus {
+            info!("Bonus platform, points doubled");
+            2
+        } else {
+            1
+        };
+        self.score.0 += points;
+
+        // 发送得分事件
+        self.score_up_events.send(ScoreUpEvent {
+            landing_pos: Vec3::new(landing_pos.x, landing_pos.y + 0.5, landing_pos.z),
+            points,
+            combo: self.combo.0,
+            bonus,
+        });
+        points
+    }
+
+    /// 重置分数、连击、连击加速和本局统计，新一局或演示开始时调用
+    pub fn reset(&mut self) {
+        self.score.0 = 0;
+        self.combo.0 = 0;
+        self.chain_speed.0 = 0;
+        *self.run_stats = RunStats::default();
+    }
+
+    /// 记录一次跳跃，无论是否成功落地
+    pub fn count_jump(&mut self) {
+        self.run_stats.jumps += 1;
+    }
+
+    /// 打断连击加速，节奏恢复正常
+    ///
+    /// 非完美落地和摔落时调用
+    pub fn break_chain(&mut self) {
+        if self.chain_speed.0 > 0 {
+            info!("Chain broken after {} perfect landings", self.chain_speed.0);
+            self.chain_speed.0 = 0;
+        }
+    }
+
+    /// 当前连击加速的节奏倍率
+    pub fn chain_speed(&self) -> f32 {
+        self.chain_speed.multiplier()
+    }
 }
 
 /// 飘分效果组件，控制分数向上飘的动画效果
 #[derive(Debug, Component)]
-pub struct ScoreUpEffect(pub Vec3); // 当前飘分位置
+pub struct ScoreUpEffect {
+    pub pos: Vec3,    // 当前飘分位置
+    pub start_y: f32, // 飘分起始高度，平台高低不同时按起始高度计算飘动距离
+}
 
 /// 加载并设置游戏音效资源
 /// 
@@ -68,13 +525,199 @@ pub fn setup_game_sounds(mut commands: Commands, asset_server: Res<AssetServer>)
         accumulation: asset_server.load("sounds/accumulation.mp3"),
         fall: asset_server.load("sounds/fall.mp3"),
         success: asset_server.load("sounds/success.mp3"),
+        score: asset_server.load("sounds/score.wav"),
+        music: asset_server.load("sounds/music.wav"),
+    });
+}
+
+/// 播放背景音乐
+/// 
+/// 进入游戏时循环播放背景音乐；如果游戏结束后很快重新开始，则从暂停处继续播放而不是从头开始
+pub fn play_background_music(
+    mut commands: Commands,
+    game_sounds: Res<GameSounds>,
+    volume: Res<Volume>,
+    q_background_music: Query<Option<&AudioSink>, With<BackgroundMusic>>,
+) {
+    match q_background_music.get_single() {
+        // 已有背景音乐，继续播放
+        Ok(Some(sink)) => sink.play(),
+        // 背景音乐实体已创建但音频尚未加载完成，加载后会自动播放
+        Ok(None) => {}
+        // 没有背景音乐，创建新的循环播放实体
+        Err(_) => {
+            commands.spawn((
+                BackgroundMusic, // 标记为背景音乐
+                AudioPlayer(game_sounds.music.clone()), // 背景音乐资源
+                volume.apply(PlaybackSettings::LOOP), // 循环播放设置
+            ));
+        }
+    }
+}
+
+/// 暂停背景音乐
+/// 
+/// 退出游戏进行状态时暂停背景音乐，返回主菜单时才真正销毁
+pub fn pause_background_music(q_background_music: Query<&AudioSink, With<BackgroundMusic>>) {
+    for sink in &q_background_music {
+        sink.pause();
+    }
+}
+
+/// 标记暂停提示界面元素的组件
+#[derive(Component)]
+pub struct OnPauseScreen;
+
+/// 按暂停键（默认Esc）暂停或继续游戏
+/// 
+/// 状态过渡进行中不响应，避免在淡入淡出时暂停
+pub fn toggle_pause(
+    input: ActionInput,
+    pause_state: Res<State<PauseState>>,
+    mut next_pause_state: ResMut<NextState<PauseState>>,
+) {
+    if !input.just_pressed(InputAction::Pause) {
+        return;
+    }
+    next_pause_state.set(match pause_state.get() {
+        PauseState::Running => PauseState::Paused,
+        PauseState::Paused => PauseState::Running,
     });
 }
 
+/// 窗口失去焦点时自动暂停
+/// 
+/// 开启自动暂停设置时，游戏进行中失去焦点会暂停游戏，重新获得焦点后继续；
+/// 玩家手动暂停的游戏不会因为重新获得焦点而继续
+pub fn auto_pause_on_focus_change(
+    mut focus_events: EventReader<WindowFocused>,
+    auto_pause: Res<AutoPause>,
+    pause_state: Res<State<PauseState>>,
+    mut next_pause_state: ResMut<NextState<PauseState>>,
+    mut auto_paused: Local<bool>,
+) {
+    // 一帧内可能收到多个焦点事件，只处理最后一个
+    let Some(focused) = focus_events.read().last().map(|event| event.focused) else {
+        return;
+    };
+    match (focused, pause_state.get()) {
+        (false, PauseState::Running) if auto_pause.0 => {
+            info!("Window lost focus, auto pause");
+            next_pause_state.set(PauseState::Paused);
+            *auto_paused = true;
+        }
+        (true, PauseState::Paused) if *auto_paused => {
+            info!("Window regained focus, resume");
+            next_pause_state.set(PauseState::Running);
+            *auto_paused = false;
+        }
+        // 手动继续或其他情况，清除自动暂停记录
+        (true, _) => *auto_paused = false,
+        _ => {}
+    }
+}
+
+/// 暂停游戏
+/// 
+/// 停止游戏时间并暂停背景音乐；未完成的蓄力会被取消，继续游戏后需要重新按下
+pub fn pause_game(
+    mut commands: Commands,
+    mut time: ResMut<Time<Virtual>>,
+    mut accumulator: ResMut<Accumulator>,
+    q_background_music: Query<&AudioSink, With<BackgroundMusic>>,
+    q_accumulation_sound: Query<Entity, With<AccumulationSound>>,
+    input: ActionInput,
+) {
+    info!("Game paused");
+    time.pause();
+    accumulator.0 = None;
+    for entity in &q_accumulation_sound {
+        commands.entity(entity).despawn();
+    }
+    for sink in &q_background_music {
+        sink.pause();
+    }
+
+    // 暂停提示
+    commands.spawn((
+        Text::new(format!("Paused\n[{}] Resume", input.label(InputAction::Pause))),
+        TextColor(Color::BLACK), // 文本颜色
+        TextFont { // 文本字体设置
+            font_size: 50.0,
+            ..default()
+        },
+        TextLayout::new_with_justify(JustifyText::Center), // 文本居中
+        Node { // 位置设置，水平居中于屏幕中部
+            position_type: PositionType::Absolute,
+            top: Val::Percent(40.0),
+            width: Val::Percent(100.0),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(1.0, 1.0, 1.0, 0.4)), // 半透明背景
+        OnPauseScreen, // 标记为暂停提示元素
+    ));
+}
+
+/// 继续游戏
+/// 
+/// 恢复游戏时间；离开游戏进行状态时也会调用，背景音乐只在仍处于游戏中时恢复
+pub fn resume_game(
+    mut time: ResMut<Time<Virtual>>,
+    game_state: Res<State<GameState>>,
+    q_background_music: Query<&AudioSink, With<BackgroundMusic>>,
+) {
+    info!("Game resumed");
+    time.unpause();
+    if *game_state.get() == GameState::Playing {
+        for sink in &q_background_music {
+            sink.play();
+        }
+    }
+}
+
+/// 生成文字按钮
+/// 
+/// 用于没有对应图片资源的按钮，按钮功能由`action`组件决定
+/// 
+/// # 参数
+/// - `parent`: 父节点构建器
+/// - `label`: 按钮文字
+/// - `action`: 按钮功能组件
+pub fn spawn_text_button(parent: &mut ChildBuilder, label: &str, action: impl Component) {
+    parent
+        .spawn((
+            Button, // 按钮交互组件
+            Node { // 按钮样式
+                min_width: Val::Px(40.),
+                height: Val::Px(40.),
+                padding: UiRect::horizontal(Val::Px(12.0)),
+                margin: UiRect::all(Val::Px(6.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(TEXT_BUTTON_COLOR),
+            BorderRadius::all(Val::Px(8.0)),
+            action, // 按钮功能标记
+        ))
+        .with_child((
+            Text::new(label),
+            TextColor(Color::WHITE),
+            TextFont {
+                font_size: 24.0,
+                ..default()
+            },
+        ));
+}
+
 /// 设置主菜单界面
 /// 
-/// 创建主菜单布局，包含游戏标题和开始按钮
-pub fn setup_main_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
+/// 创建主菜单布局，包含游戏标题、开始按钮和皮肤选择
+pub fn setup_main_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    player_skin: Res<PlayerSkin>,
+) {
     commands
         .spawn((
             Node { // 主容器节点，全屏覆盖
@@ -113,60 +756,335 @@ pub fn setup_main_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
                         ImageNode::new(asset_server.load("image/btn_start.png").into()),
                         MenuButtonAction::StartGame, // 按钮功能标记
                     ));
+
+                    // 每日挑战按钮
+                    spawn_text_button(parent, "Daily Challenge", MenuButtonAction::DailyChallenge);
+
+                    // 练习模式按钮
+                    spawn_text_button(parent, "Practice", MenuButtonAction::Practice);
+
+                    // 限时挑战按钮
+                    spawn_text_button(parent, "Time Attack", MenuButtonAction::TimeAttack);
+
+                    // 分岔路径挑战按钮
+                    spawn_text_button(parent, "Forked Paths", MenuButtonAction::ForkedPaths);
+
+                    // 皮肤选择行
+                    parent
+                        .spawn((Node { // 水平排列的皮肤选择容器
+                            flex_direction: FlexDirection::Row,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },))
+                        .with_children(|parent| {
+                            spawn_text_button(parent, "<", SkinButtonAction::Previous);
+                            parent.spawn((
+                                Text::new(player_skin.name()),
+                                TextColor(Color::BLACK),
+                                TextFont {
+                                    font_size: 30.0,
+                                    ..default()
+                                },
+                                Node {
+                                    min_width: Val::Px(120.0),
+                                    justify_content: JustifyContent::Center,
+                                    ..default()
+                                },
+                                TextLayout::new_with_justify(JustifyText::Center),
+                                SkinText, // 标记为皮肤名称文本
+                            ));
+                            spawn_text_button(parent, ">", SkinButtonAction::Next);
+                        });
+
+                    // 录像回放按钮，网页版无法读写文件时不显示
+                    if !cfg!(target_arch = "wasm32") {
+                        spawn_text_button(parent, "Watch Replay", ReplayButtonAction::Watch);
+                    }
+
+                    // 排行榜按钮
+                    spawn_text_button(parent, "Leaderboard", MenuButtonAction::Leaderboard);
+
+                    // 设置按钮
+                    spawn_text_button(parent, "Settings", MenuButtonAction::Settings);
+
+                    // 退出按钮，网页版无法退出程序时不显示
+                    if !cfg!(target_arch = "wasm32") {
+                        spawn_text_button(parent, "Quit", MenuButtonAction::Quit);
+                    }
                 });
         });
 }
 
-/// 设置游戏结束菜单界面
+/// 生成退出确认对话框
 /// 
-/// 创建游戏结束布局，包含标题、返回按钮和重新开始按钮
-pub fn setup_game_over_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
+/// 全屏半透明遮罩挡住主菜单按钮，避免误触后直接关闭游戏；
+/// 同时标记为主菜单元素，离开主菜单时一并移除
+fn spawn_quit_confirm_dialog(commands: &mut Commands) {
     commands
         .spawn((
-            Node { // 主容器节点，全屏覆盖
+            Node { // 全屏遮罩，内容居中
                 width: Val::Percent(100.),
                 height: Val::Percent(100.),
+                position_type: PositionType::Absolute,
                 align_items: AlignItems::Center,
                 justify_content: JustifyContent::Center,
                 ..default()
             },
-            OnGameOverMenuScreen, // 标记为属于游戏结束菜单的元素
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.5)), // 半透明黑色遮罩
+            FocusPolicy::Block, // 阻止点击穿透到下层的主菜单按钮
+            GlobalZIndex(40), // 显示在主菜单之上、状态切换遮罩之下
+            QuitConfirmDialog, // 标记为退出确认对话框
+            OnMainMenuScreen, // 标记为属于主菜单的元素
         ))
         .with_children(|parent| {
             parent
-                .spawn((Node { // 垂直排列的内容容器
-                    flex_direction: FlexDirection::Column,
-                    align_items: AlignItems::Center,
-                    ..default()
-                },))
+                .spawn((
+                    Node { // 对话框面板
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        padding: UiRect::all(Val::Px(20.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::WHITE),
+                    BorderRadius::all(Val::Px(12.0)),
+                ))
                 .with_children(|parent| {
-                    // 标题
-                    parent.spawn((ImageNode::new(asset_server.load("image/title.png")),));
+                    // 提示文本
+                    parent.spawn((
+                        Text::new("Quit the game?"),
+                        TextColor(Color::BLACK),
+                        TextFont {
+                            font_size: 30.0,
+                            ..default()
+                        },
+                    ));
 
+                    // 确认和取消按钮
                     parent
                         .spawn((Node { // 水平排列的按钮容器
                             flex_direction: FlexDirection::Row,
-                            align_items: AlignItems::Center,
+                            margin: UiRect::top(Val::Px(10.0)),
                             ..default()
                         },))
                         .with_children(|parent| {
-                            // 返回按钮
-                            parent.spawn((
-                                Button, // 按钮交互组件
-                                Node { // 按钮样式
-                                    width: Val::Px(40.),
-                                    height: Val::Px(40.),
-                                    margin: UiRect::all(Val::Px(10.0)),
-                                    justify_content: JustifyContent::Center,
-                                    align_items: AlignItems::Center,
-                                    ..default()
-                                },
-                                ImageNode::new(asset_server.load("image/btn_home.png")),
-                                MenuButtonAction::BackToMainMenu, // 按钮功能标记
-                            ));
+                            spawn_text_button(parent, "Quit", MenuButtonAction::ConfirmQuit);
+                            spawn_text_button(parent, "Cancel", MenuButtonAction::CancelQuit);
+                        });
+                });
+        });
+}
 
-                            // 重新开始按钮
-                            parent.spawn((
+/// 键盘菜单导航的焦点资源，记录当前获得焦点的按钮，未用键盘导航时为`None`
+#[derive(Debug, Default, Resource)]
+pub struct MenuFocus(pub Option<Entity>);
+
+/// 获得焦点的按钮的描边颜色
+const MENU_FOCUS_OUTLINE_COLOR: Color = Color::srgb(1.0, 0.85, 0.2);
+
+/// 键盘菜单导航
+/// 
+/// 上下方向键或Tab（Shift+Tab反向）按从上到下、从左到右的顺序在当前界面的按钮之间移动焦点，
+/// Enter或空格把焦点按钮标记为按下，复用各界面按钮原有的点击处理。
+/// 在`PreUpdate`中UI交互检测之后运行，模拟的按下在下一帧恢复，避免按钮一直处于按下状态；
+/// 退出确认对话框打开时只在对话框内移动焦点，等待绑定新按键时不响应
+pub fn navigate_menu_with_keyboard(
+    keys: Res<ButtonInput<KeyCode>>,
+    rebinding: Res<Rebinding>,
+    mut menu_focus: ResMut<MenuFocus>,
+    mut q_buttons: Query<
+        (Entity, &GlobalTransform, &InheritedVisibility, &mut Interaction),
+        With<Button>,
+    >,
+    q_quit_dialog: Query<Entity, With<QuitConfirmDialog>>,
+    q_parents: Query<&Parent>,
+    mut pressed: Local<Option<Entity>>,
+) {
+    // 恢复上一帧模拟按下的按钮
+    if let Some(entity) = pressed.take() {
+        if let Ok((.., mut interaction)) = q_buttons.get_mut(entity) {
+            if *interaction == Interaction::Pressed {
+                *interaction = Interaction::None;
+            }
+        }
+    }
+    // 等待绑定新按键时，按下的键会被绑定到操作上，不用于导航
+    if rebinding.0.is_some() {
+        return;
+    }
+
+    // 当前界面可见的按钮，按屏幕位置从上到下、从左到右排序
+    let quit_dialog = q_quit_dialog.iter().next();
+    let mut buttons: Vec<(Entity, Vec3)> = q_buttons
+        .iter()
+        .filter(|(_, _, visibility, _)| visibility.get())
+        .filter(|(entity, ..)| {
+            quit_dialog.is_none_or(|dialog| {
+                q_parents.iter_ancestors(*entity).any(|ancestor| ancestor == dialog)
+            })
+        })
+        .map(|(entity, transform, ..)| (entity, transform.translation()))
+        .collect();
+    buttons.sort_by(|(_, a), (_, b)| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)));
+
+    // 焦点按钮已经不在当前界面中（例如切换界面或打开对话框）时清除焦点
+    let focus_index = menu_focus
+        .0
+        .and_then(|focus| buttons.iter().position(|(entity, _)| *entity == focus));
+    if focus_index.is_none() && menu_focus.0.is_some() {
+        menu_focus.0 = None;
+    }
+
+    // 移动焦点，没有焦点时向后移动聚焦第一个按钮，向前移动聚焦最后一个按钮
+    let shift = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    let tab = keys.just_pressed(KeyCode::Tab);
+    let step = if keys.just_pressed(KeyCode::ArrowDown) || (tab && !shift) {
+        1
+    } else if keys.just_pressed(KeyCode::ArrowUp) || (tab && shift) {
+        -1
+    } else {
+        0
+    };
+    if step != 0 && !buttons.is_empty() {
+        let len = buttons.len() as isize;
+        let next = match focus_index {
+            Some(index) => (index as isize + step).rem_euclid(len),
+            None if step > 0 => 0,
+            None => len - 1,
+        };
+        menu_focus.0 = Some(buttons[next as usize].0);
+    }
+
+    // 激活焦点按钮
+    if keys.any_just_pressed([KeyCode::Enter, KeyCode::NumpadEnter, KeyCode::Space]) {
+        if let Some(focus) = menu_focus.0 {
+            if let Ok((.., mut interaction)) = q_buttons.get_mut(focus) {
+                *interaction = Interaction::Pressed;
+                *pressed = Some(focus);
+            }
+        }
+    }
+}
+
+/// 给获得焦点的按钮添加描边，其余按钮移除描边
+pub fn update_menu_focus_outline(
+    mut commands: Commands,
+    menu_focus: Res<MenuFocus>,
+    q_outlined: Query<Entity, (With<Button>, With<Outline>)>,
+) {
+    if !menu_focus.is_changed() {
+        return;
+    }
+    for entity in &q_outlined {
+        if Some(entity) != menu_focus.0 {
+            commands.entity(entity).remove::<Outline>();
+        }
+    }
+    if let Some(mut focused) = menu_focus.0.and_then(|focus| commands.get_entity(focus)) {
+        focused.insert(Outline::new(Val::Px(3.0), Val::Px(2.0), MENU_FOCUS_OUTLINE_COLOR));
+    }
+}
+
+/// 切换游戏状态时清除焦点，进入新界面后按导航键从第一个按钮开始
+pub fn reset_menu_focus(mut menu_focus: ResMut<MenuFocus>) {
+    menu_focus.0 = None;
+}
+
+/// 主菜单中按Esc打开或关闭退出确认对话框
+/// 
+/// 网页版无法退出程序，不响应
+pub fn toggle_quit_confirm(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    q_dialog: Query<Entity, With<QuitConfirmDialog>>,
+) {
+    if cfg!(target_arch = "wasm32") || !keys.just_pressed(KeyCode::Escape) {
+        return;
+    }
+    if q_dialog.is_empty() {
+        spawn_quit_confirm_dialog(&mut commands);
+    } else {
+        for entity in &q_dialog {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+/// 设置游戏结束菜单界面
+/// 
+/// 创建游戏结束布局，包含标题、本局统计、返回按钮和重新开始按钮
+pub fn setup_game_over_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    score: Res<Score>,
+    run_stats: Res<RunStats>,
+) {
+    commands
+        .spawn((
+            Node { // 主容器节点，全屏覆盖
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            OnGameOverMenuScreen, // 标记为属于游戏结束菜单的元素
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((Node { // 垂直排列的内容容器
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },))
+                .with_children(|parent| {
+                    // 标题
+                    parent.spawn((ImageNode::new(asset_server.load("image/title.png")),));
+
+                    // 本局统计
+                    parent.spawn((
+                        Text::new(format!(
+                            "Score: {}\nJumps: {}\nPerfect landings: {}\nBest combo: {}",
+                            score.0,
+                            run_stats.jumps,
+                            run_stats.perfect_landings,
+                            run_stats.best_combo,
+                        )),
+                        TextColor(Color::BLACK),
+                        TextFont {
+                            font_size: 28.0,
+                            ..default()
+                        },
+                        TextLayout::new_with_justify(JustifyText::Center),
+                        Node {
+                            margin: UiRect::all(Val::Px(10.0)),
+                            ..default()
+                        },
+                    ));
+
+                    parent
+                        .spawn((Node { // 水平排列的按钮容器
+                            flex_direction: FlexDirection::Row,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },))
+                        .with_children(|parent| {
+                            // 返回按钮
+                            parent.spawn((
+                                Button, // 按钮交互组件
+                                Node { // 按钮样式
+                                    width: Val::Px(40.),
+                                    height: Val::Px(40.),
+                                    margin: UiRect::all(Val::Px(10.0)),
+                                    justify_content: JustifyContent::Center,
+                                    align_items: AlignItems::Center,
+                                    ..default()
+                                },
+                                ImageNode::new(asset_server.load("image/btn_home.png")),
+                                MenuButtonAction::BackToMainMenu, // 按钮功能标记
+                            ));
+
+                            // 重新开始按钮
+                            parent.spawn((
                                 Button, // 按钮交互组件
                                 Node { // 按钮样式
                                     width: Val::Px(150.),
@@ -180,14 +1098,23 @@ pub fn setup_game_over_menu(mut commands: Commands, asset_server: Res<AssetServe
                                 MenuButtonAction::RestartGame, // 按钮功能标记
                             ));
                         });
+
+                    // 保存录像按钮，网页版无法读写文件时不显示
+                    if !cfg!(target_arch = "wasm32") {
+                        spawn_text_button(parent, "Save Replay", ReplayButtonAction::Save);
+                    }
                 });
         });
 }
 
 /// 设置计分板界面
 /// 
-/// 在游戏界面左上角创建显示分数的文本元素
-pub fn setup_scoreboard(mut commands: Commands, asset_server: Res<AssetServer>) {
+/// 在游戏界面左上角创建显示分数的文本元素，练习模式中隐藏
+pub fn setup_scoreboard(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    practice_mode: Res<PracticeMode>,
+) {
     commands
         .spawn((
             Text::new("Score: "), // 分数标签文本
@@ -203,17 +1130,50 @@ pub fn setup_scoreboard(mut commands: Commands, asset_server: Res<AssetServer>)
                 left: Val::Px(30.0), // 距离左侧30像素
                 ..default()
             },
+            // 练习模式不显示分数，由练习模式提示代替
+            if practice_mode.0 { Visibility::Hidden } else { Visibility::Inherited },
+            OnScoreboard, // 标记为计分板根节点
         ))
-        .with_child(( // 分数值文本子元素
-            TextSpan::new("0"), // 初始分数
-            TextColor(Color::BLACK), // 分数颜色
-            TextFont { // 字体设置
-                font: asset_server.load("fonts/num.ttf"),
-                font_size: 40.0,
-                ..default()
-            },
-            Scoreboard, // 标记为计分板元素
-        ));
+        .with_children(|parent| {
+            parent.spawn(( // 分数值文本子元素
+                TextSpan::new("0"), // 初始分数
+                TextColor(Color::BLACK), // 分数颜色
+                TextFont { // 字体设置
+                    font: asset_server.load("fonts/num.ttf"),
+                    font_size: 40.0,
+                    ..default()
+                },
+                Scoreboard, // 标记为计分板元素
+            ));
+            parent.spawn(( // 剩余重试次数文本子元素，没有重试次数时为空
+                TextSpan::default(),
+                TextColor(Color::BLACK),
+                TextFont {
+                    font_size: 24.0,
+                    ..default()
+                },
+                RetriesText, // 标记为剩余重试次数文本
+            ));
+        });
+}
+
+/// 更新剩余重试次数显示
+/// 
+/// 重试次数变化或计分板重新创建时更新，未开启安全网时不显示
+pub fn update_retries_text(
+    retries: Res<Retries>,
+    safety_net: Res<SafetyNet>,
+    mut q_retries_text: Query<(&mut TextSpan, Ref<RetriesText>)>,
+) {
+    for (mut span, marker) in &mut q_retries_text {
+        if retries.is_changed() || marker.is_added() {
+            span.0 = if safety_net.0 {
+                format!("  Retries: {}", retries.0)
+            } else {
+                String::new()
+            };
+        }
+    }
 }
 
 /// 更新计分板显示
@@ -225,24 +1185,554 @@ pub fn update_scoreboard(score: Res<Score>, mut span: Single<&mut TextSpan, With
     }
 }
 
+/// 分数里程碑检测进度
+#[derive(Debug, Default)]
+pub struct MilestoneTracker {
+    /// 已越过的里程碑数量
+    last: u32,
+    /// 已越过、等待落地后庆祝的里程碑分数
+    pending: Option<u32>,
+}
+
+/// 检测分数里程碑
+/// 
+/// 分数变化时检查是否越过了新的里程碑，每个里程碑只触发一次；
+/// 得分在起跳时就已确定，庆祝效果等到落地后再触发
+pub fn detect_score_milestone(
+    score: Res<Score>,
+    interval: Res<MilestoneInterval>,
+    jump_state: Res<JumpState>,
+    mut game_events: EventWriter<GameEvent>,
+    mut milestone_events: EventWriter<MilestoneEvent>,
+    mut tracker: Local<MilestoneTracker>,
+) {
+    if score.is_changed() {
+        let reached = score.0 / interval.0.max(1);
+        if reached > tracker.last {
+            tracker.pending = Some(reached * interval.0.max(1));
+        }
+        // 重新开始时分数清零，里程碑随之重置
+        tracker.last = reached;
+    }
+
+    if !jump_state.completed {
+        return;
+    }
+    let Some(milestone) = tracker.pending.take() else {
+        return;
+    };
+    info!("Reached score milestone {}", milestone);
+
+    // 高音调的成功音效
+    game_events.send(GameEvent::Milestone);
+    milestone_events.send(MilestoneEvent {
+        pos: jump_state.end_pos,
+    });
+}
+
+/// 达到分数里程碑时开始计分板缩放脉冲
+pub fn start_milestone_pulse(
+    mut commands: Commands,
+    mut milestone_events: EventReader<MilestoneEvent>,
+    q_scoreboard: Query<Entity, With<OnScoreboard>>,
+) {
+    if milestone_events.read().count() == 0 {
+        return;
+    }
+    for scoreboard in &q_scoreboard {
+        commands.entity(scoreboard).insert(MilestonePulse(Timer::from_seconds(
+            MILESTONE_PULSE_SECS,
+            TimerMode::Once,
+        )));
+    }
+}
+
+/// 计分板里程碑脉冲动画
+/// 
+/// 计分板先放大再恢复原始大小，结束后移除脉冲组件
+pub fn animate_milestone_pulse(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut q_pulse: Query<(Entity, &mut Transform, &mut MilestonePulse)>,
+) {
+    for (entity, mut transform, mut pulse) in &mut q_pulse {
+        if pulse.0.tick(time.delta()).finished() {
+            transform.scale = Vec3::ONE;
+            commands.entity(entity).remove::<MilestonePulse>();
+        } else {
+            // 正弦曲线，中间时刻最大放大到1.4倍
+            let scale = 1.0 + 0.4 * (std::f32::consts::PI * pulse.0.fraction()).sin();
+            transform.scale = Vec3::splat(scale);
+        }
+    }
+}
+
+/// 设置开局倒计时界面
+/// 
+/// 在屏幕中央显示"3… 2… 1… Go!"倒计时文本
+pub fn setup_countdown(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((
+        Text::new(COUNTDOWN_SECS.to_string()), // 初始显示倒计时秒数
+        TextColor(Color::BLACK), // 文本颜色
+        TextFont { // 文本字体设置
+            font: asset_server.load("fonts/num.ttf"),
+            font_size: 120.0,
+            ..default()
+        },
+        TextLayout::new_with_justify(JustifyText::Center), // 文本居中
+        Node { // 位置设置，水平居中于屏幕上部
+            position_type: PositionType::Absolute,
+            top: Val::Percent(30.0),
+            width: Val::Percent(100.0),
+            ..default()
+        },
+        OnCountdown, // 标记为倒计时元素
+    ));
+}
+
+/// 更新开局倒计时
+/// 
+/// 推进倒计时计时器并刷新文本，倒计时到达"Go!"后短暂显示然后移除文本
+pub fn update_countdown(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut countdown_timer: ResMut<CountdownTimer>,
+    mut q_countdown: Query<(Entity, &mut Text), With<OnCountdown>>,
+) {
+    countdown_timer.0.tick(time.delta());
+    for (entity, mut text) in &mut q_countdown {
+        if countdown_timer.0.finished() {
+            // "Go!"显示结束，移除倒计时文本
+            commands.entity(entity).despawn();
+        } else if countdown_timer.is_go() {
+            text.0 = "Go!".to_string();
+        } else {
+            // 显示剩余整秒数
+            let remaining = COUNTDOWN_SECS - countdown_timer.0.elapsed_secs();
+            text.0 = (remaining.ceil() as u32).to_string();
+        }
+    }
+}
+
+/// 设置完美落地闪白遮罩
+/// 
+/// 创建覆盖全屏的白色遮罩，默认完全透明
+pub fn setup_perfect_flash(mut commands: Commands) {
+    commands.spawn((
+        Node { // 全屏覆盖
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.),
+            height: Val::Percent(100.),
+            ..default()
+        },
+        BackgroundColor(Color::WHITE.with_alpha(0.0)), // 初始完全透明
+        OnPerfectFlash, // 标记为闪白遮罩
+    ));
+}
+
+/// 更新完美落地闪白
+/// 
+/// 推进闪白计时器，遮罩透明度随剩余时间衰减
+pub fn update_perfect_flash(
+    time: Res<Time>,
+    mut perfect_flash: ResMut<PerfectFlash>,
+    mut q_flash: Query<&mut BackgroundColor, With<OnPerfectFlash>>,
+) {
+    perfect_flash.0.tick(time.delta());
+    let alpha = PERFECT_FLASH_ALPHA * perfect_flash.0.fraction_remaining();
+    for mut background in &mut q_flash {
+        background.0 = Color::WHITE.with_alpha(alpha);
+    }
+}
+
+/// 结束完美落地闪白，避免离开游戏后再次进入时继续显示
+pub fn reset_perfect_flash(mut perfect_flash: ResMut<PerfectFlash>) {
+    *perfect_flash = PerfectFlash::default();
+}
+
+/// 生成暗角纹理
+/// 
+/// 中心完全透明，越靠近四角越黑，透明度按平滑曲线过渡
+fn vignette_image() -> Image {
+    let size = FALL_VIGNETTE_TEXTURE_SIZE;
+    let half = (size - 1) as f32 / 2.0;
+    let data = (0..size * size)
+        .flat_map(|i| {
+            let offset = Vec2::new((i % size) as f32 - half, (i / size) as f32 - half) / half;
+            // 到中心的距离归一化到四角为1，从一半距离处开始变暗
+            let t = ((offset.length() / std::f32::consts::SQRT_2 - 0.5) / 0.5).clamp(0.0, 1.0);
+            let alpha = t * t * (3.0 - 2.0 * t);
+            [0, 0, 0, (alpha * 255.0).round() as u8]
+        })
+        .collect();
+    Image::new(
+        Extent3d {
+            width: size,
+            height: size,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    )
+}
+
+/// 设置摔落暗角遮罩
+/// 
+/// 创建全屏暗角图片，初始完全透明，每局开始时重新创建
+pub fn setup_fall_vignette(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    commands.spawn((
+        ImageNode {
+            image: images.add(vignette_image()),
+            color: Color::WHITE.with_alpha(0.0), // 初始完全透明
+            ..default()
+        },
+        Node { // 全屏覆盖
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.),
+            height: Val::Percent(100.),
+            ..default()
+        },
+        GlobalZIndex(-1), // 位于其他界面元素之下，不遮挡计分板
+        OnFallVignette, // 标记为摔落暗角遮罩
+    ));
+}
+
+/// 更新摔落暗角
+/// 
+/// 摔落过程中暗角随下落深度逐渐加深，摔落结束或重试回到平台后淡出；
+/// 关闭暗角设置时始终透明
+pub fn update_fall_vignette(
+    time: Res<Time>,
+    fall_vignette: Res<FallVignette>,
+    jump_state: Res<JumpState>,
+    fall_state: Res<FallState>,
+    q_player: Query<&Transform, With<Player>>,
+    mut q_vignette: Query<&mut ImageNode, With<OnFallVignette>>,
+) {
+    let falling = fall_vignette.0 && jump_state.completed && !fall_state.completed;
+    let target = match q_player.get_single() {
+        // 从摔落起点到地面的下落比例
+        Ok(player) if falling => {
+            let depth = fall_state.pos.y - player.translation.y;
+            FALL_VIGNETTE_MAX_ALPHA * (depth / fall_state.pos.y.max(0.1)).clamp(0.0, 1.0)
+        }
+        _ => 0.0,
+    };
+    // 指数平滑，与帧率无关
+    let t = 1.0 - (-FALL_VIGNETTE_FADE_SPEED * time.delta_secs()).exp();
+    for mut image in &mut q_vignette {
+        let alpha = image.color.alpha();
+        // 已经到达目标时不修改，避免每帧触发界面更新
+        if alpha == target {
+            continue;
+        }
+        // 接近目标时直接到达，避免无限逼近
+        let new_alpha = if (target - alpha).abs() < 0.005 {
+            target
+        } else {
+            alpha + (target - alpha) * t
+        };
+        image.color.set_alpha(new_alpha);
+    }
+}
+
+/// 设置新手引导提示
+/// 
+/// 还没有看过引导时，在玩家下方显示按住蓄力、松开跳跃的提示
+pub fn setup_tutorial_hint(mut commands: Commands, seen_tutorial: Res<SeenTutorial>) {
+    if seen_tutorial.0 {
+        return;
+    }
+    commands.spawn((
+        Text::new("Press and hold to charge"), // 提示文本
+        TextColor(Color::BLACK), // 文本颜色
+        TextFont { // 文本字体设置
+            font_size: 32.0,
+            ..default()
+        },
+        TextLayout::new_with_justify(JustifyText::Center), // 文本居中
+        Node { // 位置设置，水平居中于屏幕中下部
+            position_type: PositionType::Absolute,
+            top: Val::Percent(60.0),
+            width: Val::Percent(100.0),
+            ..default()
+        },
+        OnTutorialHint, // 标记为新手引导元素
+    ));
+}
+
+/// 更新新手引导提示
+/// 
+/// 提示文本随蓄力状态变化并闪烁；第一次跳跃后移除提示，并记录已看过引导
+pub fn update_tutorial_hint(
+    mut commands: Commands,
+    time: Res<Time>,
+    accumulator: Res<Accumulator>,
+    jump_state: Res<JumpState>,
+    mut seen_tutorial: ResMut<SeenTutorial>,
+    mut q_hint: Query<(Entity, &mut Text, &mut TextColor), With<OnTutorialHint>>,
+) {
+    for (entity, mut text, mut color) in &mut q_hint {
+        // 完成第一次跳跃，引导结束
+        if !jump_state.completed {
+            seen_tutorial.0 = true;
+            commands.entity(entity).despawn();
+            continue;
+        }
+        let hint = if accumulator.0.is_some() {
+            "Release to jump"
+        } else {
+            "Press and hold to charge"
+        };
+        if text.0 != hint {
+            text.0 = hint.to_string();
+        }
+        // 透明度随时间周期变化，形成闪烁效果
+        let alpha = 0.6 + 0.4 * (time.elapsed_secs() * 4.0).sin();
+        color.0 = Color::BLACK.with_alpha(alpha);
+    }
+}
+
+/// 设置蓄力条界面
+/// 
+/// 在屏幕底部中央创建蓄力条，默认隐藏，蓄力时显示
+pub fn setup_charge_bar(mut commands: Commands) {
+    commands
+        .spawn((
+            Node { // 底部全宽容器，用于水平居中蓄力条
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(40.0),
+                width: Val::Percent(100.),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            Visibility::Hidden, // 未蓄力时隐藏
+            OnChargeBar, // 标记为蓄力条元素
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node { // 蓄力条背景
+                        width: Val::Px(200.0),
+                        height: Val::Px(16.0),
+                        border: UiRect::all(Val::Px(2.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(1.0, 1.0, 1.0, 0.5)),
+                    BorderColor(Color::BLACK),
+                    ChargeBarBackground, // 标记为蓄力条背景
+                ))
+                .with_child((
+                    Node { // 蓄力条填充部分，宽度随蓄力时长变化
+                        width: Val::Percent(0.0),
+                        height: Val::Percent(100.0),
+                        ..default()
+                    },
+                    BackgroundColor(Color::hsl(120.0, 0.8, 0.5)),
+                    ChargeBarFill, // 标记为蓄力条填充
+                ));
+        });
+}
+
+/// 更新蓄力条显示
+/// 
+/// 蓄力条长度与蓄力时长成正比（以最大蓄力时长为满），颜色随蓄力程度由绿变红
+pub fn update_charge_bar(
+    accumulator: Res<Accumulator>,
+    max_charge: Res<MaxCharge>,
+    charge_overshoot: Res<ChargeOvershoot>,
+    mut charge_bar: Single<&mut Visibility, With<OnChargeBar>>,
+    mut q_charge_bar_fill: Query<(&mut Node, &mut BackgroundColor), With<ChargeBarFill>>,
+    mut q_charge_bar_border: Query<&mut BorderColor, With<ChargeBarBackground>>,
+) {
+    // 未蓄力时隐藏蓄力条
+    if accumulator.0.is_none() {
+        **charge_bar = Visibility::Hidden;
+        return;
+    }
+    **charge_bar = Visibility::Inherited;
+
+    // 蓄力比例，范围0.0到1.0
+    let ratio = (accumulator.charge_secs(&max_charge) / max_charge.0).clamp(0.0, 1.0);
+    for (mut node, mut background_color) in &mut q_charge_bar_fill {
+        node.width = Val::Percent(ratio * 100.0);
+        // 现在松开会跳过下一个平台时显示为警告红色，否则色相从120（绿色）逐渐过渡到0（红色）
+        background_color.0 = if charge_overshoot.0 {
+            OVERSHOOT_COLOR
+        } else {
+            Color::hsl(120.0 * (1.0 - ratio), 0.8, 0.5)
+        };
+    }
+    // 跳过头时边框同样变红，与接近满蓄力的颜色区分开
+    for mut border_color in &mut q_charge_bar_border {
+        border_color.0 = if charge_overshoot.0 { OVERSHOOT_COLOR } else { Color::BLACK };
+    }
+}
+
+/// 将3D世界坐标投影到屏幕视口坐标
+/// 
+/// # 返回值
+/// 投影失败（位于相机后方等）或投影点不在窗口范围内时返回`None`
+pub fn world_to_screen(
+    camera: &Camera,
+    camera_global_transform: &GlobalTransform,
+    window: &Window,
+    world_pos: Vec3,
+) -> Option<Vec2> {
+    let viewport_pos = camera
+        .world_to_viewport(camera_global_transform, world_pos)
+        .ok()?;
+    let on_screen = (0.0..=window.resolution.width()).contains(&viewport_pos.x)
+        && (0.0..=window.resolution.height()).contains(&viewport_pos.y);
+    on_screen.then_some(viewport_pos)
+}
+
+/// 设置下一个平台指示箭头
+/// 
+/// 箭头由只有上边框和右边框的正方形组成，旋转后指向下一个平台，默认隐藏
+pub fn setup_next_platform_arrow(mut commands: Commands, colorblind_mode: Res<ColorblindMode>) {
+    commands.spawn((
+        Node { // 绝对定位，位置每帧更新
+            position_type: PositionType::Absolute,
+            width: Val::Px(NEXT_PLATFORM_ARROW_SIZE),
+            height: Val::Px(NEXT_PLATFORM_ARROW_SIZE),
+            border: UiRect {
+                top: Val::Px(6.0),
+                right: Val::Px(6.0),
+                ..default()
+            },
+            ..default()
+        },
+        BorderColor(colorblind_mode.highlight_color()), // 高亮颜色随色盲配色模式变化
+        Visibility::Hidden, // 平台在屏幕内时隐藏
+        OnNextPlatformArrow, // 标记为下一个平台指示箭头
+    ));
+}
+
+/// 更新下一个平台指示箭头
+/// 
+/// 下一个平台靠近或超出窗口边缘时，箭头从屏幕中心指向平台的投影位置并限制在窗口边缘内；
+/// 平台完整位于屏幕内时隐藏箭头
+pub fn update_next_platform_arrow(
+    q_next_platform: Query<&GlobalTransform, (With<NextPlatform>, With<AimedPlatform>)>,
+    q_camera: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    q_windows: Query<&Window, With<PrimaryWindow>>,
+    mut q_arrow: Query<(&mut Node, &mut Transform, &mut Visibility), With<OnNextPlatformArrow>>,
+    ui_scale: Res<UiScale>,
+) {
+    let Ok((mut node, mut transform, mut visibility)) = q_arrow.get_single_mut() else {
+        return;
+    };
+    // 缺少平台、相机或窗口时隐藏箭头
+    let (Ok(next_platform), Ok((camera, camera_global_transform)), Ok(window)) = (
+        q_next_platform.get_single(),
+        q_camera.get_single(),
+        q_windows.get_single(),
+    ) else {
+        *visibility = Visibility::Hidden;
+        return;
+    };
+    // 投影失败（例如平台位于相机后方）时无法确定方向，隐藏箭头
+    let Ok(viewport_pos) =
+        camera.world_to_viewport(camera_global_transform, next_platform.translation())
+    else {
+        *visibility = Visibility::Hidden;
+        return;
+    };
+
+    let window_size = Vec2::new(window.resolution.width(), window.resolution.height());
+    let center = window_size / 2.0;
+    let offset = viewport_pos - center;
+    // 平台投影点离窗口边缘足够远时不需要提示
+    let comfortable_extent = center - NEXT_PLATFORM_ON_SCREEN_MARGIN;
+    if offset.x.abs() <= comfortable_extent.x && offset.y.abs() <= comfortable_extent.y {
+        *visibility = Visibility::Hidden;
+        return;
+    }
+
+    // 沿中心到平台的方向缩放，使箭头贴住窗口边缘内侧
+    let arrow_extent = (center - NEXT_PLATFORM_ARROW_MARGIN).max(Vec2::ZERO);
+    let scale_x = if offset.x != 0.0 { arrow_extent.x / offset.x.abs() } else { f32::INFINITY };
+    let scale_y = if offset.y != 0.0 { arrow_extent.y / offset.y.abs() } else { f32::INFINITY };
+    let arrow_pos = center + offset * scale_x.min(scale_y).min(1.0);
+
+    // 视口坐标原点在左上角，与UI坐标一致；UI像素值会乘以缩放倍率，需要先换算
+    node.left = Val::Px(arrow_pos.x / ui_scale.0 - NEXT_PLATFORM_ARROW_SIZE / 2.0);
+    node.top = Val::Px(arrow_pos.y / ui_scale.0 - NEXT_PLATFORM_ARROW_SIZE / 2.0);
+    // 上边框和右边框组成的折角默认指向右上方（-45°），旋转到平台方向
+    let angle = offset.y.atan2(offset.x);
+    transform.rotation = Quat::from_rotation_z(angle + std::f32::consts::FRAC_PI_4);
+    *visibility = Visibility::Inherited;
+}
+
+/// 飘分显示系统参数
+///
+/// 组合飘分的字体、色盲配色、界面缩放以及主相机和主窗口，负责飘分的外观和屏幕定位
+#[derive(SystemParam)]
+pub struct ScoreUpDisplay<'w, 's> {
+    asset_server: Res<'w, AssetServer>,
+    colorblind_mode: Res<'w, ColorblindMode>,
+    ui_scale: Res<'w, UiScale>,
+    q_camera: Query<'w, 's, (&'static Camera, &'static GlobalTransform), With<Camera3d>>,
+    q_windows: Query<'w, 's, &'static Window, With<PrimaryWindow>>,
+}
+
+impl ScoreUpDisplay<'_, '_> {
+    /// 主摄像机和主窗口是否都已存在
+    fn is_ready(&self) -> bool {
+        self.q_camera.get_single().is_ok() && self.q_windows.get_single().is_ok()
+    }
+
+    /// 3D世界坐标对应的飘分节点位置，返回上边距和左边距
+    ///
+    /// 缺少主摄像机或主窗口、投影失败或位于屏幕外时返回`None`
+    fn node_position(&self, world_pos: Vec3) -> Option<(Val, Val)> {
+        let (Ok((camera, camera_global_transform)), Ok(window)) =
+            (self.q_camera.get_single(), self.q_windows.get_single())
+        else {
+            return None;
+        };
+        let viewport_pos = world_to_screen(camera, camera_global_transform, window, world_pos)?;
+        // 注意y轴需要翻转（屏幕坐标系与世界坐标系y轴方向相反）；UI像素值会乘以缩放倍率，需要先换算
+        Some((
+            Val::Px((window.resolution.height() - viewport_pos.y) / self.ui_scale.0),
+            Val::Px(viewport_pos.x / self.ui_scale.0),
+        ))
+    }
+
+    /// 飘分文本颜色，双倍得分时为金色，否则随色盲配色模式变化
+    fn color(&self, bonus: bool) -> Color {
+        if bonus {
+            BONUS_PLATFORM_COLOR
+        } else {
+            self.colorblind_mode.score_up_color()
+        }
+    }
+}
+
 /// 同步飘分效果与3D世界坐标
 /// 
 /// 将3D世界中的位置转换为屏幕坐标，更新飘分UI元素的位置
 pub fn sync_score_up_effect(
-    mut q_score_up_effect: Query<(&mut Node, &mut ScoreUpEffect)>,
-    q_camera: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
-    q_windows: Query<&Window, With<PrimaryWindow>>,
+    mut commands: Commands,
+    mut q_score_up_effect: Query<(Entity, &mut Node, &mut ScoreUpEffect)>,
+    display: ScoreUpDisplay,
 ) {
-    let (camera, camera_global_transform) = q_camera.single(); // 获取主摄像机
-    let window = q_windows.single(); // 获取主窗口
-    for (mut score_up_effect_style, score_up_effect) in &mut q_score_up_effect {
-        // 将3D世界坐标转换为屏幕视口坐标
-        let viewport_pos = camera
-            .world_to_viewport(camera_global_transform, score_up_effect.0)
-            .unwrap();
-        // 更新UI元素位置，注意y轴需要翻转（屏幕坐标系与世界坐标系y轴方向相反）
-        score_up_effect_style.top = Val::Px(window.resolution.height() - viewport_pos.y);
-        score_up_effect_style.left = Val::Px(viewport_pos.x);
+    // 主摄像机和主窗口不存在时跳过本帧
+    if !display.is_ready() {
+        return;
+    }
+    for (entity, mut score_up_effect_style, score_up_effect) in &mut q_score_up_effect {
+        // 将3D世界坐标转换为飘分节点位置
+        // 投影失败或位于屏幕外（例如长时间摔落后位于相机后方）时直接移除飘分效果
+        let Some((top, left)) = display.node_position(score_up_effect.pos) else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+        score_up_effect_style.top = top;
+        score_up_effect_style.left = left;
     }
 }
 
@@ -256,13 +1746,13 @@ pub fn shift_score_up_effect(
 ) {
     for (entity, mut text_color, mut score_up_effect) in &mut q_score_up_effect {
         // 垂直方向向上移动
-        score_up_effect.0.y += 1.0 * time.delta_secs();
+        score_up_effect.pos.y += 1.0 * time.delta_secs();
         // 边移动边增加透明度，实现淡出效果
         let alpha = text_color.0.alpha();
         text_color.0.set_alpha(alpha * 0.97);
         
         // 当飘分到足够高度时，移除该元素
-        if score_up_effect.0.y > INITIAL_PLAYER_POS.y + 1.2 {
+        if score_up_effect.pos.y > score_up_effect.start_y + 0.7 {
             commands.entity(entity).despawn();
         }
     }
@@ -270,46 +1760,58 @@ pub fn shift_score_up_effect(
 
 /// 创建飘分效果
 /// 
-/// 当跳跃完成时，从飘分队列中创建新的飘分UI元素
+/// 读取得分事件，等到跳跃完成落地时再创建飘分UI元素并播放得分音效，
+/// 连击越长音效音调越高
 pub fn spawn_score_up_effect(
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    mut score_up_queue: ResMut<ScoreUpQueue>,
+    mut game_events: EventWriter<GameEvent>,
+    mut score_up_events: EventReader<ScoreUpEvent>,
+    mut pending: Local<Vec<ScoreUpEvent>>,
     jump_state: Res<JumpState>,
-    q_camera: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
-    q_windows: Query<&Window, With<PrimaryWindow>>,
+    display: ScoreUpDisplay,
 ) {
+    // 得分在起跳时就已确定，先暂存事件，落地后再显示
+    pending.extend(score_up_events.read().cloned());
+
     // 只有当跳跃完成时才处理飘分效果
-    if jump_state.completed {
-        let window = q_windows.single();
-        // 为队列中的每个飘分事件创建UI元素
-        for score_up_event in score_up_queue.0.iter_mut() {
-            let (camera, camera_global_transform) = q_camera.single();
-            // 将3D世界坐标转换为屏幕坐标
-            let viewport_pos = camera
-                .world_to_viewport(camera_global_transform, score_up_event.landing_pos)
-                .unwrap();
+    if jump_state.completed && !pending.is_empty() {
+        // 主摄像机和主窗口不存在时留到之后再处理
+        if !display.is_ready() {
+            return;
+        }
+        // 为每个暂存的得分事件创建UI元素
+        for score_up_event in pending.drain(..) {
+            // 得分音效，音调随连击升高
+            game_events.send(GameEvent::ScoreUp {
+                combo: score_up_event.combo,
+            });
+
+            // 将3D世界坐标转换为飘分节点位置，不在屏幕内时跳过该飘分效果
+            let Some((top, left)) = display.node_position(score_up_event.landing_pos) else {
+                continue;
+            };
             
             // 创建飘分文本元素
             commands.spawn((
-                Text::new("+1"), // 分数增量文本
-                TextColor(Color::srgb(0.5, 0.5, 1.0)), // 文本颜色
+                Text::new(format!("+{}", score_up_event.points)), // 分数增量文本
+                TextColor(display.color(score_up_event.bonus)), // 文本颜色
                 TextFont { // 字体设置
-                    font: asset_server.load("fonts/num.ttf"),
+                    font: display.asset_server.load("fonts/num.ttf"),
                     font_size: 40.0,
                     ..default()
                 },
                 Node { // 位置设置
                     position_type: PositionType::Absolute,
-                    top: Val::Px(window.resolution.height() - viewport_pos.y),
-                    left: Val::Px(viewport_pos.x),
+                    top,
+                    left,
                     ..default()
                 },
-                ScoreUpEffect(score_up_event.landing_pos), // 飘分效果组件
+                ScoreUpEffect { // 飘分效果组件
+                    pos: score_up_event.landing_pos,
+                    start_y: score_up_event.landing_pos.y,
+                },
             ));
         }
-        // 清空队列，避免重复处理
-        score_up_queue.0.clear();
     }
 }
 
@@ -317,11 +1819,13 @@ pub fn spawn_score_up_effect(
 /// 
 /// 监听所有菜单按钮的点击事件，并根据按钮功能执行相应操作
 pub fn click_button(
-    mut interaction_query: Query<
-        (&Interaction, &MenuButtonAction),
-        (Changed<Interaction>, With<Button>),
-    >,
-    mut next_game_state: ResMut<NextState<GameState>>,
+    mut interaction_query: ButtonInteractions<MenuButtonAction>,
+    mut state_transition: StateTransition,
+    mut game_mode: ResMut<GameMode>,
+    mut practice_mode: ResMut<PracticeMode>,
+    mut commands: Commands,
+    q_quit_dialog: Query<Entity, With<QuitConfirmDialog>>,
+    mut app_exit: EventWriter<AppExit>,
 ) {
     for (interaction, menu_button_action) in &mut interaction_query {
         // 只有在按钮被按下时处理
@@ -329,15 +1833,74 @@ pub fn click_button(
             Interaction::Pressed => match menu_button_action {
                 MenuButtonAction::StartGame => {
                     info!("StartGame button clicked");
-                    next_game_state.set(GameState::Playing); // 切换到游戏进行状态
+                    *game_mode = GameMode::Endless; // 无尽模式，使用新的随机种子
+                    practice_mode.0 = false; // 正式游戏
+                    state_transition.go(GameState::Playing); // 切换到游戏进行状态
                 }
                 MenuButtonAction::RestartGame => {
                     info!("RestartGame button clicked");
-                    next_game_state.set(GameState::Playing); // 切换到游戏进行状态
+                    state_transition.go(GameState::Playing); // 切换到游戏进行状态
                 }
                 MenuButtonAction::BackToMainMenu => {
                     info!("BackToMainMenu button clicked");
-                    next_game_state.set(GameState::MainMenu); // 切换到主菜单状态
+                    state_transition.go(GameState::MainMenu); // 切换到主菜单状态
+                }
+                MenuButtonAction::Settings => {
+                    info!("Settings button clicked");
+                    state_transition.go(GameState::Settings); // 切换到设置界面状态
+                }
+                MenuButtonAction::DailyChallenge => {
+                    info!("DailyChallenge button clicked");
+                    *game_mode = GameMode::Daily(UtcDate::today()); // 使用当天日期作为种子
+                    practice_mode.0 = false; // 正式游戏
+                    state_transition.go(GameState::Playing); // 切换到游戏进行状态
+                }
+                MenuButtonAction::Leaderboard => {
+                    info!("Leaderboard button clicked");
+                    state_transition.go(GameState::Leaderboard); // 切换到排行榜界面状态
+                }
+                MenuButtonAction::Controls => {
+                    info!("Controls button clicked");
+                    state_transition.go(GameState::Controls); // 切换到按键设置界面状态
+                }
+                MenuButtonAction::BackToSettings => {
+                    info!("BackToSettings button clicked");
+                    state_transition.go(GameState::Settings); // 返回设置界面状态
+                }
+                MenuButtonAction::Practice => {
+                    info!("Practice button clicked");
+                    *game_mode = GameMode::Endless; // 练习使用新的随机种子
+                    practice_mode.0 = true; // 无限重试，不计分
+                    state_transition.go(GameState::Playing); // 切换到游戏进行状态
+                }
+                MenuButtonAction::TimeAttack => {
+                    info!("TimeAttack button clicked");
+                    *game_mode = GameMode::TimeAttack; // 限时挑战，使用新的随机种子
+                    practice_mode.0 = false; // æ­£å¼æ¸¸æ
+                    state_transition.go(GameState::Playing); // åæ¢å°æ¸¸æè¿è¡ç¶æ
+                }
+                MenuButtonAction::ForkedPaths => {
+                    info!("ForkedPaths button clicked");
+                    *game_mode = GameMode::Forked; // 分岔路径挑战，使用新的随机种子
+                    practice_mode.0 = false; // 正式游戏
+                    state_transition.go(GameState::Playing); // 切换到游戏进行状态
+                }
+                MenuButtonAction::Quit => {
+                    info!("Quit button clicked");
+                    // 对话框已打开时不重复生成
+                    if q_quit_dialog.is_empty() {
+                        spawn_quit_confirm_dialog(&mut commands);
+                    }
+                }
+                MenuButtonAction::ConfirmQuit => {
+                    info!("ConfirmQuit button clicked");
+                    app_exit.send(AppExit::Success); // 退出游戏，退出前会写入未保存的设置
+                }
+                MenuButtonAction::CancelQuit => {
+                    info!("CancelQuit button clicked");
+                    for entity in &q_quit_dialog {
+                        commands.entity(entity).despawn_recursive();
+                    }
                 }
             },
             _ => {} // 忽略其他交互状态
@@ -345,6 +1908,189 @@ pub fn click_button(
     }
 }
 
+/// 处理皮肤选择按钮点击事件
+/// 
+/// 在预设皮肤之间循环切换
+pub fn click_skin_button(
+    interaction_query: ButtonInteractions<SkinButtonAction>,
+    mut player_skin: ResMut<PlayerSkin>,
+) {
+    for (interaction, skin_button_action) in &interaction_query {
+        // 只有在按钮被按下时处理
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let index = player_skin.index();
+        let next_index = match skin_button_action {
+            SkinButtonAction::Previous => index + PLAYER_SKINS.len() - 1,
+            SkinButtonAction::Next => index + 1,
+        };
+        *player_skin = PlayerSkin::from_index(next_index);
+    }
+}
+
+/// 更新皮肤名称显示
+pub fn update_skin_text(player_skin: Res<PlayerSkin>, mut q_skin_text: Query<&mut Text, With<SkinText>>) {
+    if player_skin.is_changed() {
+        for mut text in &mut q_skin_text {
+            text.0 = player_skin.name().to_string();
+        }
+    }
+}
+
+/// 设置状态切换遮罩
+/// 
+/// 创建覆盖全屏的黑色遮罩，默认完全透明
+pub fn setup_fade_overlay(mut commands: Commands) {
+    commands.spawn((
+        Node { // 全屏覆盖
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.),
+            height: Val::Percent(100.),
+            ..default()
+        },
+        BackgroundColor(Color::BLACK.with_alpha(0.0)), // 初始完全透明
+        GlobalZIndex(50), // 显示在游戏界面之上、调试信息之下
+        FadeOverlay, // 标记为状态切换遮罩
+    ));
+}
+
+/// 状态切换过渡系统
+/// 
+/// 淡出结束时切换游戏状态并开始淡入，淡入结束后过渡完成；使用真实时间，不受游戏时间影响
+pub fn update_transition(
+    time: Res<Time<Real>>,
+    mut transition: ResMut<Transition>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+    mut q_overlay: Query<&mut BackgroundColor, With<FadeOverlay>>,
+) {
+    let alpha = match transition.phase {
+        TransitionPhase::Idle => 0.0,
+        TransitionPhase::FadeOut => {
+            if transition.timer.tick(time.delta()).finished() {
+                // 完全黑屏时切换状态，OnExit和OnEnter中的场景变化不会被看到
+                if let Some(to) = transition.to.take() {
+                    info!("Transition {:?} -> {:?}", transition.from, to);
+                    next_game_state.set(to);
+                }
+                transition.phase = TransitionPhase::FadeIn;
+                transition.timer.reset();
+                1.0
+            } else {
+                transition.timer.fraction()
+            }
+        }
+        TransitionPhase::FadeIn => {
+            if transition.timer.tick(time.delta()).finished() {
+                transition.phase = TransitionPhase::Idle;
+                transition.from = None;
+                0.0
+            } else {
+                transition.timer.fraction_remaining()
+            }
+        }
+    };
+    for mut background in &mut q_overlay {
+        background.0 = Color::BLACK.with_alpha(alpha);
+    }
+}
+
+/// 运行条件：没有进行中的状态切换过渡
+pub fn transition_finished(transition: Res<Transition>) -> bool {
+    transition.phase == TransitionPhase::Idle
+}
+
+/// 设置调试信息界面
+/// 
+/// 在右上角创建显示帧率的文本，默认隐藏
+pub fn setup_debug_overlay(mut commands: Commands) {
+    commands.spawn((
+        Text::new("FPS: --"), // 帧率文本
+        TextColor(Color::BLACK), // 文本颜色
+        TextFont { // 文本字体设置
+            font_size: 20.0,
+            ..default()
+        },
+        Node { // 位置设置
+            position_type: PositionType::Absolute, // 绝对定位
+            top: Val::Px(8.0), // 距离顶部8像素
+            right: Val::Px(8.0), // 距离右侧8像素
+            ..default()
+        },
+        GlobalZIndex(100), // 显示在其他界面之上
+        Visibility::Hidden, // 默认隐藏
+        DebugOverlay, // 标记为调试信息文本
+    ));
+}
+
+/// 按F3切换调试信息的显示和隐藏
+pub fn toggle_debug_overlay(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut q_overlay: Query<&mut Visibility, With<DebugOverlay>>,
+) {
+    if keys.just_pressed(KeyCode::F3) {
+        for mut visibility in &mut q_overlay {
+            *visibility = match *visibility {
+                Visibility::Hidden => Visibility::Inherited,
+                _ => Visibility::Hidden,
+            };
+        }
+    }
+}
+
+/// 更新调试信息中的帧率显示，隐藏时不更新
+pub fn update_debug_overlay(
+    diagnostics: Res<DiagnosticsStore>,
+    mut q_overlay: Query<(&mut Text, &Visibility), With<DebugOverlay>>,
+) {
+    for (mut text, visibility) in &mut q_overlay {
+        if *visibility == Visibility::Hidden {
+            continue;
+        }
+        if let Some(fps) = diagnostics
+            .get(&FrameTimeDiagnosticsPlugin::FPS)
+            .and_then(|fps| fps.smoothed())
+        {
+            text.0 = format!("FPS: {:.0}", fps);
+        }
+    }
+}
+
+/// 调试命令跳到的目标分数
+#[cfg(debug_assertions)]
+const DEBUG_TARGET_SCORE: u32 = 100;
+
+/// 调试命令：按Ctrl+Shift+J把分数直接提高到目标分数（仅调试构建）
+/// 
+/// 用于测试后期的难度递增和相机缩放；同时移除当前的下一个平台，
+/// 让`generate_next_platform`按新分数对应的难度重新生成。
+/// 只在跳跃、摔落都已完成且未蓄力时生效，连击和连击加速保持不变
+#[cfg(debug_assertions)]
+pub fn debug_jump_to_score(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut score: ResMut<Score>,
+    accumulator: Res<Accumulator>,
+    jump_state: Res<JumpState>,
+    fall_state: Res<crate::player::FallState>,
+    q_next_platform: Query<Entity, With<NextPlatform>>,
+) {
+    let ctrl = keys.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+    let shift = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    if !(ctrl && shift && keys.just_pressed(KeyCode::KeyJ)) {
+        return;
+    }
+    if !jump_state.completed || !fall_state.completed || accumulator.0.is_some() {
+        warn!("Debug score jump ignored while jumping, falling or charging");
+        return;
+    }
+    info!("Debug: jump score from {} to {}", score.0, DEBUG_TARGET_SCORE);
+    score.0 = score.0.max(DEBUG_TARGET_SCORE);
+    for next_platform in &q_next_platform {
+        commands.entity(next_platform).despawn_recursive(); // 递归删除，同时清理中心标记
+    }
+}
+
 /// 清理指定类型的UI界面元素
 /// 
 /// 通用函数，用于在状态切换时移除特定类型的UI元素
@@ -357,15 +2103,294 @@ pub fn despawn_screen<T: Component>(to_despawn: Query<Entity, With<T>>, mut comm
 /// 清理计分板元素
 /// 
 /// 在游戏状态切换时移除计分板
-pub fn despawn_scoreboard(mut commands: Commands, q_scoreboard: Query<Entity, With<Scoreboard>>) {
+pub fn despawn_scoreboard(mut commands: Commands, q_scoreboard: Query<Entity, With<OnScoreboard>>) {
     for scoreboard in &q_scoreboard {
-        commands.entity(scoreboard).despawn();
+        commands.entity(scoreboard).despawn_recursive(); // 递归删除，同时清理分数和重试次数文本
     }
 }
 
 /// 重置游戏分数
 /// 
-/// 在游戏重新开始时将分数重置为0
-pub fn reset_score(mut score: ResMut<Score>) {
-    score.0 = 0;
+/// 在游戏重新开始时将分数、连击、连击加速和本局统计重置为0
+pub fn reset_score(mut score_keeper: ScoreKeeper) {
+    score_keeper.reset();
+}
+
+/// 各模式最高分系统参数
+/// 
+/// 组合无尽模式最高分和排行榜、每日挑战、限时挑战和分岔路径挑战的最高分，游戏结束时按模式更新其中之一
+#[derive(SystemParam)]
+pub struct BestScores<'w> {
+    high_score: ResMut<'w, HighScore>,
+    leaderboard: ResMut<'w, Leaderboard>,
+    daily_high_score: ResMut<'w, DailyHighScore>,
+    time_attack_high_score: ResMut<'w, TimeAttackHighScore>,
+    forked_high_score: ResMut<'w, ForkedHighScore>,
+}
+
+/// 更新最高分
+/// 
+/// 游戏结束时如果本局分数超过最高分则更新最高分，只有无尽模式同时记录到排行榜；
+/// 每日挑战只更新当天的每日最高分，限时挑战和分岔路径挑战只更新各自的最高分；练习模式不记录
+pub fn update_high_score(
+    score: Res<Score>,
+    game_mode: Res<GameMode>,
+    mut best_scores: BestScores,
+    replay_playback: Res<ReplayPlayback>,
+    practice_mode: Res<PracticeMode>,
+) {
+    // 回放录像的分数已在录制时记录过，练习模式不计分
+    if replay_playback.is_active() || practice_mode.0 {
+        return;
+    }
+    match *game_mode {
+        GameMode::Endless => {
+            if score.0 > best_scores.high_score.0 {
+                best_scores.high_score.0 = score.0;
+            }
+            // 0分不计入排行榜
+            if score.0 > 0 {
+                if let Some(rank) = best_scores.leaderboard.insert(score.0) {
+                    info!("New leaderboard entry #{}: {}", rank + 1, score.0);
+                }
+            }
+        }
+        GameMode::Daily(date) => {
+            // 新的一天重新计算每日最高分
+            let daily_high_score = &mut best_scores.daily_high_score;
+            if daily_high_score.date_seed != date.seed() {
+                daily_high_score.date_seed = date.seed();
+                daily_high_score.score = 0;
+            }
+            if score.0 > daily_high_score.score {
+                daily_high_score.score = score.0;
+            }
+        }
+        GameMode::TimeAttack => {
+            if score.0 > best_scores.time_attack_high_score.0 {
+                best_scores.time_attack_high_score.0 = score.0;
+            }
+        }
+        GameMode::Forked => {
+            if score.0 > best_scores.forked_high_score.0 {
+                best_scores.forked_high_score.0 = score.0;
+            }
+        }
+    }
+}
+
+/// 重置限时挑战计时
+/// 
+/// 每局开始时恢复完整的限时挑战时长，其他模式也重置，保证切换模式后从头计时
+pub fn reset_time_attack(mut time_attack: ResMut<TimeAttack>) {
+    time_attack.remaining.reset();
+}
+
+/// 限时挑战剩余时间显示文本
+fn time_attack_label(time_attack: &TimeAttack, high_score: &TimeAttackHighScore) -> String {
+    format!(
+        "Time {:.0}  Best {}",
+        time_attack.remaining.remaining_secs().ceil(),
+        high_score.0,
+    )
+}
+
+/// 设置限时挑战剩余时间显示
+/// 
+/// 限时挑战时在计分板下方显示剩余时间和限时挑战最高分
+pub fn setup_time_attack_label(
+    mut commands: Commands,
+    game_mode: Res<GameMode>,
+    time_attack: Res<TimeAttack>,
+    high_score: Res<TimeAttackHighScore>,
+) {
+    if *game_mode != GameMode::TimeAttack {
+        return;
+    }
+    commands.spawn((
+        Text::new(time_attack_label(&time_attack, &high_score)), // 剩余时间和最高分
+        TextColor(Color::BLACK), // 文本颜色
+        TextFont { // 文本字体设置
+            font_size: 30.0,
+            ..default()
+        },
+        Node { // 位置设置，位于计分板下方
+            position_type: PositionType::Absolute,
+            top: Val::Px(80.0),
+            left: Val::Px(30.0),
+            ..default()
+        },
+        OnTimeAttackLabel, // 标记为限时挑战剩余时间文本
+    ));
+}
+
+/// 限时挑战计时
+/// 
+/// 开局倒计时和入场动画结束后开始计时，时间用完时结束本局；
+/// 使用游戏时间，暂停期间不计时
+pub fn tick_time_attack(
+    time: Res<Time>,
+    jump_gate: JumpGate,
+    mut time_attack: ResMut<TimeAttack>,
+    mut state_transition: StateTransition,
+) {
+    if !jump_gate.is_open() {
+        return;
+    }
+    if time_attack.remaining.tick(time.delta()).just_finished() {
+        info!("Time attack finished");
+        state_transition.go(GameState::GameOver); // 时间用完，切换到游戏结束状态
+    }
+}
+
+/// 更新限时挑战剩余时间显示
+pub fn update_time_attack_label(
+    time_attack: Res<TimeAttack>,
+    high_score: Res<TimeAttackHighScore>,
+    mut q_label: Query<&mut Text, With<OnTimeAttackLabel>>,
+) {
+    if !time_attack.is_changed() {
+        return;
+    }
+    for mut text in &mut q_label {
+        text.0 = time_attack_label(&time_attack, &high_score);
+    }
+}
+
+/// 设置每日挑战日期显示
+/// 
+/// 每日挑战时在计分板下方显示日期和种子
+pub fn setup_daily_label(mut commands: Commands, game_mode: Res<GameMode>) {
+    let GameMode::Daily(date) = *game_mode else {
+        return;
+    };
+    commands.spawn((
+        Text::new(format!("Daily {} (seed {})", date, date.seed())), // 日期和种子
+        TextColor(Color::BLACK), // 文本颜色
+        TextFont { // 文本字体设置
+            font_size: 20.0,
+            ..default()
+        },
+        Node { // 位置设置，位于计分板下方
+            position_type: PositionType::Absolute,
+            top: Val::Px(80.0),
+            left: Val::Px(30.0),
+            ..default()
+        },
+        OnDailyLabel, // 标记为每日挑战日期文本
+    ));
+}
+
+/// 设置练习模式提示
+/// 
+/// 练习模式中在计分板的位置显示练习标记和结束练习按钮，代替分数显示
+pub fn setup_practice_label(mut commands: Commands, practice_mode: Res<PracticeMode>) {
+    if !practice_mode.0 {
+        return;
+    }
+    commands
+        .spawn((
+            Node { // 位置设置，位于计分板的位置
+                position_type: PositionType::Absolute,
+                top: Val::Px(30.0),
+                left: Val::Px(30.0),
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            OnPracticeLabel, // 标记为练习模式提示元素
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("Practice"), // 练习模式标记
+                TextColor(Color::BLACK), // 文本颜色
+                TextFont { // 文本字体设置
+                    font_size: 40.0,
+                    ..default()
+                },
+            ));
+            // 结束练习，返回主菜单
+            spawn_text_button(parent, "End", MenuButtonAction::BackToMainMenu);
+        });
+}
+
+/// 设置排行榜界面
+/// 
+/// 按名次列出无尽模式的前10名分数，还没有记录时显示提示文字
+pub fn setup_leaderboard_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    leaderboard: Res<Leaderboard>,
+) {
+    commands
+        .spawn((
+            Node { // 主容器节点，全屏覆盖
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            OnLeaderboardScreen, // 标记为属于排行榜界面的元素
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((Node { // 垂直排列的内容容器
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },))
+                .with_children(|parent| {
+                    // 标题
+                    parent.spawn((
+                        Text::new("Leaderboard"),
+                        TextColor(Color::BLACK),
+                        TextFont {
+                            font_size: 50.0,
+                            ..default()
+                        },
+                        Node {
+                            margin: UiRect::all(Val::Px(20.0)),
+                            ..default()
+                        },
+                    ));
+
+                    // 首次游戏还没有任何记录
+                    if leaderboard.0.is_empty() {
+                        parent.spawn((
+                            Text::new("No scores yet"),
+                            TextColor(Color::srgb(0.4, 0.4, 0.4)),
+                            TextFont {
+                                font_size: 30.0,
+                                ..default()
+                            },
+                        ));
+                    }
+
+                    // 每个名次一行
+                    for (rank, score) in leaderboard.0.iter().enumerate() {
+                        parent.spawn((
+                            Text::new(format!("{:>2}.  {}", rank + 1, score)),
+                            TextColor(Color::BLACK),
+                            TextFont {
+                                font_size: 30.0,
+                                ..default()
+                            },
+                        ));
+                    }
+
+                    // 返回按钮
+                    parent.spawn((
+                        Button, // 按钮交互组件
+                        Node { // 按钮样式
+                            width: Val::Px(40.),
+                            height: Val::Px(40.),
+                            margin: UiRect::all(Val::Px(20.0)),
+                            ..default()
+                        },
+                        ImageNode::new(asset_server.load("image/btn_back.png")),
+                        MenuButtonAction::BackToMainMenu, // 按钮功能标记
+                    ));
+                });
+        });
 }
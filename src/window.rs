@@ -0,0 +1,197 @@
+// 导入Bevy的主要组件
+use bevy::prelude::*;
+// 导入窗口相关类型，用于设置窗口大小和全屏模式
+use bevy::window::{PrimaryWindow, WindowMode, WindowResolution};
+// 导入帧率限制设置
+use crate::settings::FrameRateLimit;
+// 导入winit窗口映射，用于设置窗口图标（Web平台由页面favicon决定，不需要）
+#[cfg(not(target_arch = "wasm32"))]
+use bevy::winit::WinitWindows;
+
+/// 窗口配置
+///
+/// 在创建应用时用于生成主窗口，之后作为资源保留，供设置图标等系统使用
+#[derive(Debug, Clone, Resource)]
+pub struct WindowConfig {
+    /// 窗口标题
+    pub title: String,
+    /// 默认窗口宽度（逻辑像素）
+    pub width: f32,
+    /// 默认窗口高度（逻辑像素）
+    pub height: f32,
+    /// 窗口图标路径，相对于assets目录
+    pub icon_path: &'static str,
+    /// UI最小缩放倍率，避免小窗口或手机屏幕上文字过小
+    pub min_ui_scale: f32,
+    /// UI最大缩放倍率，避免超大屏幕上按钮过大
+    pub max_ui_scale: f32,
+}
+
+impl Default for WindowConfig {
+    fn default() -> Self {
+        Self {
+            title: "跳一跳 Jump Game".to_string(),
+            width: 1280.0,
+            height: 720.0,
+            icon_path: "image/player.png",
+            min_ui_scale: 0.5,
+            max_ui_scale: 3.0,
+        }
+    }
+}
+
+impl WindowConfig {
+    /// 根据配置生成主窗口
+    ///
+    /// Web平台让画布自动适应父元素大小
+    pub fn window(&self) -> Window {
+        Window {
+            title: self.title.clone(),
+            resolution: WindowResolution::new(self.width, self.height),
+            fit_canvas_to_parent: true,
+            ..default()
+        }
+    }
+
+    /// 根据窗口逻辑尺寸计算UI缩放倍率
+    ///
+    /// UI按默认窗口尺寸布局，取宽高两个方向缩放比例中较小的一个，
+    /// 宽高比与默认窗口不同时UI也不会超出窗口
+    ///
+    /// # 参数
+    /// - `width`: 窗口逻辑宽度
+    /// - `height`: 窗口逻辑高度
+    pub fn ui_scale(&self, width: f32, height: f32) -> f32 {
+        (width / self.width)
+            .min(height / self.height)
+            .clamp(self.min_ui_scale, self.max_ui_scale)
+    }
+}
+
+/// 窗口图标资源，图标加载完成并设置到窗口后移除
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Resource)]
+pub struct WindowIcon(pub Handle<Image>);
+
+/// 加载窗口图标
+#[cfg(not(target_arch = "wasm32"))]
+pub fn setup_window_icon(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    window_config: Res<WindowConfig>,
+) {
+    commands.insert_resource(WindowIcon(asset_server.load(window_config.icon_path)));
+}
+
+/// 设置窗口图标
+///
+/// 等待图标图片加载完成后转换为RGBA数据设置到主窗口，只设置一次
+#[cfg(not(target_arch = "wasm32"))]
+pub fn apply_window_icon(
+    mut commands: Commands,
+    window_icon: Option<Res<WindowIcon>>,
+    images: Res<Assets<Image>>,
+    winit_windows: NonSend<WinitWindows>,
+    q_window: Query<Entity, With<PrimaryWindow>>,
+) {
+    let Some(window_icon) = window_icon else {
+        return;
+    };
+    // 图标尚未加载完成或窗口尚未创建时等待下一帧
+    let Some(image) = images.get(&window_icon.0) else {
+        return;
+    };
+    let Some(window) = q_window
+        .get_single()
+        .ok()
+        .and_then(|entity| winit_windows.get_window(entity))
+    else {
+        return;
+    };
+
+    match image.clone().try_into_dynamic() {
+        Ok(dynamic_image) => {
+            let rgba = dynamic_image.into_rgba8();
+            let (width, height) = rgba.dimensions();
+            match winit::window::Icon::from_rgba(rgba.into_raw(), width, height) {
+                Ok(icon) => window.set_window_icon(Some(icon)),
+                Err(err) => warn!("Failed to create window icon: {}", err),
+            }
+        }
+        Err(err) => warn!("Failed to convert window icon image: {}", err),
+    }
+    commands.remove_resource::<WindowIcon>();
+}
+
+/// 窗口大小变化时更新UI缩放
+///
+/// 菜单和计分板按默认窗口尺寸使用像素布局，缩放后在4K屏幕和小尺寸视口上都保持相同比例；
+/// 窗口尺寸为逻辑像素，高DPI屏幕的缩放因子由Bevy另行处理
+pub fn update_ui_scale(
+    window_config: Res<WindowConfig>,
+    mut ui_scale: ResMut<UiScale>,
+    q_window: Query<&Window, (With<PrimaryWindow>, Changed<Window>)>,
+) {
+    let Ok(window) = q_window.get_single() else {
+        return;
+    };
+    let scale = window_config.ui_scale(window.width(), window.height());
+    // 只在倍率变化时写入，避免每次窗口变化都触发UI重新布局
+    if ui_scale.0 != scale {
+        ui_scale.0 = scale;
+    }
+}
+
+/// 按帧率限制设置更新窗口的垂直同步模式
+///
+/// 每帧比较呈现模式，只在不一致时写入，启动时窗口创建后即按存档设置生效
+pub fn apply_frame_rate_limit(
+    frame_rate_limit: Res<FrameRateLimit>,
+    mut q_window: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    let Ok(mut window) = q_window.get_single_mut() else {
+        return;
+    };
+    let present_mode = frame_rate_limit.present_mode();
+    if window.present_mode != present_mode {
+        window.present_mode = present_mode;
+    }
+}
+
+/// 帧率限制器
+///
+/// 在每帧末尾休眠到目标帧间隔，限制帧率时降低CPU和GPU占用；
+/// 网页版由浏览器驱动帧循环，不能阻塞主线程
+#[cfg(not(target_arch = "wasm32"))]
+pub fn limit_frame_rate(
+    frame_rate_limit: Res<FrameRateLimit>,
+    mut last_frame: Local<Option<std::time::Instant>>,
+) {
+    if let (Some(fps), Some(last_frame)) = (frame_rate_limit.frame_cap(), *last_frame) {
+        let frame_time = std::time::Duration::from_secs_f64(1.0 / fps as f64);
+        let elapsed = last_frame.elapsed();
+        if elapsed < frame_time {
+            std::thread::sleep(frame_time - elapsed);
+        }
+    }
+    *last_frame = Some(std::time::Instant::now());
+}
+
+/// 按F11切换全屏
+///
+/// 在窗口模式和无边框全屏之间切换，Web平台会请求浏览器全屏
+pub fn toggle_fullscreen(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut q_window: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    if !keys.just_pressed(KeyCode::F11) {
+        return;
+    }
+    let Ok(mut window) = q_window.get_single_mut() else {
+        return;
+    };
+    window.mode = match window.mode {
+        WindowMode::Windowed => WindowMode::BorderlessFullscreen(MonitorSelection::Current),
+        _ => WindowMode::Windowed,
+    };
+}